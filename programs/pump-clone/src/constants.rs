@@ -0,0 +1,7 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+
+/// Raydium AMM v4, the only pool program `graduate_token` migrates into
+pub const RAYDIUM_AMM_PROGRAM_ID: Pubkey = pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+```
\ No newline at end of file
@@ -0,0 +1,132 @@
+```rust
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum PumpError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Insufficient tokens")]
+    InsufficientTokens,
+    #[msg("Insufficient token amount")]
+    InsufficientTokenAmount,
+    #[msg("Insufficient token reserves")]
+    InsufficientTokenReserves,
+    #[msg("Insufficient SOL in vault")]
+    InsufficientSolVault,
+    #[msg("Bonding curve is already complete")]
+    BondingCurveComplete,
+    #[msg("Invalid calculation")]
+    InvalidCalculation,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Name is too long")]
+    NameTooLong,
+    #[msg("Symbol is too long")]
+    SymbolTooLong,
+    #[msg("URI is too long")]
+    UriTooLong,
+    #[msg("Only one buy and sell cycle per slot is allowed for this token")]
+    SelfTradeLimitExceeded,
+    #[msg("Authority has reached the maximum number of markets")]
+    MarketLimitReached,
+    #[msg("Order receipt account is required when a client order id is given")]
+    MissingOrderReceipt,
+    #[msg("Fee curve exceeds the maximum trade fee")]
+    InvalidFeeCurve,
+    #[msg("SOL vault has not been initialized")]
+    SolVaultNotInitialized,
+    #[msg("Market ends too soon")]
+    MarketDurationTooShort,
+    #[msg("Market ends too far in the future")]
+    MarketDurationTooLong,
+    #[msg("Sell would take the SOL vault below its rent-exempt minimum")]
+    VaultRentFloorReached,
+    #[msg("A wallet cannot refer itself")]
+    SelfReferral,
+    #[msg("Referral accounts do not match the buyer's referral chain")]
+    InvalidReferralAccounts,
+    #[msg("Name registry account is required while unique token names are enforced")]
+    MissingNameRegistry,
+    #[msg("Depth scaling needs a multiplier of at least 2")]
+    InvalidDepthScaling,
+    #[msg("URI must start with https:// or ipfs://")]
+    InvalidUri,
+    #[msg("Initial liquidity is below the configured minimum")]
+    InsufficientInitialLiquidity,
+    #[msg("Floor vault and creator token account are required for a floor sell")]
+    MissingFloorAccounts,
+    #[msg("Floor price must be set before depositing a floor reserve")]
+    InvalidFloorPrice,
+    #[msg("Trade slippage against spot exceeds the protocol cap")]
+    SlippageCapExceeded,
+    #[msg("Buy exceeds the anti-snipe cap for a newly launched token")]
+    SnipeCapExceeded,
+    #[msg("Trading window must end after it starts")]
+    InvalidTradingWindow,
+    #[msg("Filled token amount differs from the quote")]
+    QuoteNotHonored,
+    #[msg("Royalty exceeds the maximum")]
+    RoyaltyTooHigh,
+    #[msg("Creator token account must not be the bonding curve's token account")]
+    CreatorAccountIsCurveAccount,
+    #[msg("Destination is not the configured treasury")]
+    TreasuryMismatch,
+    #[msg("Too many markets are open")]
+    OpenMarketLimitReached,
+    #[msg("Fee token account is not a valid partner fee account")]
+    InvalidPartnerFeeAccount,
+    #[msg("Description is too long")]
+    DescriptionTooLong,
+    #[msg("Market mint is not an allowed quote currency")]
+    QuoteMintNotAllowed,
+    #[msg("Allowed quote mint list is full")]
+    QuoteMintListFull,
+    #[msg("Creator has launched the maximum total token supply")]
+    CreatorSupplyLimitReached,
+    #[msg("Token metadata can only be skipped in builds with the skip-metadata feature")]
+    MetadataRequired,
+    #[msg("TWAP sell schedule is invalid")]
+    InvalidTwapSchedule,
+    #[msg("Next TWAP slice is not due yet")]
+    TwapSliceTooEarly,
+    #[msg("TWAP sell has no slices left")]
+    TwapSellComplete,
+    #[msg("A TWAP sell is already in progress")]
+    TwapSellInProgress,
+    #[msg("Buyer token account must be the buyer's account for the traded mint")]
+    InvalidBuyerTokenAccount,
+    #[msg("Graduation into a program-owned pool is disabled")]
+    InternalPoolGraduationDisabled,
+    #[msg("Bonding curve has not reached its graduation threshold")]
+    GraduationNotReached,
+    #[msg("Not enough holders to graduate")]
+    InsufficientHolders,
+    #[msg("Pool needs both token and SOL liquidity")]
+    InsufficientPoolLiquidity,
+    #[msg("Pool swap output below the minimum")]
+    PoolSlippageExceeded,
+    #[msg("This token's self-trade guard requires the trade guard account")]
+    MissingTradeGuard,
+    #[msg("Token has already graduated")]
+    TokenAlreadyGraduated,
+    #[msg("Insufficient liquidity for graduation")]
+    InsufficientLiquidity,
+    #[msg("Curve lamports moved by other than the migrated amount and reward")]
+    LamportAccountingMismatch,
+    #[msg("Graduation goes to the program-owned pool; use graduate_to_pool")]
+    InternalPoolGraduation,
+    #[msg("SOL vault already backs the curve's reserves")]
+    VaultsAlreadyReconciled,
+    #[msg("Rewards have not vested yet")]
+    RewardNotVested,
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+    #[msg("Token already launched; retrying create_token can't launch it again")]
+    TokenAlreadyExists,
+    #[msg("Order receipt account given without a client order id")]
+    UnexpectedOrderReceipt,
+}
+
+/// Name used by the buy path for the same error set
+pub type PumpCloneError = PumpError;
+```
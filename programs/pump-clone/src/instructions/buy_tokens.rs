@@ -1,10 +1,13 @@
 ```rust
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::utils::math::{MathUtils, BPS_DIVISOR};
 
 #[derive(Accounts)]
+#[instruction(sol_amount: u64, client_order_id: Option<u64>)]
 pub struct BuyTokens<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
@@ -34,43 +37,254 @@ pub struct BuyTokens<'info> {
     )]
     pub buyer_token_account: Account<'info, TokenAccount>,
     
+    /// Only required on curves with `self_trade_guard` on, so other traders pay no rent for it
     #[account(
-        mut,
-        seeds = [b"sol_vault"],
+        init_if_needed,
+        payer = buyer,
+        space = TradeGuard::LEN,
+        seeds = [b"trade_guard", token_mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub trade_guard: Option<Account<'info, TradeGuard>>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = HolderStats::LEN,
+        seeds = [b"holder_stats", token_mint.key().as_ref(), buyer.key().as_ref()],
         bump,
     )]
+    pub holder_stats: Account<'info, HolderStats>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = OrderReceipt::LEN,
+        seeds = [
+            b"order_receipt",
+            buyer.key().as_ref(),
+            token_mint.key().as_ref(),
+            client_order_id.unwrap_or_default().to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub order_receipt: Option<Account<'info, OrderReceipt>>,
+    
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bonding_curve.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
     pub sol_vault: SystemAccount<'info>,
     
+    #[account(mut, address = bonding_curve.creator)]
+    pub creator: SystemAccount<'info>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    #[account(seeds = [b"referral", buyer.key().as_ref()], bump = buyer_referral.bump)]
+    pub buyer_referral: Option<Account<'info, ReferralAccount>>,
+    
+    /// The referrer's own referral link, only needed for two-level payouts
+    pub referrer_referral: Option<Account<'info, ReferralAccount>>,
+    
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+    
+    #[account(mut)]
+    pub grandparent_referrer: Option<SystemAccount<'info>>,
+    
+    /// The buyer's partner-token account, to pay the trade fee in the Config partner mint
+    #[account(mut, token::authority = buyer)]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Receives partner-token fees; must belong to the Config treasury
+    #[account(mut)]
+    pub treasury_fee_token_account: Option<Account<'info, TokenAccount>>,
+    
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn buy_tokens(ctx: Context<BuyTokens>, sol_amount: u64) -> Result<()> {
+pub fn buy_tokens(
+    ctx: Context<BuyTokens>,
+    sol_amount: u64,
+    client_order_id: Option<u64>,
+    clamp_to_graduation: bool,
+) -> Result<()> {
+    execute_buy(ctx, sol_amount, client_order_id, clamp_to_graduation, None)
+}
+
+/// Buy exactly the `quoted_tokens_out` a client quoted for `sol_amount`. If the reserves
+/// have moved since the quote, the buy is rejected instead of filling at a different amount.
+pub fn quote_then_buy(
+    ctx: Context<BuyTokens>,
+    sol_amount: u64,
+    client_order_id: Option<u64>,
+    quoted_tokens_out: u64,
+) -> Result<()> {
+    execute_buy(ctx, sol_amount, client_order_id, false, Some(quoted_tokens_out))
+}
+
+fn execute_buy(
+    ctx: Context<BuyTokens>,
+    sol_amount: u64,
+    client_order_id: Option<u64>,
+    clamp_to_graduation: bool,
+    quoted_tokens_out: Option<u64>,
+) -> Result<()> {
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     
     require!(sol_amount > 0, PumpCloneError::InvalidAmount);
-    require!(!bonding_curve.is_complete, PumpCloneError::BondingCurveComplete);
+    require!(!bonding_curve.complete, PumpCloneError::BondingCurveComplete);
+    require!(bonding_curve.sol_vault_initialized, PumpCloneError::SolVaultNotInitialized);
+    bonding_curve.check_buys_enabled()?;
+    bonding_curve.check_trading_window(Clock::get()?.unix_timestamp)?;
+    check_buyer_token_account(
+        ctx.accounts.buyer_token_account.owner,
+        ctx.accounts.buyer_token_account.mint,
+        ctx.accounts.buyer.key(),
+        ctx.accounts.token_mint.key(),
+    )?;
+    ctx.accounts.config.check_snipe_cap(bonding_curve.launch_slot, Clock::get()?.slot, sol_amount)?;
+    
+    // A retried order that already filled succeeds without buying again, returning the original fill
+    check_order_receipt(client_order_id, ctx.accounts.order_receipt.is_some())?;
+    if let (Some(order_id), Some(order_receipt)) = (client_order_id, ctx.accounts.order_receipt.as_ref()) {
+        if order_receipt.is_duplicate(order_id, Clock::get()?.unix_timestamp) {
+            msg!("Order {} already filled, skipping", order_id);
+            set_return_data(&order_receipt.fill.try_to_vec()?);
+            return Ok(());
+        }
+    }
+    
+    if bonding_curve.self_trade_guard {
+        let trade_guard = ctx.accounts.trade_guard
+            .as_mut()
+            .ok_or(PumpCloneError::MissingTradeGuard)?;
+        trade_guard.wallet = ctx.accounts.buyer.key();
+        trade_guard.mint = ctx.accounts.token_mint.key();
+        trade_guard.bump = ctx.bumps.trade_guard;
+        trade_guard.record_trade(Clock::get()?.slot, true)?;
+    }
+    
+    let fee_exempt = bonding_curve.is_fee_exempt(&ctx.accounts.buyer.key())
+        || ctx.accounts.config.is_fee_free(sol_amount);
+    let creator_fee_bps = if fee_exempt {
+        0
+    } else {
+        bonding_curve.effective_creator_fee_bps(ctx.accounts.config.max_creator_fee_bps)
+    };
+    let trade_fee_bps = if fee_exempt {
+        0
+    } else {
+        bonding_curve.impact_adjusted_fee_bps(
+            bonding_curve.trade_fee_bps()?,
+            sol_amount,
+            ctx.accounts.config.impact_fee_coefficient,
+        )?
+    };
+    
+    // Some(has_grandparent) when the buyer was referred
+    let referral = match ctx.accounts.buyer_referral.as_ref().filter(|r| r.has_referrer()) {
+        Some(buyer_referral) => {
+            let referrer = ctx.accounts.referrer
+                .as_ref()
+                .ok_or(PumpCloneError::InvalidReferralAccounts)?;
+            require_keys_eq!(referrer.key(), buyer_referral.referrer, PumpCloneError::InvalidReferralAccounts);
+            
+            let has_grandparent = match (&ctx.accounts.referrer_referral, &ctx.accounts.grandparent_referrer) {
+                (Some(referrer_referral), Some(grandparent)) => {
+                    require_keys_eq!(referrer_referral.owner, referrer.key(), PumpCloneError::InvalidReferralAccounts);
+                    require_keys_eq!(grandparent.key(), referrer_referral.referrer, PumpCloneError::InvalidReferralAccounts);
+                    referrer_referral.has_referrer()
+                }
+                _ => false,
+            };
+            Some(has_grandparent)
+        }
+        None => None,
+    };
+    
+    // A buyer paying in the partner token settles the curve's share of the trade fee there;
+    // referrers are still paid their share in SOL
+    let pays_partner_fee = match (&ctx.accounts.fee_token_account, &ctx.accounts.treasury_fee_token_account) {
+        (Some(fee_token_account), Some(treasury_fee_token_account)) if trade_fee_bps > 0 => {
+            let config = &ctx.accounts.config;
+            require!(config.accepts_partner_fee(&fee_token_account.mint), PumpCloneError::InvalidPartnerFeeAccount);
+            require_keys_eq!(treasury_fee_token_account.mint, fee_token_account.mint, PumpCloneError::InvalidPartnerFeeAccount);
+            config.check_treasury(treasury_fee_token_account.owner)?;
+            true
+        }
+        _ => false,
+    };
+    
+    let referral_levels = ctx.accounts.config.referral_levels;
+    let breakdown = |gross: u64| -> Result<(BuyBreakdown, u64)> {
+        if pays_partner_fee {
+            partner_fee_breakdown(gross, creator_fee_bps, trade_fee_bps, referral_levels, referral)
+        } else {
+            Ok((buy_breakdown(gross, creator_fee_bps, trade_fee_bps, referral_levels, referral)?, 0))
+        }
+    };
+    
+    // Only take as much SOL as it needs to land exactly on the graduation threshold
+    let sol_amount = if clamp_to_graduation {
+        let remaining = bonding_curve.remaining_to_graduation();
+        let graduation_mode = bonding_curve.graduation_mode;
+        let (sol_reserves, token_reserves) =
+            (bonding_curve.virtual_sol_reserves, bonding_curve.virtual_token_reserves);
+        let clamped = clamp_to_graduation_amount(sol_amount, remaining, |gross| -> Result<u64> {
+            let (breakdown, _) = breakdown(gross)?;
+            match graduation_mode {
+                GraduationMode::SolThreshold => Ok(breakdown.curve_sol_amount),
+                GraduationMode::SupplySold => {
+                    calculate_token_amount_out(sol_reserves, token_reserves, breakdown.swap_sol_amount)
+                }
+            }
+        })?;
+        if clamped < sol_amount {
+            msg!("Buy clamped to {} lamports, {} left with the buyer", clamped, sol_amount - clamped);
+        }
+        clamped
+    } else {
+        sol_amount
+    };
+    
+    let (BuyBreakdown { creator_fee, trade_fee, referral_split, swap_sol_amount, curve_sol_amount }, replaced_fee) =
+        breakdown(sol_amount)?;
+    let partner_fee = if pays_partner_fee {
+        ctx.accounts.config.partner_fee_amount(replaced_fee)?
+    } else {
+        0
+    };
     
     // Calculate token amount based on bonding curve
-    let token_amount = calculate_token_amount_out(
+    let token_amount = quote_buy(
         bonding_curve.virtual_sol_reserves,
         bonding_curve.virtual_token_reserves,
-        sol_amount,
+        swap_sol_amount,
     )?;
-    
-    require!(token_amount > 0, PumpCloneError::InsufficientTokenAmount);
+    if let Some(quoted_tokens_out) = quoted_tokens_out {
+        check_quote(quoted_tokens_out, token_amount)?;
+    }
+    ctx.accounts.config.check_slippage(bonding_curve.tokens_at_spot(swap_sol_amount)?, token_amount)?;
     
     // Check if purchase would complete the bonding curve
     let new_sol_reserves = bonding_curve.virtual_sol_reserves
-        .checked_add(sol_amount)
+        .checked_add(curve_sol_amount)
         .ok_or(PumpCloneError::MathOverflow)?;
     
     let new_token_reserves = bonding_curve.virtual_token_reserves
         .checked_sub(token_amount)
         .ok_or(PumpCloneError::InsufficientTokenReserves)?;
     
+    let price_before = bonding_curve.spot_price()?;
+    let reserve_share_bps = reserve_share_bps(token_amount, bonding_curve.real_token_reserves);
+    
     // Transfer SOL from buyer to vault
     let transfer_sol_ix = anchor_lang::system_program::Transfer {
         from: ctx.accounts.buyer.to_account_info(),
@@ -82,14 +296,63 @@ pub fn buy_tokens(ctx: Context<BuyTokens>, sol_amount: u64) -> Result<()> {
             ctx.accounts.system_program.to_account_info(),
             transfer_sol_ix,
         ),
-        sol_amount,
+        curve_sol_amount,
     )?;
     
+    if creator_fee > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            creator_fee,
+        )?;
+        bonding_curve.record_creator_fee(creator_fee)?;
+    }
+    
+    for (payee, amount) in [
+        (&ctx.accounts.referrer, referral_split.referrer),
+        (&ctx.accounts.grandparent_referrer, referral_split.grandparent),
+    ] {
+        if let Some(payee) = payee.as_ref().filter(|_| amount > 0) {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to: payee.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+    }
+    
+    if let (Some(fee_token_account), Some(treasury_fee_token_account)) = (
+        ctx.accounts.fee_token_account.as_ref().filter(|_| partner_fee > 0),
+        ctx.accounts.treasury_fee_token_account.as_ref(),
+    ) {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: fee_token_account.to_account_info(),
+                    to: treasury_fee_token_account.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            partner_fee,
+        )?;
+    }
+    
     // Transfer tokens from bonding curve to buyer
-    let bonding_curve_key = ctx.accounts.bonding_curve.key();
+    let mint_key = ctx.accounts.token_mint.key();
     let seeds = &[
         b"bonding_curve",
-        ctx.accounts.token_mint.key().as_ref(),
+        mint_key.as_ref(),
         &[bonding_curve.bump],
     ];
     let signer_seeds = &[&seeds[..]];
@@ -99,31 +362,65 @@ pub fn buy_tokens(ctx: Context<BuyTokens>, sol_amount: u64) -> Result<()> {
         Transfer {
             from: ctx.accounts.bonding_curve_token_account.to_account_info(),
             to: ctx.accounts.buyer_token_account.to_account_info(),
-            authority: ctx.accounts.bonding_curve.to_account_info(),
+            authority: bonding_curve.to_account_info(),
         },
         signer_seeds,
     );
     
+    let balance_before = ctx.accounts.buyer_token_account.amount;
     token::transfer(transfer_tokens_ctx, token_amount)?;
     
+    if let Some(quoted_tokens_out) = quoted_tokens_out {
+        ctx.accounts.buyer_token_account.reload()?;
+        check_exact_fill(quoted_tokens_out, balance_before, ctx.accounts.buyer_token_account.amount)?;
+    }
+    
     // Update bonding curve state
     bonding_curve.virtual_sol_reserves = new_sol_reserves;
     bonding_curve.virtual_token_reserves = new_token_reserves;
     bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves
-        .checked_add(sol_amount)
+        .checked_add(curve_sol_amount)
         .ok_or(PumpCloneError::MathOverflow)?;
     bonding_curve.real_token_reserves = bonding_curve.real_token_reserves
         .checked_sub(token_amount)
         .ok_or(PumpCloneError::InsufficientTokenReserves)?;
+    bonding_curve.check_virtual_sol_backing()?;
+    
+    // Every event of this trade carries the same sequence number
+    let sequence = bonding_curve.next_sequence()?;
     
     // Check if bonding curve is complete
-    if new_sol_reserves >= bonding_curve.complete_sol_threshold {
-        bonding_curve.is_complete = true;
-        
+    if bonding_curve.check_completion(Clock::get()?.unix_timestamp)? {
         emit!(BondingCurveCompleteEvent {
             token_mint: ctx.accounts.token_mint.key(),
             final_sol_reserves: new_sol_reserves,
             final_token_reserves: new_token_reserves,
+            sequence,
+        });
+    }
+    
+    let depth_rescaled = bonding_curve.count_trade()?;
+    bonding_curve.record_volume(curve_sol_amount)?;
+    
+    let holder_stats = &mut ctx.accounts.holder_stats;
+    holder_stats.wallet = ctx.accounts.buyer.key();
+    holder_stats.mint = ctx.accounts.token_mint.key();
+    holder_stats.bump = ctx.bumps.holder_stats;
+    let was_holder = holder_stats.token_balance > 0;
+    holder_stats.start_holding(Clock::get()?.unix_timestamp);
+    holder_stats.record_buy(token_amount, sol_amount)?;
+    bonding_curve.update_holder_count(was_holder, holder_stats.token_balance > 0)?;
+    
+    emit!(bonding_curve.reserves_updated_event(Clock::get()?.slot, sequence));
+    
+    if is_high_impact(reserve_share_bps, ctx.accounts.config.high_impact_threshold_bps) {
+        emit!(HighImpactTradeEvent {
+            buyer: ctx.accounts.buyer.key(),
+            token_mint: ctx.accounts.token_mint.key(),
+            token_amount,
+            reserve_share_bps,
+            price_impact_bps: MathUtils::calculate_slippage(price_before, bonding_curve.spot_price()?)?,
+            sequence,
         });
     }
     
@@ -132,13 +429,208 @@ pub fn buy_tokens(ctx: Context<BuyTokens>, sol_amount: u64) -> Result<()> {
         token_mint: ctx.accounts.token_mint.key(),
         sol_amount,
         token_amount,
-        new_sol_reserves,
-        new_token_reserves,
+        creator_fee,
+        curve_sol_amount,
+        // Read back after `count_trade`, which may have deepened the virtual reserves
+        new_sol_reserves: bonding_curve.virtual_sol_reserves,
+        new_token_reserves: bonding_curve.virtual_token_reserves,
+        real_sol_reserves: bonding_curve.real_sol_reserves,
+        real_token_reserves: bonding_curve.real_token_reserves,
+        mint_decimals: ctx.accounts.token_mint.decimals,
+        depth_rescaled,
+        sequence,
     });
     
+    let fee = creator_fee
+        .checked_add(trade_fee)
+        .ok_or(PumpCloneError::MathOverflow)?;
+    let order_filled = bonding_curve.order_filled(token_amount, sol_amount, fee)?;
+    if let (Some(order_id), Some(order_receipt)) = (client_order_id, ctx.accounts.order_receipt.as_mut()) {
+        order_receipt.record(ctx.accounts.buyer.key(), order_id, Clock::get()?.unix_timestamp, order_filled);
+    }
+    set_return_data(&order_filled.try_to_vec()?);
+    
     Ok(())
 }
 
+/// Where the SOL of a buy goes
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct BuyBreakdown {
+    creator_fee: u64,
+    trade_fee: u64,
+    referral_split: ReferralSplit,
+    /// The part of the deposit that buys tokens
+    swap_sol_amount: u64,
+    /// Deposited into the curve's reserves: the trade fee minus referral payouts, plus the SOL that buys tokens
+    curve_sol_amount: u64,
+}
+
+/// Split a gross buy into fees and the curve deposit. `referral` is `Some(has_grandparent)`
+/// for a referred buyer.
+fn buy_breakdown(
+    sol_amount: u64,
+    creator_fee_bps: u16,
+    trade_fee_bps: u64,
+    referral_levels: u8,
+    referral: Option<bool>,
+) -> Result<BuyBreakdown> {
+    // With no fees there is nothing to split, so skip the fee math and the whole buy swaps
+    if creator_fee_bps == 0 && trade_fee_bps == 0 {
+        return Ok(BuyBreakdown {
+            creator_fee: 0,
+            trade_fee: 0,
+            referral_split: ReferralSplit::default(),
+            swap_sol_amount: sol_amount,
+            curve_sol_amount: sol_amount,
+        });
+    }
+    
+    fee_breakdown(sol_amount, creator_fee_bps, trade_fee_bps, referral_levels, referral)
+}
+
+fn fee_breakdown(
+    sol_amount: u64,
+    creator_fee_bps: u16,
+    trade_fee_bps: u64,
+    referral_levels: u8,
+    referral: Option<bool>,
+) -> Result<BuyBreakdown> {
+    // Creator fee comes off the top; the rest goes into the curve
+    let creator_fee = MathUtils::calculate_fee(sol_amount, creator_fee_bps as u64)?;
+    let curve_sol_amount = sol_amount
+        .checked_sub(creator_fee)
+        .ok_or(PumpCloneError::MathOverflow)?;
+    
+    // The trade fee stays in the curve's reserves but buys no tokens
+    let trade_fee = MathUtils::calculate_fee(curve_sol_amount, trade_fee_bps)?;
+    let swap_sol_amount = curve_sol_amount
+        .checked_sub(trade_fee)
+        .ok_or(PumpCloneError::MathOverflow)?;
+    
+    // Referrers are paid out of the trade fee, so less of it reaches the curve
+    let referral_split = match referral {
+        Some(has_grandparent) => split_referral_fee(trade_fee, referral_levels, has_grandparent)?,
+        None => ReferralSplit::default(),
+    };
+    let curve_sol_amount = curve_sol_amount
+        .checked_sub(referral_split.total()?)
+        .ok_or(PumpCloneError::MathOverflow)?;
+    
+    Ok(BuyBreakdown { creator_fee, trade_fee, referral_split, swap_sol_amount, curve_sol_amount })
+}
+
+/// Split a buy whose trade fee is paid in the partner token. Referrers still get their SOL
+/// share of the trade fee; the part the curve would have kept is charged in the partner token
+/// instead, so the rest of the deposit all buys tokens. Also returns that replaced SOL fee.
+fn partner_fee_breakdown(
+    sol_amount: u64,
+    creator_fee_bps: u16,
+    trade_fee_bps: u64,
+    referral_levels: u8,
+    referral: Option<bool>,
+) -> Result<(BuyBreakdown, u64)> {
+    let sol_path = fee_breakdown(sol_amount, creator_fee_bps, trade_fee_bps, referral_levels, referral)?;
+    let referral_fee = sol_path.referral_split.total()?;
+    let replaced_fee = sol_path
+        .trade_fee
+        .checked_sub(referral_fee)
+        .ok_or(PumpCloneError::MathOverflow)?;
+    
+    let breakdown = BuyBreakdown {
+        trade_fee: referral_fee,
+        swap_sol_amount: sol_path.curve_sol_amount,
+        ..sol_path
+    };
+    Ok((breakdown, replaced_fee))
+}
+
+/// Smallest buy whose progress toward graduation reaches `remaining`, or `sol_amount` if it
+/// doesn't overshoot. `progress` is the curve deposit in lamports, or the tokens bought for
+/// a `SupplySold` curve.
+fn clamp_to_graduation_amount(
+    sol_amount: u64,
+    remaining: u64,
+    progress: impl Fn(u64) -> Result<u64>,
+) -> Result<u64> {
+    if progress(sol_amount)? <= remaining {
+        return Ok(sol_amount);
+    }
+    
+    // Progress grows with the buy size, so binary search for the smallest one that reaches the threshold
+    let (mut low, mut high) = (0u64, sol_amount);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if progress(mid)? >= remaining {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    
+    Ok(high)
+}
+
+/// Tokens the curve pays out for `swap_sol_amount` at the given reserves
+fn quote_buy(sol_reserves: u64, token_reserves: u64, swap_sol_amount: u64) -> Result<u64> {
+    let token_amount = calculate_token_amount_out(sol_reserves, token_reserves, swap_sol_amount)?;
+    
+    require!(token_amount > 0, PumpCloneError::InsufficientTokenAmount);
+    Ok(token_amount)
+}
+
+/// Tokens must land in the buyer's own account for the traded mint. The ATA constraint
+/// already ensures this; checked again so a relaxed constraint can't misroute a fill.
+fn check_buyer_token_account(
+    owner: Pubkey,
+    mint: Pubkey,
+    buyer: Pubkey,
+    token_mint: Pubkey,
+) -> Result<()> {
+    require_keys_eq!(owner, buyer, PumpCloneError::InvalidBuyerTokenAccount);
+    require_keys_eq!(mint, token_mint, PumpCloneError::InvalidBuyerTokenAccount);
+    Ok(())
+}
+
+/// An order receipt comes with a client order id and only with one. A receipt without an id
+/// would be shared by every such buy as order 0.
+fn check_order_receipt(client_order_id: Option<u64>, has_receipt: bool) -> Result<()> {
+    match (client_order_id, has_receipt) {
+        (Some(_), false) => err!(PumpCloneError::MissingOrderReceipt),
+        (None, true) => err!(PumpCloneError::UnexpectedOrderReceipt),
+        _ => Ok(()),
+    }
+}
+
+/// The curve must still pay what the client quoted; any drift means the quote is stale
+fn check_quote(quoted_tokens_out: u64, token_amount: u64) -> Result<()> {
+    require!(token_amount == quoted_tokens_out, PumpCloneError::QuoteNotHonored);
+    Ok(())
+}
+
+/// The buyer's balance must have grown by exactly the quoted amount
+fn check_exact_fill(quoted: u64, balance_before: u64, balance_after: u64) -> Result<()> {
+    let filled = balance_after
+        .checked_sub(balance_before)
+        .ok_or(PumpCloneError::MathOverflow)?;
+    
+    require!(filled == quoted, PumpCloneError::QuoteNotHonored);
+    Ok(())
+}
+
+/// Share of the curve's real token reserves a buy takes, in basis points
+fn reserve_share_bps(token_amount: u64, real_token_reserves: u64) -> u64 {
+    if real_token_reserves == 0 {
+        return BPS_DIVISOR;
+    }
+    
+    let share = (token_amount as u128) * (BPS_DIVISOR as u128) / (real_token_reserves as u128);
+    share.min(BPS_DIVISOR as u128) as u64
+}
+
+fn is_high_impact(reserve_share_bps: u64, threshold_bps: u16) -> bool {
+    threshold_bps > 0 && reserve_share_bps >= threshold_bps as u64
+}
+
 fn calculate_token_amount_out(
     sol_reserves: u64,
     token_reserves: u64,
@@ -172,8 +664,18 @@ pub struct TokenPurchaseEvent {
     pub token_mint: Pubkey,
     pub sol_amount: u64,
     pub token_amount: u64,
+    pub creator_fee: u64,
+    /// Part of `sol_amount` that entered the curve's reserves
+    pub curve_sol_amount: u64,
+    /// Virtual reserves after the trade
     pub new_sol_reserves: u64,
     pub new_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub mint_decimals: u8,
+    /// Whether this trade deepened the virtual token reserves by the curve's depth multiplier
+    pub depth_rescaled: bool,
+    pub sequence: u64,
 }
 
 #[event]
@@ -181,5 +683,219 @@ pub struct BondingCurveCompleteEvent {
     pub token_mint: Pubkey,
     pub final_sol_reserves: u64,
     pub final_token_reserves: u64,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct HighImpactTradeEvent {
+    pub buyer: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_amount: u64,
+    pub reserve_share_bps: u64,
+    pub price_impact_bps: u64,
+    pub sequence: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buy_crossing_impact_threshold_is_flagged() {
+        let real_token_reserves = 800_000_000_000_000;
+        let threshold = DEFAULT_HIGH_IMPACT_THRESHOLD_BPS;
+
+        let small = reserve_share_bps(real_token_reserves / 20, real_token_reserves);
+        let at_threshold = reserve_share_bps(real_token_reserves / 10, real_token_reserves);
+        let large = reserve_share_bps(real_token_reserves / 4, real_token_reserves);
+
+        assert_eq!(small, 500);
+        assert_eq!(at_threshold, 1_000);
+        assert_eq!(large, 2_500);
+        assert!(!is_high_impact(small, threshold));
+        assert!(is_high_impact(at_threshold, threshold));
+        assert!(is_high_impact(large, threshold));
+    }
+
+    #[test]
+    fn test_zero_threshold_never_flags() {
+        assert!(!is_high_impact(BPS_DIVISOR, 0));
+    }
+
+    #[test]
+    fn test_purchase_event_carries_decimals() {
+        let event = TokenPurchaseEvent {
+            buyer: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            sol_amount: 1_000,
+            token_amount: 35_000,
+            creator_fee: 0,
+            curve_sol_amount: 1_000,
+            new_sol_reserves: 30_000_001_000,
+            new_token_reserves: 1_072_999_999_965_000,
+            real_sol_reserves: 1_000,
+            real_token_reserves: 799_999_999_965_000,
+            mint_decimals: 9,
+            depth_rescaled: false,
+            sequence: 1,
+        };
+
+        let decoded = TokenPurchaseEvent::try_from_slice(&event.try_to_vec().unwrap()).unwrap();
+        assert_eq!(decoded.mint_decimals, 9);
+    }
+
+    #[test]
+    fn test_overshooting_buy_clamped_to_graduation() {
+        let deposit = |gross: u64| -> Result<u64> {
+            Ok(buy_breakdown(gross, 100, 100, 2, Some(true))?.curve_sol_amount)
+        };
+        let remaining = 5_000_000_000;
+        let sol_amount = 20_000_000_000;
+
+        let clamped = clamp_to_graduation_amount(sol_amount, remaining, deposit).unwrap();
+
+        assert!(clamped < sol_amount);
+        assert!(deposit(clamped).unwrap() >= remaining);
+        assert!(deposit(clamped - 1).unwrap() < remaining);
+        // The excess is never taken from the buyer
+        assert!(sol_amount - clamped > 14_000_000_000);
+    }
+
+    #[test]
+    fn test_supply_sold_buy_clamped_to_token_target() {
+        let (sol_reserves, token_reserves) = (30_000_000_000, 1_073_000_000_000_000);
+        let tokens_bought = |gross: u64| -> Result<u64> {
+            let breakdown = buy_breakdown(gross, 100, 100, 1, None)?;
+            calculate_token_amount_out(sol_reserves, token_reserves, breakdown.swap_sol_amount)
+        };
+        let remaining = 100_000_000_000_000;
+        let sol_amount = 20_000_000_000;
+
+        let clamped = clamp_to_graduation_amount(sol_amount, remaining, tokens_bought).unwrap();
+
+        assert!(clamped < sol_amount);
+        assert!(tokens_bought(clamped).unwrap() >= remaining);
+        assert!(tokens_bought(clamped - 1).unwrap() < remaining);
+    }
+
+    #[test]
+    fn test_buy_within_graduation_not_clamped() {
+        let deposit = |gross: u64| -> Result<u64> {
+            Ok(buy_breakdown(gross, 100, 100, 1, None)?.curve_sol_amount)
+        };
+
+        assert_eq!(clamp_to_graduation_amount(1_000_000_000, 5_000_000_000, deposit).unwrap(), 1_000_000_000);
+    }
+
+    #[test]
+    fn test_buy_breakdown_accounts_for_every_lamport() {
+        let breakdown = buy_breakdown(10_000, 100, 100, 2, Some(true)).unwrap();
+
+        assert_eq!(breakdown.creator_fee, 100);
+        assert_eq!(breakdown.trade_fee, 99);
+        assert_eq!(breakdown.referral_split, ReferralSplit { referrer: 19, grandparent: 4 });
+        assert_eq!(breakdown.swap_sol_amount, 9_801);
+        assert_eq!(
+            breakdown.creator_fee + breakdown.referral_split.total().unwrap() + breakdown.curve_sol_amount,
+            10_000
+        );
+    }
+
+    #[test]
+    fn test_stale_quote_rejected() {
+        let (sol_reserves, token_reserves) = (30_000_000_000, 1_073_000_000_000_000);
+        let breakdown = buy_breakdown(1_000_000_000, 100, 100, 1, None).unwrap();
+        let quoted = quote_buy(sol_reserves, token_reserves, breakdown.swap_sol_amount).unwrap();
+        assert!(check_quote(quoted, quoted).is_ok());
+
+        // Another buy lands between the quote and the fill
+        let other = quote_buy(sol_reserves, token_reserves, 2_000_000_000).unwrap();
+        let filled = quote_buy(sol_reserves + 2_000_000_000, token_reserves - other, breakdown.swap_sol_amount).unwrap();
+
+        assert!(filled < quoted);
+        assert!(check_quote(quoted, filled).is_err());
+        assert!(check_quote(quoted, quoted + 1).is_err());
+    }
+
+    #[test]
+    fn test_short_fill_rejected() {
+        assert!(check_exact_fill(35_000, 100, 100 + 34_999).is_err());
+        assert!(check_exact_fill(35_000, 100, 100 + 35_001).is_err());
+    }
+
+    #[test]
+    fn test_sol_fee_path_keeps_trade_fee_in_sol() {
+        let breakdown = buy_breakdown(1_000_000_000, 100, 100, 1, None).unwrap();
+
+        assert_eq!(breakdown.trade_fee, 9_900_000);
+        assert_eq!(breakdown.swap_sol_amount, 980_100_000);
+    }
+
+    #[test]
+    fn test_partner_fee_path_moves_trade_fee_to_partner_token() {
+        // No trade fee comes out of the SOL, so more of it buys tokens
+        let (breakdown, replaced_fee) = partner_fee_breakdown(1_000_000_000, 100, 100, 1, None).unwrap();
+        assert_eq!(breakdown.trade_fee, 0);
+        assert_eq!(breakdown.swap_sol_amount, 990_000_000);
+        assert_eq!(breakdown.curve_sol_amount, 990_000_000);
+
+        // The SOL fee it replaces is the one the SOL path would have paid
+        assert_eq!(replaced_fee, 9_900_000);
+    }
+
+    #[test]
+    fn test_partner_fee_path_still_pays_referrers() {
+        let sol_path = buy_breakdown(1_000_000_000, 100, 100, 2, Some(true)).unwrap();
+        let (breakdown, replaced_fee) = partner_fee_breakdown(1_000_000_000, 100, 100, 2, Some(true)).unwrap();
+
+        // Referrers get the same SOL either way; only the curve's share moves to the partner token
+        assert_eq!(breakdown.referral_split, sol_path.referral_split);
+        assert!(breakdown.referral_split.total().unwrap() > 0);
+        assert_eq!(replaced_fee + breakdown.referral_split.total().unwrap(), sol_path.trade_fee);
+        assert_eq!(
+            breakdown.creator_fee + breakdown.referral_split.total().unwrap() + breakdown.curve_sol_amount,
+            1_000_000_000
+        );
+        assert_eq!(breakdown.swap_sol_amount, breakdown.curve_sol_amount);
+    }
+
+    #[test]
+    fn test_fee_free_fast_path_matches_fee_math() {
+        for sol_amount in [1, 999, 1_000_000_000, 85_000_000_000] {
+            for referral in [None, Some(false), Some(true)] {
+                assert_eq!(
+                    buy_breakdown(sol_amount, 0, 0, 2, referral).unwrap(),
+                    fee_breakdown(sol_amount, 0, 0, 2, referral).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_mismatched_buyer_token_account_rejected() {
+        let (buyer, token_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let invalid: Error = PumpCloneError::InvalidBuyerTokenAccount.into();
+
+        assert!(check_buyer_token_account(buyer, token_mint, buyer, token_mint).is_ok());
+        assert_eq!(
+            check_buyer_token_account(Pubkey::new_unique(), token_mint, buyer, token_mint).unwrap_err(),
+            invalid
+        );
+        assert_eq!(
+            check_buyer_token_account(buyer, Pubkey::new_unique(), buyer, token_mint).unwrap_err(),
+            invalid
+        );
+    }
+
+    #[test]
+    fn test_order_receipt_requires_order_id_and_vice_versa() {
+        assert!(check_order_receipt(Some(7), true).is_ok());
+        assert!(check_order_receipt(None, false).is_ok());
+
+        let missing: Error = PumpCloneError::MissingOrderReceipt.into();
+        assert_eq!(check_order_receipt(Some(7), false).unwrap_err(), missing);
+        let unexpected: Error = PumpCloneError::UnexpectedOrderReceipt.into();
+        assert_eq!(check_order_receipt(None, true).unwrap_err(), unexpected);
+    }
 }
 ```
\ No newline at end of file
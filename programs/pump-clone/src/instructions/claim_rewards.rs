@@ -0,0 +1,43 @@
+```rust
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reward", owner.key().as_ref()],
+        bump = reward_account.bump,
+        has_one = owner,
+    )]
+    pub reward_account: Account<'info, RewardAccount>,
+}
+
+/// Pay out the owner's graduation rewards once they have vested
+pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let amount = ctx.accounts.reward_account.claim(now)?;
+
+    // The reward account is program-owned, so its lamports move without a CPI
+    **ctx.accounts.reward_account.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    emit!(RewardsClaimedEvent {
+        owner: ctx.accounts.owner.key(),
+        amount,
+        claimed_at: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RewardsClaimedEvent {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+}
+```
\ No newline at end of file
@@ -3,60 +3,83 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{
-        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
+        create_metadata_accounts_v3,
+        mpl_token_metadata::{types::DataV2, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH},
+        CreateMetadataAccountsV3,
         Metadata,
     },
-    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+    token::{initialize_mint2, mint_to, InitializeMint2, Mint, MintTo, Token},
 };
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount};
+use anchor_spl::associated_token::get_associated_token_address;
 
 use crate::state::*;
 use crate::errors::*;
 
+/// Decimals of every mint created by the launchpad
+pub const TOKEN_DECIMALS: u8 = 6;
+
+/// Highest royalty a creator can set on secondary sales, 10%
+pub const MAX_ROYALTY_BPS: u16 = 1_000;
+
 #[derive(Accounts)]
-#[instruction(name: String, symbol: String, uri: String)]
+#[instruction(name: String, symbol: String, uri: String, self_trade_guard: bool, creator_fee_bps: u16, creator_fee_exempt: bool, fee_curve: Option<FeeCurve>, mutable_metadata: bool, depth_scaling: Option<DepthScaling>, trading_start: i64, trading_end: i64, burn_remainder: bool, creator_sell_locked_until_graduation: bool, graduation_mode: GraduationMode, royalty_bps: u16, skip_metadata: bool)]
 pub struct CreateToken<'info> {
+    /// CHECK: Created by the handler once it has checked the token isn't launched yet
     #[account(
-        init,
-        payer = creator,
-        mint::decimals = 6,
-        mint::authority = bonding_curve,
-        mint::freeze_authority = bonding_curve,
+        mut,
         seeds = [b"mint", creator.key().as_ref(), name.as_bytes()],
         bump
     )]
-    pub mint: Account<'info, Mint>,
+    pub mint: UncheckedAccount<'info>,
 
+    /// CHECK: Created by the handler once it has checked the token isn't launched yet
     #[account(
-        init,
-        payer = creator,
-        space = 8 + BondingCurve::INIT_SPACE,
+        mut,
         seeds = [b"bonding_curve", mint.key().as_ref()],
         bump
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub bonding_curve: UncheckedAccount<'info>,
+
+    /// CHECK: The curve's associated token account, created by the handler
+    #[account(mut, address = get_associated_token_address(&bonding_curve.key(), &mint.key()))]
+    pub bonding_curve_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: The creator's associated token account, created by the handler
+    #[account(mut, address = get_associated_token_address(&creator.key(), &mint.key()))]
+    pub creator_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
     #[account(
         init,
         payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
+        space = NameRegistry::LEN,
+        seeds = [b"name_registry", normalize_token_name(&name).as_bytes()],
+        bump
     )]
-    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+    pub name_registry: Option<Account<'info, NameRegistry>>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = creator,
+        space = AuthorityStats::LEN,
+        seeds = [b"authority_stats", creator.key().as_ref()],
+        bump
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub authority_stats: Account<'info, AuthorityStats>,
 
-    /// CHECK: This is not dangerous because we don't read or write from this account
+    /// Receives the launch fee; required only while Config charges one
     #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub creator: Signer<'info>,
+    pub treasury: Option<SystemAccount<'info>>,
 
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
@@ -65,19 +88,137 @@ pub struct CreateToken<'info> {
     pub token_metadata_program: Program<'info, Metadata>,
 }
 
+/// Launches are not idempotent: retrying one that already landed fails with
+/// `TokenAlreadyExists`. The mint, curve and their token accounts are created here rather
+/// than through `init` so that check runs before anything is created.
+///
+/// Metadata is locked at launch unless the creator passes `mutable_metadata`, which
+/// leaves the bonding curve able to update it.
+#[allow(clippy::too_many_arguments)]
 pub fn create_token(
     ctx: Context<CreateToken>,
     name: String,
     symbol: String,
     uri: String,
+    self_trade_guard: bool,
+    creator_fee_bps: u16,
+    creator_fee_exempt: bool,
+    fee_curve: Option<FeeCurve>,
+    mutable_metadata: bool,
+    depth_scaling: Option<DepthScaling>,
+    trading_start: i64,
+    trading_end: i64,
+    burn_remainder: bool,
+    creator_sell_locked_until_graduation: bool,
+    graduation_mode: GraduationMode,
+    royalty_bps: u16,
+    skip_metadata: bool,
 ) -> Result<()> {
-    require!(name.len() <= 32, PumpError::NameTooLong);
-    require!(symbol.len() <= 10, PumpError::SymbolTooLong);
-    require!(uri.len() <= 200, PumpError::UriTooLong);
+    check_not_launched(ctx.accounts.mint.owner, ctx.accounts.bonding_curve.owner)?;
+    let create_metadata = metadata_required(skip_metadata, cfg!(feature = "skip-metadata"))?;
+    // Metaplex stores these with fixed byte limits
+    check_text_len(&name, MAX_TOKEN_NAME_LEN, PumpError::NameTooLong)?;
+    check_text_len(&symbol, MAX_SYMBOL_LENGTH, PumpError::SymbolTooLong)?;
+    check_text_len(&uri, MAX_URI_LENGTH, PumpError::UriTooLong)?;
+
+    let fee_curve = fee_curve.unwrap_or_default();
+    require!(
+        fee_curve.start_bps <= MAX_TRADE_FEE_BPS && fee_curve.end_bps <= MAX_TRADE_FEE_BPS,
+        PumpError::InvalidFeeCurve
+    );
+
+    let depth_scaling = depth_scaling.unwrap_or_default();
+    require!(depth_scaling.is_valid(), PumpError::InvalidDepthScaling);
+    require!(
+        trading_end == 0 || trading_end > trading_start,
+        PumpError::InvalidTradingWindow
+    );
+
+    // Reserve the name; a taken name already has a registry account, so its init fails
+    if ctx.accounts.config.unique_token_names {
+        let name_registry = ctx.accounts.name_registry
+            .as_mut()
+            .ok_or(PumpError::MissingNameRegistry)?;
+        name_registry.name = normalize_token_name(&name);
+        name_registry.mint = ctx.accounts.mint.key();
+        name_registry.creator = ctx.accounts.creator.key();
+        name_registry.bump = ctx.bumps.name_registry;
+    }
+
+    let launch_fee = ctx.accounts.config.launch_fee_due(ctx.accounts.treasury.as_ref().map(|t| t.key()))?;
+    if let Some(treasury) = ctx.accounts.treasury.as_ref().filter(|_| launch_fee > 0) {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            launch_fee,
+        )?;
+    }
+
+    check_creator_token_account(
+        ctx.accounts.creator_token_account.key(),
+        ctx.accounts.bonding_curve_token_account.key(),
+        ctx.accounts.creator.key(),
+        ctx.accounts.bonding_curve.key(),
+    )?;
 
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
     let mint = &ctx.accounts.mint;
     let creator = &ctx.accounts.creator;
+    let curve_info = ctx.accounts.bonding_curve.to_account_info();
+    let creator_key = creator.key();
+    let mint_key = mint.key();
+    let mint_seeds: &[&[u8]] = &[b"mint", creator_key.as_ref(), name.as_bytes(), &[ctx.bumps.mint]];
+    let curve_seeds: &[&[u8]] = &[b"bonding_curve", mint_key.as_ref(), &[ctx.bumps.bonding_curve]];
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+
+    create_pda(
+        creator,
+        mint,
+        Mint::LEN,
+        &Token::id(),
+        mint_seeds,
+        &system_program_info,
+    )?;
+    initialize_mint2(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            InitializeMint2 { mint: mint.to_account_info() },
+        ),
+        TOKEN_DECIMALS,
+        &curve_info.key(),
+        Some(&curve_info.key()),
+    )?;
+    create_pda(
+        creator,
+        &curve_info,
+        BondingCurve::LEN,
+        ctx.program_id,
+        curve_seeds,
+        &system_program_info,
+    )?;
+    for (token_account, authority) in [
+        (&ctx.accounts.bonding_curve_token_account, curve_info.clone()),
+        (&ctx.accounts.creator_token_account, creator.to_account_info()),
+    ] {
+        anchor_spl::associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            anchor_spl::associated_token::Create {
+                payer: creator.to_account_info(),
+                associated_token: token_account.to_account_info(),
+                authority,
+                mint: mint.to_account_info(),
+                system_program: system_program_info.clone(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+    }
+
+    // The freshly allocated account is all zeroes; it is written back once fully set up
+    let mut bonding_curve = BondingCurve::try_deserialize_unchecked(&mut &curve_info.try_borrow_data()?[..])?;
 
     // Initialize bonding curve
     bonding_curve.mint = mint.key();
@@ -88,45 +229,95 @@ pub fn create_token(
     bonding_curve.real_sol_reserves = 0;
     bonding_curve.token_total_supply = 1_000_000_000_000_000; // 1B tokens
     bonding_curve.complete = false;
+    bonding_curve.self_trade_guard = self_trade_guard;
+    bonding_curve.creator_fee_bps = creator_fee_bps.min(ctx.accounts.config.max_creator_fee_bps);
+    bonding_curve.creator_fee_exempt = creator_fee_exempt;
+    bonding_curve.fee_curve = fee_curve;
+    bonding_curve.depth_scaling = depth_scaling;
+    bonding_curve.trade_count = 0;
+    bonding_curve.creator_fees_accrued = 0;
+    bonding_curve.floor_price = 0;
+    bonding_curve.floor_reserve = 0;
+    bonding_curve.created_at = Clock::get()?.unix_timestamp;
+    bonding_curve.launch_slot = Clock::get()?.slot;
+    bonding_curve.trading_start = trading_start;
+    bonding_curve.trading_end = trading_end;
+    bonding_curve.burn_remainder = burn_remainder;
+    bonding_curve.holder_count = 0;
+    bonding_curve.creator_sell_locked_until_graduation = creator_sell_locked_until_graduation;
+    bonding_curve.graduation_mode = graduation_mode;
+    bonding_curve.graduating = false;
+    bonding_curve.graduated = false;
+    bonding_curve.graduation_timestamp = 0;
+    bonding_curve.amm_pool = Pubkey::default();
+    bonding_curve.total_sol_volume = 0;
+    bonding_curve.buys_enabled = true;
+    bonding_curve.sells_enabled = true;
+    bonding_curve.graduation_threshold = DEFAULT_GRADUATION_THRESHOLD;
+    bonding_curve.check_graduation_threshold(
+        bonding_curve.graduation_threshold,
+        ctx.accounts.config.min_graduation_sol,
+    )?;
+    bonding_curve.initial_virtual_token_reserves = bonding_curve.virtual_token_reserves;
+    bonding_curve.initial_virtual_sol_reserves = bonding_curve.virtual_sol_reserves;
     bonding_curve.bump = ctx.bumps.bonding_curve;
 
-    // Create metadata
-    let metadata_ctx = CpiContext::new(
-        ctx.accounts.token_metadata_program.to_account_info(),
-        CreateMetadataAccountsV3 {
-            metadata: ctx.accounts.metadata.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            mint_authority: ctx.accounts.bonding_curve.to_account_info(),
-            update_authority: ctx.accounts.bonding_curve.to_account_info(),
-            payer: ctx.accounts.creator.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
-        },
+    // Count the supply against the creator's lifetime total
+    let authority_stats = &mut ctx.accounts.authority_stats;
+    if authority_stats.authority == Pubkey::default() {
+        authority_stats.authority = creator.key();
+        authority_stats.bump = ctx.bumps.authority_stats;
+    }
+    authority_stats.record_launch(
+        bonding_curve.token_total_supply,
+        ctx.accounts.config.max_creator_total_supply,
+    )?;
+
+    // Store canonical vault bumps so trades never re-derive them
+    let bonding_curve_key = curve_info.key();
+    let (_, sol_vault_bump) = Pubkey::find_program_address(
+        &[b"sol_vault", bonding_curve_key.as_ref()],
+        ctx.program_id,
     );
+    let (_, curve_vault_bump) = Pubkey::find_program_address(
+        &[b"curve_vault", bonding_curve_key.as_ref()],
+        ctx.program_id,
+    );
+    let (_, floor_vault_bump) = Pubkey::find_program_address(
+        &[b"floor_vault", bonding_curve_key.as_ref()],
+        ctx.program_id,
+    );
+    bonding_curve.sol_vault_bump = sol_vault_bump;
+    bonding_curve.curve_vault_bump = curve_vault_bump;
+    bonding_curve.floor_vault_bump = floor_vault_bump;
 
-    let mint_key = ctx.accounts.mint.key();
-    let seeds = &[
-        b"bonding_curve",
-        mint_key.as_ref(),
-        &[ctx.bumps.bonding_curve],
-    ];
-    let signer = &[&seeds[..]];
-
-    create_metadata_accounts_v3(
-        metadata_ctx.with_signer(signer),
-        DataV2 {
-            name,
-            symbol,
-            uri,
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None,
-            uses: None,
-        },
-        false,
-        true,
-        None,
-    )?;
+    let signer = &[curve_seeds];
+
+    // Create metadata
+    if create_metadata {
+        let metadata_ctx = CpiContext::new(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                mint_authority: curve_info.clone(),
+                update_authority: curve_info.clone(),
+                payer: ctx.accounts.creator.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+        );
+
+        create_metadata_accounts_v3(
+            metadata_ctx.with_signer(signer),
+            token_metadata(name, symbol, uri, royalty_bps)?,
+            mutable_metadata,
+            true,
+            None,
+        )?;
+    } else {
+        msg!("Skipping token metadata");
+    }
 
     // Mint initial supply to creator (20% of total supply)
     let initial_creator_supply = bonding_curve.token_total_supply / 5; // 20%
@@ -136,7 +327,7 @@ pub fn create_token(
         MintTo {
             mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.creator_token_account.to_account_info(),
-            authority: ctx.accounts.bonding_curve.to_account_info(),
+            authority: curve_info.clone(),
         },
     );
 
@@ -145,23 +336,139 @@ pub fn create_token(
     // Update bonding curve reserves
     bonding_curve.real_token_reserves = bonding_curve.token_total_supply - initial_creator_supply;
 
+    // Mint the rest into the curve's token account, which buys are filled from
+    let curve_mint_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            authority: curve_info.clone(),
+        },
+    );
+
+    mint_to(curve_mint_ctx.with_signer(signer), bonding_curve.real_token_reserves)?;
+    bonding_curve.initial_real_token_reserves = bonding_curve.real_token_reserves;
+    bonding_curve.try_serialize(&mut &mut curve_info.try_borrow_mut_data()?[..])?;
+
     emit!(TokenCreated {
         mint: mint.key(),
         creator: creator.key(),
         name: ctx.accounts.metadata.key().to_string(),
         symbol: ctx.accounts.metadata.key().to_string(),
         uri: ctx.accounts.metadata.key().to_string(),
-        bonding_curve: bonding_curve.key(),
+        bonding_curve: curve_info.key(),
         virtual_token_reserves: bonding_curve.virtual_token_reserves,
         virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
         real_token_reserves: bonding_curve.real_token_reserves,
         real_sol_reserves: bonding_curve.real_sol_reserves,
         token_total_supply: bonding_curve.token_total_supply,
+        mint_decimals: TOKEN_DECIMALS,
+        royalty_bps,
+        launch_fee,
     });
 
     Ok(())
 }
 
+/// A launch's mint and bonding curve are still unowned system accounts until it lands
+fn check_not_launched(mint_owner: &Pubkey, bonding_curve_owner: &Pubkey) -> Result<()> {
+    require!(
+        *mint_owner == system_program::ID && *bonding_curve_owner == system_program::ID,
+        PumpError::TokenAlreadyExists
+    );
+    Ok(())
+}
+
+/// Create a PDA owned by `owner` the way `init` does, including when someone has already
+/// sent it lamports
+fn create_pda<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent_minimum = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+    if lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount { from: payer.clone(), to: account.clone() },
+                &[seeds],
+            ),
+            rent_minimum,
+            space as u64,
+            owner,
+        );
+    }
+
+    if rent_minimum > lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer { from: payer.clone(), to: account.clone() },
+            ),
+            rent_minimum - lamports,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate { account_to_allocate: account.clone() },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign { account_to_assign: account.clone() },
+            &[seeds],
+        ),
+        owner,
+    )
+}
+
+/// Metadata for a new token; `royalty_bps` is the seller fee marketplaces charge on resales
+fn token_metadata(name: String, symbol: String, uri: String, royalty_bps: u16) -> Result<DataV2> {
+    require!(royalty_bps <= MAX_ROYALTY_BPS, PumpError::RoyaltyTooHigh);
+
+    Ok(DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: royalty_bps,
+        creators: None,
+        collection: None,
+        uses: None,
+    })
+}
+
+/// The creator's 20% must not land in the curve's own token account, so neither the
+/// accounts nor their owners may coincide
+fn check_creator_token_account(
+    creator_token_account: Pubkey,
+    bonding_curve_token_account: Pubkey,
+    creator: Pubkey,
+    bonding_curve: Pubkey,
+) -> Result<()> {
+    require_keys_neq!(creator_token_account, bonding_curve_token_account, PumpError::CreatorAccountIsCurveAccount);
+    require_keys_neq!(creator, bonding_curve, PumpError::CreatorAccountIsCurveAccount);
+    Ok(())
+}
+
+/// Whether to create token metadata. Test validators may lack the metadata program, so
+/// builds with the `skip-metadata` feature let the creator opt out; release builds always
+/// create it.
+fn metadata_required(skip_metadata: bool, skip_allowed: bool) -> Result<bool> {
+    if skip_metadata {
+        require!(skip_allowed, PumpError::MetadataRequired);
+    }
+    Ok(!skip_metadata)
+}
+
 #[event]
 pub struct TokenCreated {
     pub mint: Pubkey,
@@ -175,5 +482,79 @@ pub struct TokenCreated {
     pub real_token_reserves: u64,
     pub real_sol_reserves: u64,
     pub token_total_supply: u64,
+    pub mint_decimals: u8,
+    pub royalty_bps: u16,
+    pub launch_fee: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_created_event_carries_decimals() {
+        let event = TokenCreated {
+            mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            uri: "https://example.com".to_string(),
+            bonding_curve: Pubkey::new_unique(),
+            virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 800_000_000_000_000,
+            real_sol_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            mint_decimals: TOKEN_DECIMALS,
+            royalty_bps: 500,
+            launch_fee: 100_000_000,
+        };
+
+        let decoded = TokenCreated::try_from_slice(&event.try_to_vec().unwrap()).unwrap();
+        assert_eq!(decoded.mint_decimals, 6);
+        assert_eq!(decoded.royalty_bps, 500);
+        assert_eq!(decoded.launch_fee, 100_000_000);
+    }
+
+    #[test]
+    fn test_royalty_lands_in_metadata() {
+        let metadata = token_metadata("Token".to_string(), "TKN".to_string(), "https://example.com".to_string(), 250).unwrap();
+        assert_eq!(metadata.seller_fee_basis_points, 250);
+
+        assert!(token_metadata(String::new(), String::new(), String::new(), MAX_ROYALTY_BPS).is_ok());
+        assert!(token_metadata(String::new(), String::new(), String::new(), MAX_ROYALTY_BPS + 1).is_err());
+    }
+
+    #[test]
+    fn test_launched_token_rejected() {
+        let system = system_program::ID;
+        assert!(check_not_launched(&system, &system).is_ok());
+        let e: Error = PumpError::TokenAlreadyExists.into();
+        assert_eq!(check_not_launched(&Token::id(), &system).unwrap_err(), e);
+        assert_eq!(check_not_launched(&system, &crate::ID).unwrap_err(), e);
+    }
+
+    #[test]
+    fn test_curve_ata_as_creator_account_rejected() {
+        let (creator, bonding_curve) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (creator_ata, curve_ata) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert!(check_creator_token_account(creator_ata, curve_ata, creator, bonding_curve).is_ok());
+        assert!(check_creator_token_account(curve_ata, curve_ata, creator, bonding_curve).is_err());
+        assert!(check_creator_token_account(creator_ata, curve_ata, bonding_curve, bonding_curve).is_err());
+    }
+
+    #[test]
+    fn test_metadata_skipped_in_dev_builds() {
+        assert!(!metadata_required(true, true).unwrap());
+        assert!(metadata_required(false, true).unwrap());
+    }
+
+    #[test]
+    fn test_metadata_required_in_release_builds() {
+        let required: Error = PumpError::MetadataRequired.into();
+        assert_eq!(metadata_required(true, false).unwrap_err(), required);
+        assert!(metadata_required(false, false).unwrap());
+    }
 }
 ```
\ No newline at end of file
@@ -0,0 +1,28 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct GetCreatorSummary<'info> {
+    pub creator: Signer<'info>,
+}
+
+/// Summarizes the creator's curves, passed as remaining accounts, and returns a
+/// borsh-encoded `CreatorSummary` as return data
+pub fn creator_summary<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetCreatorSummary<'info>>,
+) -> Result<()> {
+    let creator = ctx.accounts.creator.key();
+    let mut summary = CreatorSummary::default();
+    
+    for account in ctx.remaining_accounts {
+        let bonding_curve: Account<'info, BondingCurve> = Account::try_from(account)?;
+        summary.add(&creator, &bonding_curve)?;
+    }
+    
+    set_return_data(&summary.try_to_vec()?);
+    
+    Ok(())
+}
+```
\ No newline at end of file
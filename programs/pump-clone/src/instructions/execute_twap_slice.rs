@@ -0,0 +1,51 @@
+```rust
+use anchor_lang::prelude::*;
+use crate::instructions::sell_tokens::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ExecuteTwapSlice<'info> {
+    pub sell: SellTokens<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"twap_sell", sell.token_mint.key().as_ref(), sell.seller.key().as_ref()],
+        bump = twap_sell.bump,
+    )]
+    pub twap_sell: Account<'info, TwapSell>,
+}
+
+/// Sell the next slice of a scheduled TWAP sell through the regular sell path.
+/// Fails with `TwapSliceTooEarly` until the slice's interval has started.
+pub fn execute_twap_slice<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteTwapSlice<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let twap_sell = &mut ctx.accounts.twap_sell;
+    let slice = twap_sell.next_slice(now)?;
+    twap_sell.record_slice(slice)?;
+
+    emit!(TwapSliceExecuted {
+        seller: twap_sell.seller,
+        mint: twap_sell.mint,
+        slice,
+        slices_executed: twap_sell.slices_executed,
+        amount_sold: twap_sell.amount_sold,
+    });
+
+    sell_tokens(
+        Context::new(ctx.program_id, &mut ctx.accounts.sell, ctx.remaining_accounts, ctx.bumps.sell),
+        slice,
+        false,
+    )
+}
+
+#[event]
+pub struct TwapSliceExecuted {
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub slice: u64,
+    pub slices_executed: u16,
+    pub amount_sold: u64,
+}
+```
\ No newline at end of file
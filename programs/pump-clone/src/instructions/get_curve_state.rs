@@ -0,0 +1,23 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct GetCurveState<'info> {
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+/// Returns a versioned `CurveState` snapshot as return data
+pub fn get_curve_state(ctx: Context<GetCurveState>) -> Result<()> {
+    let curve_state = ctx.accounts.bonding_curve.curve_state()?;
+    
+    set_return_data(&curve_state.try_to_vec()?);
+    
+    Ok(())
+}
+```
\ No newline at end of file
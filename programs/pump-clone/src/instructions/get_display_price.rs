@@ -0,0 +1,26 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::Mint;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct GetDisplayPrice<'info> {
+    #[account(
+        seeds = [b"bonding_curve", token_mint.key().as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    pub token_mint: Account<'info, Mint>,
+}
+
+/// Returns the display price (lamports per whole token) as little-endian u64 return data
+pub fn get_display_price(ctx: Context<GetDisplayPrice>) -> Result<()> {
+    let price = ctx.accounts.bonding_curve.display_price(ctx.accounts.token_mint.decimals)?;
+    
+    set_return_data(&price.try_to_vec()?);
+    
+    Ok(())
+}
+```
@@ -0,0 +1,24 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct GetGraduationEta<'info> {
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+/// Returns the estimated seconds to graduation as little-endian u64 return data,
+/// or `GRADUATION_ETA_UNKNOWN` when the curve has no volume yet
+pub fn get_graduation_eta(ctx: Context<GetGraduationEta>) -> Result<()> {
+    let eta = ctx.accounts.bonding_curve.graduation_eta(Clock::get()?.unix_timestamp)?;
+    
+    set_return_data(&eta.try_to_vec()?);
+    
+    Ok(())
+}
+```
\ No newline at end of file
@@ -0,0 +1,167 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct GraduateToPool<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", token_mint.key().as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bonding_curve.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    /// CHECK: This is safe as we only transfer SOL out of this account
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = AmmPool::LEN,
+        seeds = [b"amm_pool", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub amm_pool: Account<'info, AmmPool>,
+
+    #[account(
+        init,
+        payer = caller,
+        token::mint = token_mint,
+        token::authority = amm_pool,
+        seeds = [b"pool_vault", amm_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_token_vault: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Graduate a completed curve into a program-owned constant-product pool instead of
+/// Raydium. The curve's real reserves seed the pool and trading moves to `swap_*`.
+pub fn graduate_to_pool(ctx: Context<GraduateToPool>) -> Result<()> {
+    require!(ctx.accounts.config.internal_pool_graduation, PumpError::InternalPoolGraduationDisabled);
+
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    require!(bonding_curve.graduation_reached(), PumpError::GraduationNotReached);
+    require!(
+        bonding_curve.holder_count >= ctx.accounts.config.min_holders_for_graduation,
+        PumpError::InsufficientHolders
+    );
+
+    bonding_curve.check_token_reserve(ctx.accounts.bonding_curve_token_account.amount)?;
+    let token_liquidity = bonding_curve.real_token_reserves;
+    let sol_liquidity = bonding_curve.real_sol_reserves;
+
+    let now = Clock::get()?.unix_timestamp;
+    let amm_pool = &mut ctx.accounts.amm_pool;
+    amm_pool.mint = ctx.accounts.token_mint.key();
+    amm_pool.bonding_curve = bonding_curve.key();
+    amm_pool.bump = ctx.bumps.amm_pool;
+    amm_pool.token_vault_bump = ctx.bumps.pool_token_vault;
+    amm_pool.initialize(
+        token_liquidity,
+        sol_liquidity,
+        ctx.accounts.config.pool_swap_fee_bps,
+        now,
+    )?;
+
+    let mint_key = ctx.accounts.token_mint.key();
+    let seeds = &[
+        b"bonding_curve",
+        mint_key.as_ref(),
+        &[bonding_curve.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                to: ctx.accounts.pool_token_vault.to_account_info(),
+                authority: bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        token_liquidity,
+    )?;
+
+    require!(
+        ctx.accounts.sol_vault.lamports() >= sol_liquidity,
+        PumpError::InsufficientSolVault
+    );
+    let bonding_curve_key = bonding_curve.key();
+    let vault_seeds = &[
+        b"sol_vault",
+        bonding_curve_key.as_ref(),
+        &[bonding_curve.sol_vault_bump],
+    ];
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.sol_vault.to_account_info(),
+                to: amm_pool.to_account_info(),
+            },
+            &[&vault_seeds[..]],
+        ),
+        sol_liquidity,
+    )?;
+
+    // The curve is spent; every later trade goes through the pool
+    bonding_curve.real_token_reserves = 0;
+    bonding_curve.real_sol_reserves = 0;
+    bonding_curve.complete = true;
+    bonding_curve.buys_enabled = false;
+    bonding_curve.sells_enabled = false;
+
+    emit!(TokenGraduatedToPoolEvent {
+        token_mint: mint_key,
+        bonding_curve: bonding_curve.key(),
+        amm_pool: amm_pool.key(),
+        token_liquidity,
+        sol_liquidity,
+        caller: ctx.accounts.caller.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TokenGraduatedToPoolEvent {
+    pub token_mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub amm_pool: Pubkey,
+    pub token_liquidity: u64,
+    pub sol_liquidity: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+```
\ No newline at end of file
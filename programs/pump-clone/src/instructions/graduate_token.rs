@@ -1,27 +1,41 @@
 ```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token::{self, Burn, Token, TokenAccount, Mint, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
+use crate::errors::*;
+use crate::state::{BondingCurve, Config, RewardAccount};
+use crate::utils::math::BPS_DIVISOR;
 
 #[derive(Accounts)]
 pub struct GraduateToken<'info> {
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub caller: Signer<'info>,
+    
+    /// CHECK: Validated against the bonding curve via has_one
+    pub creator: UncheckedAccount<'info>,
     
     #[account(
         mut,
         seeds = [b"bonding_curve", token_mint.key().as_ref()],
-        bump,
-        has_one = token_mint,
+        bump = bonding_curve.bump,
         has_one = creator,
-        constraint = bonding_curve.graduated == false @ PumpError::TokenAlreadyGraduated,
-        constraint = bonding_curve.total_supply >= bonding_curve.graduation_threshold @ PumpError::GraduationThresholdNotMet
+        constraint = !bonding_curve.graduated @ PumpError::TokenAlreadyGraduated,
+        constraint = bonding_curve.graduation_reached() @ PumpError::GraduationNotReached
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
     
-    #[account(mut)]
+    #[account(mut, address = bonding_curve.mint)]
     pub token_mint: Account<'info, Mint>,
     
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bonding_curve.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    /// CHECK: This is safe as we only transfer SOL out of this account
+    pub sol_vault: AccountInfo<'info>,
+    
     #[account(
         mut,
         associated_token::mint = token_mint,
@@ -36,6 +50,19 @@ pub struct GraduateToken<'info> {
     )]
     pub creator_token_account: Account<'info, TokenAccount>,
     
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// Holds the caller's reward until it vests
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = RewardAccount::LEN,
+        seeds = [b"reward", caller.key().as_ref()],
+        bump,
+    )]
+    pub caller_reward_account: Account<'info, RewardAccount>,
+    
     /// CHECK: This is the Raydium AMM program ID
     #[account(constraint = amm_program.key() == crate::constants::RAYDIUM_AMM_PROGRAM_ID)]
     pub amm_program: UncheckedAccount<'info>,
@@ -94,11 +121,12 @@ pub fn graduate_token(ctx: Context<GraduateToken>) -> Result<()> {
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     let token_mint = &ctx.accounts.token_mint;
     let creator = &ctx.accounts.creator;
+    let caller = &ctx.accounts.caller;
     
     // Verify graduation requirements
     require!(
-        bonding_curve.total_supply >= bonding_curve.graduation_threshold,
-        PumpError::GraduationThresholdNotMet
+        bonding_curve.graduation_reached(),
+        PumpError::GraduationNotReached
     );
     
     require!(
@@ -106,24 +134,40 @@ pub fn graduate_token(ctx: Context<GraduateToken>) -> Result<()> {
         PumpError::TokenAlreadyGraduated
     );
     
-    // Calculate liquidity amounts
-    let token_liquidity = bonding_curve.total_supply
+    check_min_holders(bonding_curve.holder_count, ctx.accounts.config.min_holders_for_graduation)?;
+    require!(!ctx.accounts.config.internal_pool_graduation, PumpError::InternalPoolGraduation);
+    bonding_curve.check_token_reserve(ctx.accounts.bonding_curve_token_account.amount)?;
+    
+    // Persist the guard before any CPI so a reentrant call sees it in account data
+    bonding_curve.begin_graduation()?;
+    bonding_curve.exit(ctx.program_id)?;
+    
+    // Calculate liquidity amounts from what the curve actually holds
+    let curve_tokens = ctx.accounts.bonding_curve_token_account.amount;
+    let token_liquidity = curve_tokens
         .checked_mul(80)
-        .unwrap()
+        .ok_or(PumpError::MathOverflow)?
         .checked_div(100)
-        .unwrap(); // 80% of total supply
+        .ok_or(PumpError::MathOverflow)?; // 80% of the curve's tokens
     
-    let sol_liquidity = bonding_curve.sol_reserves
+    let sol_liquidity = bonding_curve.real_sol_reserves
         .checked_mul(90)
-        .unwrap()
+        .ok_or(PumpError::MathOverflow)?
         .checked_div(100)
-        .unwrap(); // 90% of SOL reserves
+        .ok_or(PumpError::MathOverflow)?; // 90% of SOL reserves
+    
+    let caller_reward = calculate_graduation_reward(
+        bonding_curve.real_sol_reserves,
+        sol_liquidity,
+        ctx.accounts.config.graduation_reward,
+        graduation_reward_cap_bps(bonding_curve.completion_timestamp, Clock::get()?.unix_timestamp),
+    )?;
     
     // Transfer tokens from bonding curve to AMM
-    let bonding_curve_key = bonding_curve.key();
+    let mint_key = token_mint.key();
     let bonding_curve_seeds = &[
         b"bonding_curve",
-        token_mint.key().as_ref(),
+        mint_key.as_ref(),
         &[bonding_curve.bump]
     ];
     let bonding_curve_signer = &[&bonding_curve_seeds[..]];
@@ -142,54 +186,106 @@ pub fn graduate_token(ctx: Context<GraduateToken>) -> Result<()> {
         token_liquidity,
     )?;
     
-    // Transfer SOL to AMM pool
-    **bonding_curve.to_account_info().try_borrow_mut_lamports()? -= sol_liquidity;
-    **ctx.accounts.amm_pool_pc_token_account.to_account_info().try_borrow_mut_lamports()? += sol_liquidity;
+    // Transfer SOL to AMM pool out of the curve's vault
+    let sol_vault = &ctx.accounts.sol_vault;
+    let sol_reserves_before = sol_vault.lamports();
+    require!(
+        sol_reserves_before >= sol_liquidity + caller_reward,
+        PumpError::InsufficientSolVault
+    );
+    let bonding_curve_key = bonding_curve.key();
+    let vault_seeds = &[
+        b"sol_vault",
+        bonding_curve_key.as_ref(),
+        &[bonding_curve.sol_vault_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: sol_vault.to_account_info(),
+                to: ctx.accounts.amm_pool_pc_token_account.to_account_info(),
+            },
+            vault_signer,
+        ),
+        sol_liquidity,
+    )?;
+    
+    // Reward the caller for triggering the migration; it becomes claimable once it vests
+    let reward_account = &mut ctx.accounts.caller_reward_account;
+    if caller_reward > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: sol_vault.to_account_info(),
+                    to: reward_account.to_account_info(),
+                },
+                vault_signer,
+            ),
+            caller_reward,
+        )?;
+    }
+    let reward_vests_at = accrue_caller_reward(
+        reward_account,
+        caller.key(),
+        ctx.bumps.caller_reward_account,
+        caller_reward,
+        Clock::get()?.unix_timestamp,
+        ctx.accounts.config.reward_vesting_period,
+    )?;
+    let sol_reserves_after = sol_vault.lamports();
+    check_lamport_moves(sol_reserves_before, sol_reserves_after, sol_liquidity, caller_reward)?;
     
     // Initialize AMM pool via CPI to Raydium
-    let initialize_pool_instruction = raydium_amm::instruction::initialize(
-        &ctx.accounts.amm_program.key(),
-        &ctx.accounts.amm_pool.key(),
-        &ctx.accounts.amm_pool_authority.key(),
-        &ctx.accounts.amm_pool_lp_mint.key(),
-        &ctx.accounts.amm_pool_coin_token_account.key(),
-        &ctx.accounts.amm_pool_pc_token_account.key(),
-        &ctx.accounts.amm_pool_withdraw_queue.key(),
-        &ctx.accounts.amm_pool_temp_lp_token_account.key(),
-        &ctx.accounts.serum_program.key(),
-        &ctx.accounts.serum_market.key(),
-        &ctx.accounts.serum_coin_vault_account.key(),
-        &ctx.accounts.serum_pc_vault_account.key(),
-        &ctx.accounts.serum_vault_signer.key(),
+    let pool_accounts = [
+        ctx.accounts.amm_pool.to_account_info(),
+        ctx.accounts.amm_pool_authority.to_account_info(),
+        ctx.accounts.amm_pool_lp_mint.to_account_info(),
+        ctx.accounts.amm_pool_coin_token_account.to_account_info(),
+        ctx.accounts.amm_pool_pc_token_account.to_account_info(),
+        ctx.accounts.amm_pool_withdraw_queue.to_account_info(),
+        ctx.accounts.amm_pool_temp_lp_token_account.to_account_info(),
+        ctx.accounts.serum_program.to_account_info(),
+        ctx.accounts.serum_market.to_account_info(),
+        ctx.accounts.serum_coin_vault_account.to_account_info(),
+        ctx.accounts.serum_pc_vault_account.to_account_info(),
+        ctx.accounts.serum_vault_signer.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.rent.to_account_info(),
+    ];
+    let initialize_pool_instruction = raydium_initialize_instruction(
+        ctx.accounts.amm_program.key(),
+        &pool_accounts,
         0, // nonce
-    )?;
+    );
     
-    anchor_lang::solana_program::program::invoke(
-        &initialize_pool_instruction,
-        &[
-            ctx.accounts.amm_pool.to_account_info(),
-            ctx.accounts.amm_pool_authority.to_account_info(),
-            ctx.accounts.amm_pool_lp_mint.to_account_info(),
-            ctx.accounts.amm_pool_coin_token_account.to_account_info(),
-            ctx.accounts.amm_pool_pc_token_account.to_account_info(),
-            ctx.accounts.amm_pool_withdraw_queue.to_account_info(),
-            ctx.accounts.amm_pool_temp_lp_token_account.to_account_info(),
-            ctx.accounts.serum_program.to_account_info(),
-            ctx.accounts.serum_market.to_account_info(),
-            ctx.accounts.serum_coin_vault_account.to_account_info(),
-            ctx.accounts.serum_pc_vault_account.to_account_info(),
-            ctx.accounts.serum_vault_signer.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.rent.to_account_info(),
-        ],
-    )?;
+    anchor_lang::solana_program::program::invoke(&initialize_pool_instruction, &pool_accounts)?;
     
     // Transfer remaining tokens to creator
-    let remaining_tokens = bonding_curve.total_supply
+    let remaining_tokens = curve_tokens
         .checked_sub(token_liquidity)
-        .unwrap();
+        .ok_or(PumpError::MathOverflow)?;
     
-    if remaining_tokens > 0 {
+    // Projects promising no creator allocation burn the remainder instead
+    let burned_remainder = if bonding_curve.burn_remainder { remaining_tokens } else { 0 };
+    
+    if remaining_tokens > 0 && bonding_curve.burn_remainder {
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: token_mint.to_account_info(),
+                    from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                    authority: bonding_curve.to_account_info(),
+                },
+                bonding_curve_signer,
+            ),
+            remaining_tokens,
+        )?;
+        bonding_curve.burn_supply(remaining_tokens)?;
+    } else if remaining_tokens > 0 {
         token::transfer(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
@@ -204,10 +300,16 @@ pub fn graduate_token(ctx: Context<GraduateToken>) -> Result<()> {
         )?;
     }
     
-    // Mark token as graduated
-    bonding_curve.graduated = true;
-    bonding_curve.graduation_timestamp = Clock::get()?.unix_timestamp;
-    bonding_curve.amm_pool = ctx.accounts.amm_pool.key();
+    // Mark token as graduated; the curve is spent and every later trade goes through the AMM
+    bonding_curve.real_sol_reserves = bonding_curve
+        .real_sol_reserves
+        .checked_sub(sol_liquidity + caller_reward)
+        .ok_or(PumpError::MathOverflow)?;
+    bonding_curve.real_token_reserves = 0;
+    bonding_curve.complete = true;
+    bonding_curve.buys_enabled = false;
+    bonding_curve.sells_enabled = false;
+    bonding_curve.finish_graduation(ctx.accounts.amm_pool.key(), Clock::get()?.unix_timestamp);
     
     // Emit graduation event
     emit!(TokenGraduatedEvent {
@@ -215,8 +317,17 @@ pub fn graduate_token(ctx: Context<GraduateToken>) -> Result<()> {
         creator: creator.key(),
         bonding_curve: bonding_curve.key(),
         amm_pool: ctx.accounts.amm_pool.key(),
+        lp_mint: ctx.accounts.amm_pool_lp_mint.key(),
+        coin_vault: ctx.accounts.amm_pool_coin_token_account.key(),
+        pc_vault: ctx.accounts.amm_pool_pc_token_account.key(),
         token_liquidity,
         sol_liquidity,
+        caller: caller.key(),
+        caller_reward,
+        reward_vests_at,
+        burned_remainder,
+        sol_reserves_before,
+        sol_reserves_after,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
@@ -225,18 +336,103 @@ pub fn graduate_token(ctx: Context<GraduateToken>) -> Result<()> {
     Ok(())
 }
 
-#[account]
-pub struct BondingCurve {
-    pub creator: Pubkey,
-    pub token_mint: Pubkey,
-    pub total_supply: u64,
-    pub current_supply: u64,
-    pub sol_reserves: u64,
-    pub graduation_threshold: u64,
-    pub graduated: bool,
-    pub graduation_timestamp: i64,
-    pub amm_pool: Pubkey,
-    pub bump: u8,
+/// Raydium's `initialize` instruction (tag 0) over the pool accounts, in the order passed in
+fn raydium_initialize_instruction(
+    amm_program: Pubkey,
+    pool_accounts: &[AccountInfo],
+    nonce: u8,
+) -> Instruction {
+    Instruction {
+        program_id: amm_program,
+        accounts: pool_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: vec![0, nonce],
+    }
+}
+
+/// Credit `caller_reward` to the caller's reward account, taking ownership of a fresh one, and
+/// return when the balance vests
+fn accrue_caller_reward(
+    reward_account: &mut RewardAccount,
+    caller: Pubkey,
+    bump: u8,
+    caller_reward: u64,
+    now: i64,
+    vesting_period: i64,
+) -> Result<i64> {
+    if reward_account.owner == Pubkey::default() {
+        reward_account.owner = caller;
+        reward_account.bump = bump;
+    }
+    if caller_reward > 0 {
+        reward_account.accrue(caller_reward, now, vesting_period)?;
+    }
+    Ok(reward_account.vests_at)
+}
+
+/// The curve's lamports must have dropped by exactly what was migrated and set aside for the caller
+fn check_lamport_moves(before: u64, after: u64, sol_liquidity: u64, caller_reward: u64) -> Result<()> {
+    let moved = sol_liquidity
+        .checked_add(caller_reward)
+        .ok_or(PumpError::LamportAccountingMismatch)?;
+    require!(
+        before.checked_sub(after) == Some(moved),
+        PumpError::LamportAccountingMismatch
+    );
+    Ok(())
+}
+
+/// Maximum share of SOL reserves that can be paid out as a graduation reward (1%)
+pub const MAX_GRADUATION_REWARD_BPS: u64 = 100;
+
+/// Raised reward cap once a completed curve has waited past the migration deadline (3%)
+pub const LATE_GRADUATION_REWARD_BPS: u64 = 300;
+
+/// Seconds after completion within which graduation is expected
+pub const GRADUATION_DEADLINE: i64 = 60 * 60;
+
+/// Reward cap in force at `now`; overdue migrations pay more to attract a caller
+fn graduation_reward_cap_bps(completion_timestamp: i64, now: i64) -> u64 {
+    let overdue = completion_timestamp > 0
+        && now.saturating_sub(completion_timestamp) > GRADUATION_DEADLINE;
+
+    if overdue {
+        LATE_GRADUATION_REWARD_BPS
+    } else {
+        MAX_GRADUATION_REWARD_BPS
+    }
+}
+
+/// A single whale can't graduate a token alone, however much SOL they put in
+fn check_min_holders(holder_count: u64, min_holders_for_graduation: u64) -> Result<()> {
+    require!(holder_count >= min_holders_for_graduation, PumpError::InsufficientHolders);
+    Ok(())
+}
+
+/// Reward paid to the graduation caller, capped so it never touches AMM liquidity
+fn calculate_graduation_reward(
+    sol_reserves: u64,
+    sol_liquidity: u64,
+    graduation_reward: u64,
+    max_reward_bps: u64,
+) -> Result<u64> {
+    let max_reward = (sol_reserves as u128)
+        .checked_mul(max_reward_bps as u128)
+        .ok_or(PumpError::InsufficientLiquidity)?
+        .checked_div(BPS_DIVISOR as u128)
+        .ok_or(PumpError::InsufficientLiquidity)? as u64;
+
+    let residual = sol_reserves
+        .checked_sub(sol_liquidity)
+        .ok_or(PumpError::InsufficientLiquidity)?;
+
+    Ok(graduation_reward.min(max_reward).min(residual))
 }
 
 #[event]
@@ -245,20 +441,131 @@ pub struct TokenGraduatedEvent {
     pub creator: Pubkey,
     pub bonding_curve: Pubkey,
     pub amm_pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub coin_vault: Pubkey,
+    pub pc_vault: Pubkey,
     pub token_liquidity: u64,
     pub sol_liquidity: u64,
+    pub caller: Pubkey,
+    /// Accrued to the caller's reward account, claimable from `reward_vests_at`
+    pub caller_reward: u64,
+    pub reward_vests_at: i64,
+    pub burned_remainder: u64,
+    /// The bonding curve's lamports around the direct SOL moves, for reconciliation
+    pub sol_reserves_before: u64,
+    pub sol_reserves_after: u64,
     pub timestamp: i64,
 }
 
-#[error_code]
-pub enum PumpError {
-    #[msg("Token has already graduated")]
-    TokenAlreadyGraduated,
-    #[msg("Graduation threshold not met")]
-    GraduationThresholdNotMet,
-    #[msg("Insufficient liquidity for graduation")]
-    InsufficientLiquidity,
-    #[msg("Invalid AMM program")]
-    InvalidAmmProgram,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::DEFAULT_REWARD_VESTING_PERIOD;
+
+    #[test]
+    fn test_graduated_event_includes_pool_keys() {
+        let event = TokenGraduatedEvent {
+            token_mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            bonding_curve: Pubkey::new_unique(),
+            amm_pool: Pubkey::new_unique(),
+            lp_mint: Pubkey::new_unique(),
+            coin_vault: Pubkey::new_unique(),
+            pc_vault: Pubkey::new_unique(),
+            token_liquidity: 800,
+            sol_liquidity: 90,
+            caller: Pubkey::new_unique(),
+            caller_reward: 1,
+            reward_vests_at: 0,
+            burned_remainder: 0,
+            sol_reserves_before: 100,
+            sol_reserves_after: 9,
+            timestamp: 0,
+        };
+
+        let data = event.try_to_vec().unwrap();
+        let decoded = TokenGraduatedEvent::try_from_slice(&data).unwrap();
+
+        assert_eq!(decoded.amm_pool, event.amm_pool);
+        assert_eq!(decoded.lp_mint, event.lp_mint);
+        assert_eq!(decoded.coin_vault, event.coin_vault);
+        assert_eq!(decoded.pc_vault, event.pc_vault);
+    }
+
+    #[test]
+    fn test_graduation_reward_paid_to_caller() {
+        // 85 SOL reserves, 90% migrated, 0.5 SOL reward requested
+        let reward = calculate_graduation_reward(85_000_000_000, 76_500_000_000, 500_000_000, MAX_GRADUATION_REWARD_BPS).unwrap();
+        assert_eq!(reward, 500_000_000);
+    }
+
+    #[test]
+    fn test_graduation_reward_capped() {
+        // A 5 SOL reward is capped at 1% of reserves
+        let reward = calculate_graduation_reward(85_000_000_000, 76_500_000_000, 5_000_000_000, MAX_GRADUATION_REWARD_BPS).unwrap();
+        assert_eq!(reward, 850_000_000);
+
+        // The reward never dips into the migrated liquidity
+        let reward = calculate_graduation_reward(1_000, 999, 10, MAX_GRADUATION_REWARD_BPS).unwrap();
+        assert_eq!(reward, 1);
+    }
+
+    #[test]
+    fn test_reward_cap_rises_after_deadline() {
+        let completed_at = 1_000;
+
+        assert_eq!(graduation_reward_cap_bps(completed_at, completed_at + GRADUATION_DEADLINE), MAX_GRADUATION_REWARD_BPS);
+        assert_eq!(graduation_reward_cap_bps(completed_at, completed_at + GRADUATION_DEADLINE + 1), LATE_GRADUATION_REWARD_BPS);
+
+        // Never-completed curves don't count as overdue
+        assert_eq!(graduation_reward_cap_bps(0, i64::MAX), MAX_GRADUATION_REWARD_BPS);
+    }
+
+    #[test]
+    fn test_overdue_graduation_pays_larger_reward() {
+        let cap = graduation_reward_cap_bps(1_000, 1_000 + GRADUATION_DEADLINE + 1);
+        let reward = calculate_graduation_reward(85_000_000_000, 76_500_000_000, 5_000_000_000, cap).unwrap();
+
+        assert_eq!(reward, 2_550_000_000);
+    }
+
+    #[test]
+    fn test_graduation_requires_min_holders() {
+        assert!(check_min_holders(25, 25).is_ok());
+        assert!(check_min_holders(100, 25).is_ok());
+
+        // One whale past the SOL threshold still can't graduate alone
+        assert!(check_min_holders(1, 25).is_err());
+
+        // No requirement configured
+        assert!(check_min_holders(0, 0).is_ok());
+    }
+
+    #[test]
+    fn test_graduation_lamport_delta_reconciles() {
+        let before = 85_000_000_000 + 1_461_600;
+        let sol_liquidity = 76_500_000_000;
+        let caller_reward = 500_000_000;
+        let after = before - sol_liquidity - caller_reward;
+
+        assert!(check_lamport_moves(before, after, sol_liquidity, caller_reward).is_ok());
+        assert_eq!(before - after, sol_liquidity + caller_reward);
+        assert!(check_lamport_moves(before, after + 1, sol_liquidity, caller_reward).is_err());
+        assert!(check_lamport_moves(before, after - 1, sol_liquidity, caller_reward).is_err());
+    }
+
+    #[test]
+    fn test_caller_reward_claimable_only_after_vesting() {
+        let caller = Pubkey::new_unique();
+        let mut reward_account = RewardAccount { owner: Pubkey::default(), accrued: 0, vests_at: 0, bump: 0 };
+        let vests_at = accrue_caller_reward(&mut reward_account, caller, 254, 500, 1_000, DEFAULT_REWARD_VESTING_PERIOD).unwrap();
+        assert_eq!(reward_account.owner, caller);
+        assert_eq!(vests_at, 1_000 + DEFAULT_REWARD_VESTING_PERIOD);
+
+        // claim_rewards refuses the reward until it vests, then pays all of it
+        let not_vested: Error = PumpError::RewardNotVested.into();
+        assert_eq!(reward_account.claim(vests_at - 1).unwrap_err(), not_vested);
+        assert_eq!(reward_account.claim(vests_at).unwrap(), 500);
+    }
 }
 ```
\ No newline at end of file
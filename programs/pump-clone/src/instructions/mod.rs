@@ -0,0 +1,49 @@
+```rust
+pub mod buy_tokens;
+pub mod claim_rewards;
+pub mod create_token;
+pub mod creator_summary;
+pub mod execute_twap_slice;
+pub mod get_curve_state;
+pub mod get_display_price;
+pub mod get_graduation_eta;
+pub mod graduate_to_pool;
+pub mod graduate_token;
+pub mod initialize_sol_vault;
+pub mod refresh_market_cap;
+pub mod reconcile_vaults;
+pub mod register_referral;
+pub mod schedule_twap_sell;
+pub mod sell_tokens;
+pub mod set_creator_fee;
+pub mod set_graduation_threshold;
+pub mod set_price_floor;
+pub mod set_trading_flags;
+pub mod swap;
+pub mod verify_reserve_ratio;
+pub mod verify_solvency;
+
+pub use buy_tokens::*;
+pub use claim_rewards::*;
+pub use create_token::*;
+pub use creator_summary::*;
+pub use execute_twap_slice::*;
+pub use get_curve_state::*;
+pub use get_display_price::*;
+pub use get_graduation_eta::*;
+pub use graduate_to_pool::*;
+pub use graduate_token::*;
+pub use initialize_sol_vault::*;
+pub use refresh_market_cap::*;
+pub use reconcile_vaults::*;
+pub use register_referral::*;
+pub use schedule_twap_sell::*;
+pub use sell_tokens::*;
+pub use set_creator_fee::*;
+pub use set_graduation_threshold::*;
+pub use set_price_floor::*;
+pub use set_trading_flags::*;
+pub use swap::*;
+pub use verify_reserve_ratio::*;
+pub use verify_solvency::*;
+```
\ No newline at end of file
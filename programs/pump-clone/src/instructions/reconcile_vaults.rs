@@ -0,0 +1,150 @@
+```rust
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ReconcileVaults<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bonding_curve.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// Program-wide vault buys paid into before the vault seeds were unified
+    #[account(mut, seeds = [b"sol_vault"], bump)]
+    pub legacy_sol_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// One-time admin sweep for curves that traded before buys moved to the per-curve vault.
+/// Those buys paid into the program-wide `[b"sol_vault"]` PDA while sells paid out of the
+/// per-curve one, so the curve's vault holds less than the reserves it must back. This
+/// moves the shortfall, and nothing more, over from the legacy vault.
+pub fn reconcile_vaults(ctx: Context<ReconcileVaults>) -> Result<()> {
+    let rent_minimum = Rent::get()?.minimum_balance(0);
+    let vault_before = ctx.accounts.sol_vault.lamports();
+    let legacy_before = ctx.accounts.legacy_sol_vault.lamports();
+    let amount = reconcile_amount(
+        ctx.accounts.bonding_curve.real_sol_reserves,
+        vault_before,
+        legacy_before,
+        rent_minimum,
+    )?;
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.legacy_sol_vault.to_account_info(),
+                to: ctx.accounts.sol_vault.to_account_info(),
+            },
+            &[&[b"sol_vault".as_ref(), &[ctx.bumps.legacy_sol_vault]]],
+        ),
+        amount,
+    )?;
+
+    let vault_after = ctx.accounts.sol_vault.lamports();
+    let legacy_after = ctx.accounts.legacy_sol_vault.lamports();
+    require!(
+        vault_after.checked_sub(vault_before) == Some(amount)
+            && legacy_before.checked_sub(legacy_after) == Some(amount),
+        PumpError::LamportAccountingMismatch
+    );
+
+    emit!(VaultsReconciledEvent {
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        sol_vault: ctx.accounts.sol_vault.key(),
+        legacy_sol_vault: ctx.accounts.legacy_sol_vault.key(),
+        amount,
+        sol_vault_balance: vault_after,
+        legacy_sol_vault_balance: legacy_after,
+        real_sol_reserves: ctx.accounts.bonding_curve.real_sol_reserves,
+    });
+
+    Ok(())
+}
+
+/// Lamports to sweep so the curve's vault holds its rent minimum plus `real_sol_reserves`,
+/// limited to what the legacy vault can spend without dropping below its own rent minimum
+fn reconcile_amount(
+    real_sol_reserves: u64,
+    vault_lamports: u64,
+    legacy_lamports: u64,
+    rent_minimum: u64,
+) -> Result<u64> {
+    let target = real_sol_reserves
+        .checked_add(rent_minimum)
+        .ok_or(PumpError::MathOverflow)?;
+    let shortfall = target.saturating_sub(vault_lamports);
+    require!(shortfall > 0, PumpError::VaultsAlreadyReconciled);
+
+    let available = legacy_lamports.saturating_sub(rent_minimum);
+    let amount = shortfall.min(available);
+    require!(amount > 0, PumpError::InsufficientSolVault);
+    Ok(amount)
+}
+
+#[event]
+pub struct VaultsReconciledEvent {
+    pub bonding_curve: Pubkey,
+    pub sol_vault: Pubkey,
+    pub legacy_sol_vault: Pubkey,
+    pub amount: u64,
+    /// Balances after the sweep, for reconciliation off-chain
+    pub sol_vault_balance: u64,
+    pub legacy_sol_vault_balance: u64,
+    pub real_sol_reserves: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RENT_MINIMUM: u64 = 890_880;
+
+    #[test]
+    fn test_sweeps_exactly_the_shortfall() {
+        // 3 SOL of buys landed in the legacy vault alongside another curve's 2 SOL
+        let amount = reconcile_amount(3_000_000_000, RENT_MINIMUM, RENT_MINIMUM + 5_000_000_000, RENT_MINIMUM).unwrap();
+        assert_eq!(amount, 3_000_000_000);
+
+        // Part already in the unified vault is left where it is
+        let amount = reconcile_amount(3_000_000_000, RENT_MINIMUM + 1_000_000_000, RENT_MINIMUM + 5_000_000_000, RENT_MINIMUM).unwrap();
+        assert_eq!(amount, 2_000_000_000);
+    }
+
+    #[test]
+    fn test_vault_without_rent_gets_it_back() {
+        let amount = reconcile_amount(1_000, 0, RENT_MINIMUM + 1_000_000, RENT_MINIMUM).unwrap();
+        assert_eq!(amount, RENT_MINIMUM + 1_000);
+    }
+
+    #[test]
+    fn test_legacy_vault_keeps_its_rent() {
+        let amount = reconcile_amount(3_000_000_000, RENT_MINIMUM, RENT_MINIMUM + 1_000, RENT_MINIMUM).unwrap();
+        assert_eq!(amount, 1_000);
+
+        assert!(reconcile_amount(3_000_000_000, RENT_MINIMUM, RENT_MINIMUM, RENT_MINIMUM).is_err());
+    }
+
+    #[test]
+    fn test_reconciled_vault_rejected() {
+        assert!(reconcile_amount(3_000_000_000, RENT_MINIMUM + 3_000_000_000, u64::MAX, RENT_MINIMUM).is_err());
+        assert!(reconcile_amount(0, RENT_MINIMUM, u64::MAX, RENT_MINIMUM).is_err());
+    }
+}
+```
\ No newline at end of file
@@ -0,0 +1,19 @@
+```rust
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RefreshMarketCap<'info> {
+    #[account(mut)]
+    pub token_launch: Account<'info, TokenLaunch>,
+}
+
+/// Permissionless: anyone may force `market_cap` to agree with the current reserves
+pub fn refresh_market_cap(ctx: Context<RefreshMarketCap>) -> Result<()> {
+    let event = ctx.accounts.token_launch.refresh_market_cap(Clock::get()?.unix_timestamp)?;
+    
+    emit!(event);
+    
+    Ok(())
+}
+```
\ No newline at end of file
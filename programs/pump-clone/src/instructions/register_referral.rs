@@ -0,0 +1,42 @@
+```rust
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RegisterReferral<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = ReferralAccount::LEN,
+        seeds = [b"referral", owner.key().as_ref()],
+        bump,
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_referral(ctx: Context<RegisterReferral>, referrer: Pubkey) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    require!(referrer != owner, PumpError::SelfReferral);
+    
+    let referral_account = &mut ctx.accounts.referral_account;
+    referral_account.owner = owner;
+    referral_account.referrer = referrer;
+    referral_account.bump = ctx.bumps.referral_account;
+    
+    emit!(ReferralRegisteredEvent { owner, referrer });
+    
+    Ok(())
+}
+
+#[event]
+pub struct ReferralRegisteredEvent {
+    pub owner: Pubkey,
+    pub referrer: Pubkey,
+}
+```
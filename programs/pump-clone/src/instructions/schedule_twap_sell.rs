@@ -0,0 +1,76 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ScheduleTwapSell<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        associated_token::mint = token_mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = TwapSell::LEN,
+        seeds = [b"twap_sell", token_mint.key().as_ref(), seller.key().as_ref()],
+        bump,
+    )]
+    pub twap_sell: Account<'info, TwapSell>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Schedule `total_amount` to be sold in `intervals` equal slices, `interval_seconds` apart.
+/// A finished schedule may be replaced; a running one must complete first.
+pub fn schedule_twap_sell(
+    ctx: Context<ScheduleTwapSell>,
+    total_amount: u64,
+    intervals: u16,
+    interval_seconds: i64,
+) -> Result<()> {
+    let twap_sell = &mut ctx.accounts.twap_sell;
+    require!(
+        twap_sell.seller == Pubkey::default() || twap_sell.is_complete(),
+        PumpError::TwapSellInProgress
+    );
+    require!(
+        ctx.accounts.seller_token_account.amount >= total_amount,
+        PumpError::InsufficientTokens
+    );
+
+    twap_sell.seller = ctx.accounts.seller.key();
+    twap_sell.mint = ctx.accounts.token_mint.key();
+    twap_sell.bump = ctx.bumps.twap_sell;
+    twap_sell.schedule(total_amount, intervals, interval_seconds, Clock::get()?.unix_timestamp)?;
+
+    emit!(TwapSellScheduled {
+        seller: twap_sell.seller,
+        mint: twap_sell.mint,
+        total_amount,
+        intervals,
+        interval_seconds,
+        start_ts: twap_sell.start_ts,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TwapSellScheduled {
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub intervals: u16,
+    pub interval_seconds: i64,
+    pub start_ts: i64,
+}
+```
\ No newline at end of file
@@ -1,8 +1,10 @@
 ```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::utils::math::{MathUtils, BPS_DIVISOR};
 
 #[derive(Accounts)]
 pub struct SellTokens<'info> {
@@ -26,45 +28,149 @@ pub struct SellTokens<'info> {
     )]
     pub seller_token_account: Account<'info, TokenAccount>,
     
+    /// Only required on curves with `self_trade_guard` on, so other traders pay no rent for it
     #[account(
-        mut,
-        seeds = [b"curve_vault", bonding_curve.key().as_ref()],
+        init_if_needed,
+        payer = seller,
+        space = TradeGuard::LEN,
+        seeds = [b"trade_guard", token_mint.key().as_ref(), seller.key().as_ref()],
         bump,
     )]
-    pub curve_vault: Account<'info, TokenAccount>,
+    pub trade_guard: Option<Account<'info, TradeGuard>>,
+    
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = HolderStats::LEN,
+        seeds = [b"holder_stats", token_mint.key().as_ref(), seller.key().as_ref()],
+        bump,
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+    
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
         seeds = [b"sol_vault", bonding_curve.key().as_ref()],
-        bump,
+        bump = bonding_curve.sol_vault_bump,
     )]
     /// CHECK: This is safe as we only transfer SOL to/from this account
     pub sol_vault: AccountInfo<'info>,
     
+    #[account(mut, address = bonding_curve.creator)]
+    pub creator: SystemAccount<'info>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// Creator's floor reserve; required only when the sell routes to the floor
+    #[account(
+        mut,
+        seeds = [b"floor_vault", bonding_curve.key().as_ref()],
+        bump = bonding_curve.floor_vault_bump,
+    )]
+    pub floor_vault: Option<SystemAccount<'info>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64) -> Result<()> {
+/// With `close_ata`, a sell that empties the seller's token account also closes it and
+/// refunds its rent to the seller
+pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64, close_ata: bool) -> Result<()> {
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     let seller = &ctx.accounts.seller;
     let seller_token_account = &ctx.accounts.seller_token_account;
-    let curve_vault = &ctx.accounts.curve_vault;
+    let bonding_curve_token_account = &ctx.accounts.bonding_curve_token_account;
     let sol_vault = &ctx.accounts.sol_vault;
     
     require!(token_amount > 0, PumpError::InvalidAmount);
     require!(seller_token_account.amount >= token_amount, PumpError::InsufficientTokens);
-    require!(!bonding_curve.is_complete, PumpError::BondingCurveComplete);
+    require!(!bonding_curve.complete, PumpError::BondingCurveComplete);
+    bonding_curve.check_sells_enabled()?;
+    bonding_curve.check_creator_can_sell(&seller.key())?;
+    bonding_curve.check_trading_window(Clock::get()?.unix_timestamp)?;
+    
+    if bonding_curve.self_trade_guard {
+        let trade_guard = ctx.accounts.trade_guard
+            .as_mut()
+            .ok_or(PumpError::MissingTradeGuard)?;
+        trade_guard.wallet = seller.key();
+        trade_guard.mint = ctx.accounts.token_mint.key();
+        trade_guard.bump = ctx.bumps.trade_guard;
+        trade_guard.record_trade(Clock::get()?.slot, false)?;
+    }
+    
+    let gross_sol_amount = calculate_sell_price(
+        bonding_curve.virtual_token_reserves,
+        bonding_curve.virtual_sol_reserves,
+        token_amount,
+        0,
+    )?;
+    
+    // At or below the creator's floor, the floor reserve buys instead of the curve
+    if let Some(floor_sol_amount) = bonding_curve.floor_payout(token_amount, gross_sol_amount)? {
+        return sell_to_floor(ctx, token_amount, floor_sol_amount, close_ata);
+    }
+    
+    let fee_exempt = bonding_curve.is_fee_exempt(&seller.key())
+        || ctx.accounts.config.is_fee_free(gross_sol_amount);
+    let sell_fee_bps = if fee_exempt {
+        0
+    } else {
+        bonding_curve.impact_adjusted_fee_bps(
+            bonding_curve.trade_fee_bps()?,
+            gross_sol_amount,
+            ctx.accounts.config.impact_fee_coefficient,
+        )?
+    };
+    // Long-term holders get part of the sell fee waived
+    let sell_fee_bps = if ctx.accounts.config.hold_rebate_enabled {
+        ctx.accounts.holder_stats.rebated_fee_bps(sell_fee_bps, Clock::get()?.unix_timestamp)
+    } else {
+        sell_fee_bps
+    };
     
     // Calculate SOL amount to receive based on bonding curve
     let sol_amount = calculate_sell_price(
         bonding_curve.virtual_token_reserves,
         bonding_curve.virtual_sol_reserves,
         token_amount,
+        sell_fee_bps,
     )?;
     
     require!(sol_amount > 0, PumpError::InvalidCalculation);
+    ctx.accounts.config.check_slippage(bonding_curve.sol_at_spot(token_amount)?, gross_sol_amount)?;
+    
+    // The trade fee stays in the curve's reserves
+    let trade_fee = gross_sol_amount
+        .checked_sub(sol_amount)
+        .ok_or(PumpError::MathOverflow)?;
+    
     require!(sol_vault.lamports() >= sol_amount, PumpError::InsufficientSolVault);
+    check_vault_rent_floor(sol_vault.lamports(), Rent::get()?.minimum_balance(0), sol_amount)?;
+    
+    let creator_fee_bps = if fee_exempt {
+        0
+    } else {
+        bonding_curve.effective_creator_fee_bps(ctx.accounts.config.max_creator_fee_bps)
+    };
+    let creator_fee = MathUtils::calculate_fee(sol_amount, creator_fee_bps as u64)?;
+    let seller_sol_amount = sol_amount
+        .checked_sub(creator_fee)
+        .ok_or(PumpError::MathOverflow)?;
     
     // Update bonding curve reserves
     bonding_curve.virtual_token_reserves = bonding_curve
@@ -87,12 +193,17 @@ pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64) -> Result<()> {
         .checked_sub(sol_amount)
         .ok_or(PumpError::MathOverflow)?;
     
-    // Transfer tokens from seller to curve vault
+    bonding_curve.check_sol_reserve_floor()?;
+    bonding_curve.check_redemption_backed()?;
+    let depth_rescaled = bonding_curve.count_trade()?;
+    bonding_curve.record_volume(gross_sol_amount)?;
+    
+    // Sold tokens return to the account buys draw from, so it keeps holding the real reserves
     let transfer_tokens_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
         Transfer {
             from: seller_token_account.to_account_info(),
-            to: curve_vault.to_account_info(),
+            to: bonding_curve_token_account.to_account_info(),
             authority: seller.to_account_info(),
         },
     );
@@ -104,19 +215,49 @@ pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64) -> Result<()> {
     let seeds = &[
         b"sol_vault",
         bonding_curve_key.as_ref(),
-        &[ctx.bumps.sol_vault],
+        &[bonding_curve.sol_vault_bump],
     ];
     let signer_seeds = &[&seeds[..]];
     
-    **sol_vault.try_borrow_mut_lamports()? = sol_vault
-        .lamports()
-        .checked_sub(sol_amount)
-        .ok_or(PumpError::MathOverflow)?;
+    // The vault is system-owned, so only the system program can debit it
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: sol_vault.to_account_info(),
+                to: seller.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        seller_sol_amount,
+    )?;
     
-    **seller.try_borrow_mut_lamports()? = seller
-        .lamports()
-        .checked_add(sol_amount)
-        .ok_or(PumpError::MathOverflow)?;
+    if creator_fee > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: sol_vault.to_account_info(),
+                    to: ctx.accounts.creator.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            creator_fee,
+        )?;
+        bonding_curve.record_creator_fee(creator_fee)?;
+    }
+    
+    let holder_stats = &mut ctx.accounts.holder_stats;
+    holder_stats.wallet = seller.key();
+    holder_stats.mint = ctx.accounts.token_mint.key();
+    holder_stats.bump = ctx.bumps.holder_stats;
+    let was_holder = holder_stats.token_balance > 0;
+    holder_stats.record_sell(token_amount, seller_sol_amount)?;
+    bonding_curve.update_holder_count(was_holder, holder_stats.token_balance > 0)?;
+    
+    // Both events of this sell carry the same sequence number
+    let sequence = bonding_curve.next_sequence()?;
+    emit!(bonding_curve.reserves_updated_event(Clock::get()?.slot, sequence));
     
     // Emit sell event
     emit!(TokenSellEvent {
@@ -124,18 +265,131 @@ pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64) -> Result<()> {
         token_mint: ctx.accounts.token_mint.key(),
         token_amount,
         sol_amount,
+        creator_fee,
         virtual_token_reserves: bonding_curve.virtual_token_reserves,
         virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+        real_token_reserves: bonding_curve.real_token_reserves,
+        real_sol_reserves: bonding_curve.real_sol_reserves,
+        mint_decimals: ctx.accounts.token_mint.decimals,
         timestamp: Clock::get()?.unix_timestamp,
+        depth_rescaled,
+        sequence,
     });
     
-    Ok(())
+    let fee = trade_fee
+        .checked_add(creator_fee)
+        .ok_or(PumpError::MathOverflow)?;
+    let order_filled = bonding_curve.order_filled(token_amount, seller_sol_amount, fee)?;
+    set_return_data(&order_filled.try_to_vec()?);
+    
+    ctx.accounts.close_emptied_token_account(close_ata)
+}
+
+/// Fill a sell from the creator's floor reserve: the creator receives the tokens and the
+/// curve's reserves are untouched. No fees apply since the creator is the counterparty.
+fn sell_to_floor(
+    ctx: Context<SellTokens>,
+    token_amount: u64,
+    floor_sol_amount: u64,
+    close_ata: bool,
+) -> Result<()> {
+    require!(floor_sol_amount > 0, PumpError::InvalidCalculation);
+    
+    let floor_vault = ctx.accounts.floor_vault.as_ref().ok_or(PumpError::MissingFloorAccounts)?;
+    let creator_token_account = ctx.accounts.creator_token_account
+        .as_ref()
+        .ok_or(PumpError::MissingFloorAccounts)?;
+    check_vault_rent_floor(floor_vault.lamports(), Rent::get()?.minimum_balance(0), floor_sol_amount)?;
+    
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: creator_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        token_amount,
+    )?;
+    
+    let bonding_curve_key = ctx.accounts.bonding_curve.key();
+    let seeds = &[
+        b"floor_vault",
+        bonding_curve_key.as_ref(),
+        &[ctx.accounts.bonding_curve.floor_vault_bump],
+    ];
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: floor_vault.to_account_info(),
+                to: ctx.accounts.seller.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        floor_sol_amount,
+    )?;
+    
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.floor_reserve = bonding_curve
+        .floor_reserve
+        .checked_sub(floor_sol_amount)
+        .ok_or(PumpError::MathOverflow)?;
+    
+    let holder_stats = &mut ctx.accounts.holder_stats;
+    holder_stats.wallet = ctx.accounts.seller.key();
+    holder_stats.mint = ctx.accounts.token_mint.key();
+    holder_stats.bump = ctx.bumps.holder_stats;
+    let was_holder = holder_stats.token_balance > 0;
+    holder_stats.record_sell(token_amount, floor_sol_amount)?;
+    bonding_curve.update_holder_count(was_holder, holder_stats.token_balance > 0)?;
+    
+    emit!(FloorBuybackEvent {
+        seller: ctx.accounts.seller.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        token_amount,
+        sol_amount: floor_sol_amount,
+        floor_reserve: bonding_curve.floor_reserve,
+        timestamp: Clock::get()?.unix_timestamp,
+        sequence: bonding_curve.next_sequence()?,
+    });
+    
+    let order_filled = bonding_curve.order_filled(token_amount, floor_sol_amount, 0)?;
+    set_return_data(&order_filled.try_to_vec()?);
+    
+    ctx.accounts.close_emptied_token_account(close_ata)
+}
+
+impl<'info> SellTokens<'info> {
+    /// Close the seller's token account once a sell has emptied it, if they asked to
+    fn close_emptied_token_account(&mut self, close_ata: bool) -> Result<()> {
+        self.seller_token_account.reload()?;
+        if !should_close_token_account(close_ata, self.seller_token_account.amount) {
+            return Ok(());
+        }
+        
+        token::close_account(CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.seller_token_account.to_account_info(),
+                destination: self.seller.to_account_info(),
+                authority: self.seller.to_account_info(),
+            },
+        ))
+    }
+}
+
+/// SPL only closes empty token accounts, so a partial exit keeps the account open
+fn should_close_token_account(close_ata: bool, remaining_balance: u64) -> bool {
+    close_ata && remaining_balance == 0
 }
 
 fn calculate_sell_price(
     virtual_token_reserves: u64,
     virtual_sol_reserves: u64,
     token_amount: u64,
+    fee_bps: u64,
 ) -> Result<u64> {
     // Using constant product formula: x * y = k
     // When selling tokens: new_sol_reserves = k / (token_reserves + token_amount)
@@ -157,11 +411,11 @@ fn calculate_sell_price(
         .checked_sub(new_sol_reserves)
         .ok_or(PumpError::MathOverflow)?;
     
-    // Apply fee (1% fee)
+    // Apply fee
     let fee = sol_out
-        .checked_mul(100)
+        .checked_mul(fee_bps as u128)
         .ok_or(PumpError::MathOverflow)?
-        .checked_div(10000)
+        .checked_div(BPS_DIVISOR as u128)
         .ok_or(PumpError::MathOverflow)?;
     
     let sol_out_after_fee = sol_out
@@ -171,14 +425,111 @@ fn calculate_sell_price(
     Ok(sol_out_after_fee as u64)
 }
 
+/// The vault's rent-exempt minimum is never paid out, so the PDA can't be reclaimed
+fn check_vault_rent_floor(vault_lamports: u64, rent_minimum: u64, sol_amount: u64) -> Result<()> {
+    let spendable = vault_lamports.saturating_sub(rent_minimum);
+    require!(sol_amount <= spendable, PumpError::VaultRentFloorReached);
+    Ok(())
+}
+
 #[event]
 pub struct TokenSellEvent {
     pub seller: Pubkey,
     pub token_mint: Pubkey,
     pub token_amount: u64,
     pub sol_amount: u64,
+    pub creator_fee: u64,
     pub virtual_token_reserves: u64,
     pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub mint_decimals: u8,
+    pub timestamp: i64,
+    /// Whether this trade deepened the virtual token reserves by the curve's depth multiplier
+    pub depth_rescaled: bool,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct FloorBuybackEvent {
+    pub seller: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub floor_reserve: u64,
     pub timestamp: i64,
+    pub sequence: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sell_fee_applied() {
+        let gross = calculate_sell_price(1_000_000, 1_000_000, 1_000, 0).unwrap();
+        let net = calculate_sell_price(1_000_000, 1_000_000, 1_000, DEFAULT_TRADE_FEE_BPS as u64).unwrap();
+
+        assert_eq!(gross, 1_000);
+        assert_eq!(net, gross - gross / 100);
+    }
+
+    #[test]
+    fn test_exempt_creator_pays_no_sell_fee() {
+        let non_exempt = calculate_sell_price(1_000_000_000, 30_000_000_000, 1_000_000, DEFAULT_TRADE_FEE_BPS as u64).unwrap();
+        let exempt = calculate_sell_price(1_000_000_000, 30_000_000_000, 1_000_000, 0).unwrap();
+
+        assert!(exempt > non_exempt);
+    }
+
+    #[test]
+    fn test_sells_drain_vault_to_rent_floor() {
+        const RENT_MINIMUM: u64 = 890_880;
+        let mut vault_lamports = RENT_MINIMUM + 10_000;
+
+        for _ in 0..4 {
+            assert!(check_vault_rent_floor(vault_lamports, RENT_MINIMUM, 2_500).is_ok());
+            vault_lamports -= 2_500;
+        }
+
+        assert_eq!(vault_lamports, RENT_MINIMUM);
+        assert!(check_vault_rent_floor(vault_lamports, RENT_MINIMUM, 1).is_err());
+    }
+
+    #[test]
+    fn test_sell_event_carries_decimals() {
+        let event = TokenSellEvent {
+            seller: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            token_amount: 1_000,
+            sol_amount: 990,
+            creator_fee: 0,
+            virtual_token_reserves: 1_000_000,
+            virtual_sol_reserves: 1_000_000,
+            real_token_reserves: 800_000,
+            real_sol_reserves: 990,
+            mint_decimals: 6,
+            timestamp: 0,
+            depth_rescaled: false,
+            sequence: 1,
+        };
+
+        let decoded = TokenSellEvent::try_from_slice(&event.try_to_vec().unwrap()).unwrap();
+        assert_eq!(decoded.mint_decimals, 6);
+    }
+
+    #[test]
+    fn test_token_account_closed_after_full_sell() {
+        let balance = 1_000;
+        let remaining = balance - 1_000;
+
+        assert!(should_close_token_account(true, remaining));
+        assert!(!should_close_token_account(false, remaining));
+    }
+
+    #[test]
+    fn test_token_account_kept_after_partial_sell() {
+        assert!(!should_close_token_account(true, 1));
+    }
 }
 ```
\ No newline at end of file
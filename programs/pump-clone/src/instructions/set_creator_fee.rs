@@ -0,0 +1,39 @@
+```rust
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetCreatorFee<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+        has_one = creator,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn set_creator_fee(ctx: Context<SetCreatorFee>, creator_fee_bps: u16) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    
+    bonding_curve.creator_fee_bps = creator_fee_bps.min(ctx.accounts.config.max_creator_fee_bps);
+    
+    emit!(CreatorFeeUpdatedEvent {
+        token_mint: bonding_curve.mint,
+        creator_fee_bps: bonding_curve.creator_fee_bps,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CreatorFeeUpdatedEvent {
+    pub token_mint: Pubkey,
+    pub creator_fee_bps: u16,
+}
+```
@@ -0,0 +1,42 @@
+```rust
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetGraduationThreshold<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+        has_one = creator,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn set_graduation_threshold(
+    ctx: Context<SetGraduationThreshold>,
+    graduation_threshold: u64,
+) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    
+    bonding_curve.set_graduation_threshold(graduation_threshold, ctx.accounts.config.min_graduation_sol)?;
+    
+    emit!(GraduationThresholdUpdatedEvent {
+        token_mint: bonding_curve.mint,
+        graduation_threshold,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct GraduationThresholdUpdatedEvent {
+    pub token_mint: Pubkey,
+    pub graduation_threshold: u64,
+}
+```
@@ -0,0 +1,69 @@
+```rust
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetPriceFloor<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+        has_one = creator,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    #[account(
+        mut,
+        seeds = [b"floor_vault", bonding_curve.key().as_ref()],
+        bump = bonding_curve.floor_vault_bump,
+    )]
+    pub floor_vault: SystemAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the floor price (scaled by `SPOT_PRICE_SCALE`) and top up the floor reserve
+/// that buys back tokens sold at or below it. A zero price turns the floor off.
+pub fn set_price_floor(ctx: Context<SetPriceFloor>, floor_price: u64, deposit: u64) -> Result<()> {
+    require!(floor_price > 0 || deposit == 0, PumpError::InvalidFloorPrice);
+    
+    if deposit > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.floor_vault.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+    }
+    
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.floor_price = floor_price;
+    bonding_curve.floor_reserve = bonding_curve
+        .floor_reserve
+        .checked_add(deposit)
+        .ok_or(PumpError::MathOverflow)?;
+    
+    emit!(PriceFloorUpdatedEvent {
+        token_mint: bonding_curve.mint,
+        floor_price,
+        floor_reserve: bonding_curve.floor_reserve,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct PriceFloorUpdatedEvent {
+    pub token_mint: Pubkey,
+    pub floor_price: u64,
+    pub floor_reserve: u64,
+}
+```
\ No newline at end of file
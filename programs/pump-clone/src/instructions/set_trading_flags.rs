@@ -0,0 +1,43 @@
+```rust
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetTradingFlags<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+        has_one = creator,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+pub fn set_trading_flags(
+    ctx: Context<SetTradingFlags>,
+    buys_enabled: bool,
+    sells_enabled: bool,
+) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    
+    bonding_curve.buys_enabled = buys_enabled;
+    bonding_curve.sells_enabled = sells_enabled;
+    
+    emit!(TradingFlagsUpdatedEvent {
+        token_mint: bonding_curve.mint,
+        buys_enabled,
+        sells_enabled,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct TradingFlagsUpdatedEvent {
+    pub token_mint: Pubkey,
+    pub buys_enabled: bool,
+    pub sells_enabled: bool,
+}
+```
\ No newline at end of file
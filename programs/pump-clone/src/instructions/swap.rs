@@ -0,0 +1,150 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_pool", token_mint.key().as_ref()],
+        bump = amm_pool.bump,
+    )]
+    pub amm_pool: Account<'info, AmmPool>,
+
+    #[account(address = amm_pool.mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_vault", amm_pool.key().as_ref()],
+        bump = amm_pool.token_vault_bump,
+    )]
+    pub pool_token_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buy tokens from a program-owned pool with `sol_in` lamports
+pub fn swap_sol_for_tokens(ctx: Context<Swap>, sol_in: u64, min_tokens_out: u64) -> Result<()> {
+    require!(sol_in > 0, PumpError::InvalidAmount);
+
+    let tokens_out = ctx.accounts.amm_pool.buy(sol_in, min_tokens_out)?;
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.amm_pool.to_account_info(),
+            },
+        ),
+        sol_in,
+    )?;
+
+    let mint_key = ctx.accounts.token_mint.key();
+    let seeds = &[
+        b"amm_pool",
+        mint_key.as_ref(),
+        &[ctx.accounts.amm_pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.pool_token_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.amm_pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        tokens_out,
+    )?;
+
+    emit!(ctx.accounts.swap_event(true, sol_in, tokens_out));
+
+    Ok(())
+}
+
+/// Sell `tokens_in` to a program-owned pool for lamports
+pub fn swap_tokens_for_sol(ctx: Context<Swap>, tokens_in: u64, min_sol_out: u64) -> Result<()> {
+    require!(tokens_in > 0, PumpError::InvalidAmount);
+    require!(ctx.accounts.user_token_account.amount >= tokens_in, PumpError::InsufficientTokens);
+
+    let sol_out = ctx.accounts.amm_pool.sell(tokens_in, min_sol_out)?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.pool_token_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        tokens_in,
+    )?;
+
+    // The pool account is program-owned and holds its SOL reserves directly
+    let amm_pool = ctx.accounts.amm_pool.to_account_info();
+    let rent_minimum = Rent::get()?.minimum_balance(AmmPool::LEN);
+    let pool_lamports = amm_pool
+        .lamports()
+        .checked_sub(sol_out)
+        .ok_or(PumpError::InsufficientSolVault)?;
+    require!(pool_lamports >= rent_minimum, PumpError::VaultRentFloorReached);
+
+    **amm_pool.try_borrow_mut_lamports()? = pool_lamports;
+    let user = ctx.accounts.user.to_account_info();
+    **user.try_borrow_mut_lamports()? = user
+        .lamports()
+        .checked_add(sol_out)
+        .ok_or(PumpError::MathOverflow)?;
+
+    emit!(ctx.accounts.swap_event(false, sol_out, tokens_in));
+
+    Ok(())
+}
+
+impl<'info> Swap<'info> {
+    fn swap_event(&self, is_buy: bool, sol_amount: u64, token_amount: u64) -> PoolSwapEvent {
+        PoolSwapEvent {
+            user: self.user.key(),
+            amm_pool: self.amm_pool.key(),
+            is_buy,
+            sol_amount,
+            token_amount,
+            token_reserves: self.amm_pool.token_reserves,
+            sol_reserves: self.amm_pool.sol_reserves,
+        }
+    }
+}
+
+#[event]
+pub struct PoolSwapEvent {
+    pub user: Pubkey,
+    pub amm_pool: Pubkey,
+    pub is_buy: bool,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub token_reserves: u64,
+    pub sol_reserves: u64,
+}
+```
\ No newline at end of file
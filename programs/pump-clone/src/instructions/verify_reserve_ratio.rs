@@ -0,0 +1,27 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct VerifyReserveRatio<'info> {
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+/// Monitoring aid: compares the curve's reserve ratio to its launch ratio and returns a
+/// borsh `ReserveRatioCheck` in return data, flagged past `max_deviation_bps`
+pub fn verify_reserve_ratio(ctx: Context<VerifyReserveRatio>, max_deviation_bps: u64) -> Result<()> {
+    let check = ctx.accounts.bonding_curve.check_reserve_ratio(max_deviation_bps)?;
+    
+    if check.flagged {
+        msg!("Reserve ratio deviates {} bps from launch", check.deviation_bps);
+    }
+    set_return_data(&check.try_to_vec()?);
+    
+    Ok(())
+}
+```
\ No newline at end of file
@@ -0,0 +1,33 @@
+```rust
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct VerifySolvency<'info> {
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    #[account(
+        seeds = [b"sol_vault", bonding_curve.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+}
+
+/// Returns whether the SOL vault covers a full redemption of outstanding tokens, as a
+/// borsh bool in return data
+pub fn verify_solvency(ctx: Context<VerifySolvency>) -> Result<()> {
+    let solvent = ctx.accounts.bonding_curve.verify_solvency(
+        ctx.accounts.sol_vault.lamports(),
+        Rent::get()?.minimum_balance(0),
+    )?;
+    
+    set_return_data(&solvent.try_to_vec()?);
+    
+    Ok(())
+}
+```
\ No newline at end of file
@@ -1,7 +1,22 @@
 ```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction, sysvar::instructions as ix_sysvar};
+
+pub mod constants;
+pub mod errors;
+pub mod instructions;
+pub mod state;
+pub mod utils;
+
+use instructions::*;
+use state::{
+    AuthorityStats, Config, UpdateConfigArgs, DEFAULT_HIGH_IMPACT_THRESHOLD_BPS,
+    DEFAULT_MAX_CREATOR_FEE_BPS, DEFAULT_MAX_MARKET_DURATION, DEFAULT_MIN_INITIAL_LIQUIDITY,
+    DEFAULT_MIN_MARKET_DURATION, DEFAULT_POOL_SWAP_FEE_BPS, DEFAULT_REWARD_VESTING_PERIOD,
+    DEFAULT_SNIPE_MAX_BUY, MAX_ALLOWED_QUOTE_MINTS,
+};
 
 declare_id!("11111111111111111111111111111112");
 
@@ -9,6 +24,79 @@ declare_id!("11111111111111111111111111111112");
 pub mod pump_clone {
     use super::*;
 
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        max_markets_per_authority: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        config.admin = ctx.accounts.admin.key();
+        config.max_markets_per_authority = max_markets_per_authority;
+        config.max_creator_fee_bps = DEFAULT_MAX_CREATOR_FEE_BPS;
+        config.min_market_duration = DEFAULT_MIN_MARKET_DURATION;
+        config.max_market_duration = DEFAULT_MAX_MARKET_DURATION;
+        config.referral_levels = 1;
+        config.unique_token_names = false;
+        config.high_impact_threshold_bps = DEFAULT_HIGH_IMPACT_THRESHOLD_BPS;
+        config.min_initial_liquidity = DEFAULT_MIN_INITIAL_LIQUIDITY;
+        config.max_allowed_slippage_bps = 0;
+        config.snipe_block_delay = 0;
+        config.snipe_max_buy = DEFAULT_SNIPE_MAX_BUY;
+        config.min_holders_for_graduation = 0;
+        config.fee_free_threshold_lamports = 0;
+        config.treasury = ctx.accounts.admin.key();
+        config.max_open_markets = 0;
+        config.open_markets = 0;
+        config.partner_fee_mint = Pubkey::default();
+        config.partner_fee_rate = 0;
+        config.partner_fee_discount_bps = 0;
+        config.hold_rebate_enabled = false;
+        config.quote_mint_allowlist_enabled = false;
+        config.allowed_quote_mints = [Pubkey::default(); MAX_ALLOWED_QUOTE_MINTS];
+        config.resolution_reward_bps = 0;
+        config.max_creator_total_supply = 0;
+        config.impact_fee_coefficient = 0;
+        config.internal_pool_graduation = false;
+        config.pool_swap_fee_bps = DEFAULT_POOL_SWAP_FEE_BPS;
+        config.launch_fee = 0;
+        config.min_graduation_sol = 0;
+        config.graduation_reward = 0;
+        config.reward_vesting_period = DEFAULT_REWARD_VESTING_PERIOD;
+        config.bump = ctx.bumps.config;
+
+        Ok(())
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
+        ctx.accounts.config.apply(args);
+
+        Ok(())
+    }
+
+    pub fn set_treasury(ctx: Context<UpdateConfig>, treasury: Pubkey) -> Result<()> {
+        ctx.accounts.config.treasury = treasury;
+
+        Ok(())
+    }
+
+    pub fn set_allowed_quote_mint(ctx: Context<UpdateConfig>, mint: Pubkey, allowed: bool) -> Result<()> {
+        ctx.accounts.config.set_allowed_quote_mint(mint, allowed)
+    }
+
+    /// Record a moderation or admin action on-chain for off-chain audit trails
+    pub fn admin_note(ctx: Context<AdminNote>, action_code: u16, note: String) -> Result<()> {
+        let event = admin_action_event(
+            ctx.accounts.admin.key(),
+            action_code,
+            note,
+            Clock::get()?.unix_timestamp,
+        )?;
+        emit!(event);
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_market(
         ctx: Context<InitializeMarket>,
         market_id: u64,
@@ -16,14 +104,26 @@ pub mod pump_clone {
         description: String,
         end_time: i64,
         initial_liquidity: u64,
+        min_price: u64,
+        max_price: u64,
+        oracle: Pubkey,
+        max_shares_per_user: u64,
     ) -> Result<()> {
         let market = &mut ctx.accounts.market;
         let clock = Clock::get()?;
 
         require!(end_time > clock.unix_timestamp, ErrorCode::InvalidEndTime);
+        ctx.accounts.config.check_market_duration(clock.unix_timestamp, end_time)?;
         require!(question.len() <= 200, ErrorCode::QuestionTooLong);
         require!(description.len() <= 1000, ErrorCode::DescriptionTooLong);
         require!(initial_liquidity > 0, ErrorCode::InvalidLiquidity);
+        ctx.accounts.config.check_initial_liquidity(initial_liquidity)?;
+        ctx.accounts.config.check_quote_mint(&ctx.accounts.mint.key())?;
+        require!(
+            min_price > 0 && min_price < max_price && max_price < PRICE_SCALE,
+            ErrorCode::InvalidPriceBounds
+        );
+        check_viable_liquidity(initial_liquidity, min_price, max_price)?;
 
         market.authority = ctx.accounts.authority.key();
         market.market_id = market_id;
@@ -34,9 +134,28 @@ pub mod pump_clone {
         market.total_yes_shares = 0;
         market.total_no_shares = 0;
         market.total_liquidity = initial_liquidity;
+        market.locked_liquidity = initial_liquidity;
         market.resolved = false;
         market.outcome = None;
-        market.bump = *ctx.bumps.get("market").unwrap();
+        market.min_price = min_price;
+        market.max_price = max_price;
+        market.oracle = oracle;
+        market.accrued_fees = 0;
+        market.claim_deadline = 0;
+        market.max_shares_per_user = max_shares_per_user;
+        market.claimed_winnings = 0;
+        market.dust = 0;
+        market.dust_remainder = 0;
+        market.bump = ctx.bumps.market;
+        market.vault_bump = ctx.bumps.market_vault;
+
+        let authority_stats = &mut ctx.accounts.authority_stats;
+        if authority_stats.authority == Pubkey::default() {
+            authority_stats.authority = ctx.accounts.authority.key();
+            authority_stats.bump = ctx.bumps.authority_stats;
+        }
+        authority_stats.record_market(ctx.accounts.config.max_markets_per_authority)?;
+        ctx.accounts.config.open_market()?;
 
         // Transfer initial liquidity
         let cpi_accounts = Transfer {
@@ -63,6 +182,8 @@ pub mod pump_clone {
         ctx: Context<BuyShares>,
         amount: u64,
         is_yes: bool,
+        max_cost: u64,
+        adjust: bool,
     ) -> Result<()> {
         let market = &mut ctx.accounts.market;
         let user_position = &mut ctx.accounts.user_position;
@@ -77,9 +198,22 @@ pub mod pump_clone {
             market.total_no_shares,
             market.total_liquidity,
             is_yes,
+            market.min_price,
+            market.max_price,
         )?;
 
-        let cost = (amount as u128 * price as u128 / 1_000_000) as u64;
+        let mut amount = amount;
+        let mut cost = calculate_share_cost(amount, price);
+
+        // Either shrink the order to fit max_cost or reject it outright
+        if cost > max_cost {
+            require!(adjust, ErrorCode::SlippageExceeded);
+            amount = calculate_max_shares_for_cost(amount, price, max_cost);
+            require!(amount > 0, ErrorCode::InvalidAmount);
+            cost = calculate_share_cost(amount, price);
+        }
+
+        check_position_size(market, user_position, amount)?;
 
         // Transfer payment
         let cpi_accounts = Transfer {
@@ -105,6 +239,7 @@ pub mod pump_clone {
             user_position.market = market.key();
             user_position.yes_shares = 0;
             user_position.no_shares = 0;
+            user_position.bump = ctx.bumps.user_position;
         }
 
         if is_yes {
@@ -150,13 +285,21 @@ pub mod pump_clone {
             market.total_no_shares,
             market.total_liquidity,
             is_yes,
+            market.min_price,
+            market.max_price,
         )?;
 
-        let payout = (amount as u128 * price as u128 / 1_000_000) as u64;
+        let payout = calculate_share_proceeds(amount, price);
+
+        // Seed liquidity stays in the vault until the market resolves
+        require!(
+            payout <= withdrawable_liquidity(market),
+            ErrorCode::LiquidityLocked
+        );
 
         // Transfer payout
         let seeds = &[
-            b"market",
+            b"market".as_ref(),
             &market.market_id.to_le_bytes(),
             &[market.bump],
         ];
@@ -198,9 +341,87 @@ pub mod pump_clone {
         Ok(())
     }
 
+    pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
+        let market = &ctx.accounts.market;
+
+        require!(ctx.accounts.authority.key() == market.authority, ErrorCode::Unauthorized);
+        check_cancellable(market)?;
+        ctx.accounts.config.close_market();
+        ctx.accounts.authority_stats.release_market();
+
+        let seeds = &[
+            b"market".as_ref(),
+            &market.market_id.to_le_bytes(),
+            &[market.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Refund whatever the vault holds, then close it; the market closes via `close`
+        let refund = ctx.accounts.market_vault.amount;
+        if refund > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.authority_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            token::transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), refund)?;
+        }
+
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.market_vault.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: market.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::close_account(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer))?;
+
+        emit!(MarketCancelled {
+            market: market.key(),
+            authority: market.authority,
+            refund,
+        });
+
+        Ok(())
+    }
+
+    pub fn extend_end_time(ctx: Context<ExtendEndTime>, new_end_time: i64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        let old_end_time = extend_market_end_time(
+            market,
+            &ctx.accounts.config,
+            &ctx.accounts.authority.key(),
+            new_end_time,
+        )?;
+
+        emit!(MarketEndTimeExtended {
+            market: market.key(),
+            old_end_time,
+            new_end_time,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_market_oracle(ctx: Context<SetMarketOracle>, new_oracle: Pubkey) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        let old_oracle = rotate_market_oracle(market, &ctx.accounts.authority.key(), new_oracle)?;
+
+        emit!(MarketOracleRotated {
+            market: market.key(),
+            old_oracle,
+            new_oracle,
+        });
+
+        Ok(())
+    }
+
     pub fn resolve_market(
         ctx: Context<ResolveMarket>,
         outcome: bool,
+        evidence_uri: String,
     ) -> Result<()> {
         let market = &mut ctx.accounts.market;
         let clock = Clock::get()?;
@@ -209,45 +430,102 @@ pub mod pump_clone {
         require!(!market.resolved, ErrorCode::MarketAlreadyResolved);
         require!(clock.unix_timestamp >= market.end_time, ErrorCode::MarketNotExpired);
 
-        market.resolved = true;
-        market.outcome = Some(outcome);
+        let market_key = market.key();
+        ctx.accounts.config.close_market();
 
-        emit!(MarketResolved {
-            market: market.key(),
-            outcome,
-            resolved_at: clock.unix_timestamp,
-        });
+        if void_if_untraded(
+            market,
+            &ctx.accounts.market_vault,
+            &ctx.accounts.authority_token_account,
+            &ctx.accounts.token_program,
+            clock.unix_timestamp,
+        )? {
+            return Ok(());
+        }
+
+        let event = record_resolution(market, market_key, outcome, evidence_uri, clock.unix_timestamp)?;
+
+        // The authority resolved it, so the authority is paid for the chore
+        pay_resolution_reward(
+            market,
+            &ctx.accounts.market_vault,
+            &ctx.accounts.authority_token_account,
+            &ctx.accounts.token_program,
+            ctx.accounts.config.resolution_reward_bps,
+        )?;
+
+        emit!(event);
+
+        Ok(())
+    }
+
+    pub fn resolve_with_signature(
+        ctx: Context<ResolveWithSignature>,
+        outcome: bool,
+        timestamp: i64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(market.oracle != Pubkey::default(), ErrorCode::OracleNotSet);
+        require!(!market.resolved, ErrorCode::MarketAlreadyResolved);
+        require!(clock.unix_timestamp >= market.end_time, ErrorCode::MarketNotExpired);
+        check_oracle_timestamp(timestamp, clock.unix_timestamp, market.end_time)?;
+
+        // The oracle's signature is checked by the ed25519 program in the preceding instruction
+        let instructions = ctx.accounts.instructions.to_account_info();
+        let current_index = ix_sysvar::load_current_index_checked(&instructions)?;
+        require!(current_index > 0, ErrorCode::InvalidOracleSignature);
+        let ed25519_ix = ix_sysvar::load_instruction_at_checked(current_index as usize - 1, &instructions)?;
+
+        let message = oracle_resolution_message(market.market_id, outcome, timestamp);
+        verify_ed25519_instruction(&ed25519_ix, &market.oracle, &message)?;
+
+        let market_key = market.key();
+        ctx.accounts.config.close_market();
+
+        if void_if_untraded(
+            market,
+            &ctx.accounts.market_vault,
+            &ctx.accounts.authority_token_account,
+            &ctx.accounts.token_program,
+            clock.unix_timestamp,
+        )? {
+            return Ok(());
+        }
+
+        // The signed message doesn't cover an evidence link, so none is recorded
+        let event = record_resolution(market, market_key, outcome, String::new(), clock.unix_timestamp)?;
+
+        // Whoever submitted the oracle's signature is paid for the chore
+        pay_resolution_reward(
+            market,
+            &ctx.accounts.market_vault,
+            &ctx.accounts.resolver_token_account,
+            &ctx.accounts.token_program,
+            ctx.accounts.config.resolution_reward_bps,
+        )?;
+
+        emit!(event);
 
         Ok(())
     }
 
     pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
-        let market = &ctx.accounts.market;
+        let market = &mut ctx.accounts.market;
         let user_position = &mut ctx.accounts.user_position;
 
         require!(market.resolved, ErrorCode::MarketNotResolved);
         require!(user_position.user == ctx.accounts.user.key(), ErrorCode::Unauthorized);
+        check_claim_window(market, Clock::get()?.unix_timestamp)?;
 
-        let outcome = market.outcome.unwrap();
-        let winning_shares = if outcome {
-            user_position.yes_shares
-        } else {
-            user_position.no_shares
-        };
-
-        require!(winning_shares > 0, ErrorCode::NoWinningShares);
-
-        let total_winning_shares = if outcome {
-            market.total_yes_shares
-        } else {
-            market.total_no_shares
-        };
-
-        let payout = (winning_shares as u128 * market.total_liquidity as u128 / total_winning_shares as u128) as u64;
+        let payout = record_winnings_claim(market, user_position)?;
+        require!(payout > 0, ErrorCode::NoWinningShares);
+        let payout = take_winnings_fee(market, payout)?;
 
         // Transfer winnings
         let seeds = &[
-            b"market",
+            b"market".as_ref(),
             &market.market_id.to_le_bytes(),
             &[market.bump],
         ];
@@ -274,158 +552,2149 @@ pub mod pump_clone {
 
         Ok(())
     }
-}
 
-fn calculate_share_price(
-    yes_shares: u64,
-    no_shares: u64,
-    liquidity: u64,
-    is_yes: bool,
-) -> Result<u64> {
-    if liquidity == 0 {
-        return Ok(500_000); // 0.5 price in micro-units
-    }
+    pub fn claim_all_winnings<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimAllWinnings<'info>>,
+    ) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
+        let user = &ctx.accounts.user;
 
-    let total_shares = yes_shares + no_shares;
-    if total_shares == 0 {
-        return Ok(500_000); // 0.5 price in micro-units
-    }
+        // Each claim is passed as [market, user_position, market_vault, user_token_account]
+        require!(
+            !remaining.is_empty() && remaining.len() % CLAIM_ACCOUNTS_PER_MARKET == 0,
+            ErrorCode::InvalidClaimAccounts
+        );
+        require!(
+            remaining.len() / CLAIM_ACCOUNTS_PER_MARKET <= MAX_CLAIM_BATCH,
+            ErrorCode::ClaimBatchTooLarge
+        );
+        let now = Clock::get()?.unix_timestamp;
 
-    let target_shares = if is_yes { yes_shares } else { no_shares };
-    let price = (target_shares as u128 * 1_000_000 / total_shares as u128) as u64;
-    
-    // Ensure price is between 0.01 and 0.99
-    Ok(price.max(10_000).min(990_000))
-}
+        for accounts in remaining.chunks(CLAIM_ACCOUNTS_PER_MARKET) {
+            let mut market: Account<'info, Market> = Account::try_from(&accounts[0])?;
+            let mut user_position: Account<'info, UserPosition> = Account::try_from(&accounts[1])?;
+            let market_vault: Account<'info, TokenAccount> = Account::try_from(&accounts[2])?;
+            let user_token_account: Account<'info, TokenAccount> = Account::try_from(&accounts[3])?;
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct InitializeMarket<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Market::INIT_SPACE,
-        seeds = [b"market", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        init,
-        payer = authority,
-        token::mint = mint,
-        token::authority = market,
-        seeds = [b"vault", market.key().as_ref()],
-        bump
-    )]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = authority
-    )]
-    pub authority_token_account: Account<'info, TokenAccount>,
-    
-    pub mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+            let position_key = Pubkey::create_program_address(
+                &[b"position", market.key().as_ref(), user.key().as_ref(), &[user_position.bump]],
+                ctx.program_id,
+            )
+            .map_err(|_| ErrorCode::InvalidClaimAccounts)?;
+            require_keys_eq!(user_position.key(), position_key, ErrorCode::InvalidClaimAccounts);
 
-#[derive(Accounts)]
-pub struct BuyShares<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + UserPosition::INIT_SPACE,
-        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
-        bump
-    )]
-    pub user_position: Account<'info, UserPosition>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = user
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault", market.key().as_ref()],
-        bump
-    )]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    pub mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+            let vault_key = Pubkey::create_program_address(
+                &[b"vault", market.key().as_ref(), &[market.vault_bump]],
+                ctx.program_id,
+            )
+            .map_err(|_| ErrorCode::InvalidClaimAccounts)?;
+            require_keys_eq!(market_vault.key(), vault_key, ErrorCode::InvalidClaimAccounts);
+            require_keys_eq!(user_token_account.owner, user.key(), ErrorCode::Unauthorized);
+            require!(user_position.user == user.key(), ErrorCode::Unauthorized);
 
-#[derive(Accounts)]
-pub struct SellShares<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        mut,
-        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
-        bump
-    )]
-    pub user_position: Account<'info, UserPosition>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = user
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault", market.key().as_ref()],
-        bump
-    )]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    pub mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
-}
+            // Unresolved or closed markets and losing positions are skipped rather than failing the batch
+            if !market.resolved || check_claim_window(&market, now).is_err() {
+                continue;
+            }
 
-#[derive(Accounts)]
-pub struct ResolveMarket<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    pub authority: Signer<'info>,
-}
+            let payout = record_winnings_claim(&mut market, &user_position)?;
+            if payout == 0 {
+                continue;
+            }
+            let payout = take_winnings_fee(&mut market, payout)?;
 
-#[derive(Accounts)]
-pub struct ClaimWinnings<'info> {
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        mut,
-        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
-        bump
-    )]
-    pub user_position: Account<'info, UserPosition>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
\ No newline at end of file
+            let seeds = &[
+                b"market".as_ref(),
+                &market.market_id.to_le_bytes(),
+                &[market.bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: market_vault.to_account_info(),
+                to: user_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, payout)?;
+
+            user_position.yes_shares = 0;
+            user_position.no_shares = 0;
+            user_position.exit(ctx.program_id)?;
+            market.exit(ctx.program_id)?;
+
+            emit!(WinningsClaimed {
+                market: market.key(),
+                user: user.key(),
+                amount: payout,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_market_fees(ctx: Context<WithdrawMarketFees>, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(ctx.accounts.authority.key() == market.authority, ErrorCode::Unauthorized);
+        withdraw_accrued_fees(market, amount)?;
+
+        let seeds = &[
+            b"market".as_ref(),
+            &market.market_id.to_le_bytes(),
+            &[market.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.authority_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit!(MarketFeesWithdrawn {
+            market: market.key(),
+            authority: market.authority,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(ctx.accounts.authority.key() == market.authority, ErrorCode::Unauthorized);
+        check_sweep_allowed(market, Clock::get()?.unix_timestamp)?;
+        ctx.accounts.config.check_treasury(ctx.accounts.treasury_token_account.owner)?;
+
+        let amount = ctx.accounts.market_vault.amount;
+        // Fees leave with everything else
+        market.accrued_fees = 0;
+
+        if amount > 0 {
+            let seeds = &[
+                b"market".as_ref(),
+                &market.market_id.to_le_bytes(),
+                &[market.bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.treasury_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        emit!(UnclaimedSwept {
+            market: market.key(),
+            treasury: ctx.accounts.treasury_token_account.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn get_position_value(ctx: Context<GetPositionValue>) -> Result<()> {
+        let value = calculate_position_value(&ctx.accounts.market, &ctx.accounts.user_position)?;
+
+        anchor_lang::solana_program::program::set_return_data(&value.try_to_vec()?);
+
+        Ok(())
+    }
+
+    pub fn aggregate_positions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AggregatePositions<'info>>,
+    ) -> Result<()> {
+        let user = ctx.accounts.user.key();
+        let remaining = ctx.remaining_accounts;
+
+        // Positions are passed as [market, user_position] pairs
+        require!(
+            !remaining.is_empty() && remaining.len() % 2 == 0,
+            ErrorCode::InvalidPositionAccounts
+        );
+        require!(
+            remaining.len() / 2 <= MAX_AGGREGATE_POSITIONS,
+            ErrorCode::TooManyPositions
+        );
+
+        let mut values = Vec::with_capacity(remaining.len() / 2);
+        for accounts in remaining.chunks(2) {
+            let market: Account<'info, Market> = Account::try_from(&accounts[0])?;
+            let user_position: Account<'info, UserPosition> = Account::try_from(&accounts[1])?;
+
+            require_keys_eq!(user_position.market, market.key(), ErrorCode::InvalidPositionAccounts);
+            require_keys_eq!(user_position.user, user, ErrorCode::InvalidPositionAccounts);
+
+            values.push(calculate_position_value(&market, &user_position)?);
+        }
+
+        let exposure = sum_position_values(&values)?;
+        anchor_lang::solana_program::program::set_return_data(&exposure.try_to_vec()?);
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_token(
+        ctx: Context<CreateToken>,
+        name: String,
+        symbol: String,
+        uri: String,
+        self_trade_guard: bool,
+        creator_fee_bps: u16,
+        creator_fee_exempt: bool,
+        fee_curve: Option<state::FeeCurve>,
+        mutable_metadata: bool,
+        depth_scaling: Option<state::DepthScaling>,
+        trading_start: i64,
+        trading_end: i64,
+        burn_remainder: bool,
+        creator_sell_locked_until_graduation: bool,
+        graduation_mode: state::GraduationMode,
+        royalty_bps: u16,
+        skip_metadata: bool,
+    ) -> Result<()> {
+        instructions::create_token::create_token(
+            ctx,
+            name,
+            symbol,
+            uri,
+            self_trade_guard,
+            creator_fee_bps,
+            creator_fee_exempt,
+            fee_curve,
+            mutable_metadata,
+            depth_scaling,
+            trading_start,
+            trading_end,
+            burn_remainder,
+            creator_sell_locked_until_graduation,
+            graduation_mode,
+            royalty_bps,
+            skip_metadata,
+        )
+    }
+
+    pub fn initialize_sol_vault(ctx: Context<InitializeSolVault>) -> Result<()> {
+        instructions::initialize_sol_vault::initialize_sol_vault(ctx)
+    }
+
+    pub fn buy_tokens(
+        ctx: Context<BuyTokens>,
+        sol_amount: u64,
+        client_order_id: Option<u64>,
+        clamp_to_graduation: bool,
+    ) -> Result<()> {
+        instructions::buy_tokens::buy_tokens(ctx, sol_amount, client_order_id, clamp_to_graduation)
+    }
+
+    pub fn quote_then_buy(
+        ctx: Context<BuyTokens>,
+        sol_amount: u64,
+        client_order_id: Option<u64>,
+        quoted_tokens_out: u64,
+    ) -> Result<()> {
+        instructions::buy_tokens::quote_then_buy(ctx, sol_amount, client_order_id, quoted_tokens_out)
+    }
+
+    pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64, close_ata: bool) -> Result<()> {
+        instructions::sell_tokens::sell_tokens(ctx, token_amount, close_ata)
+    }
+
+    pub fn schedule_twap_sell(
+        ctx: Context<ScheduleTwapSell>,
+        total_amount: u64,
+        intervals: u16,
+        interval_seconds: i64,
+    ) -> Result<()> {
+        instructions::schedule_twap_sell::schedule_twap_sell(ctx, total_amount, intervals, interval_seconds)
+    }
+
+    pub fn execute_twap_slice<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteTwapSlice<'info>>,
+    ) -> Result<()> {
+        instructions::execute_twap_slice::execute_twap_slice(ctx)
+    }
+
+    pub fn graduate_token(ctx: Context<GraduateToken>) -> Result<()> {
+        instructions::graduate_token::graduate_token(ctx)
+    }
+
+    pub fn graduate_to_pool(ctx: Context<GraduateToPool>) -> Result<()> {
+        instructions::graduate_to_pool::graduate_to_pool(ctx)
+    }
+
+    pub fn swap_sol_for_tokens(ctx: Context<Swap>, sol_in: u64, min_tokens_out: u64) -> Result<()> {
+        instructions::swap::swap_sol_for_tokens(ctx, sol_in, min_tokens_out)
+    }
+
+    pub fn swap_tokens_for_sol(ctx: Context<Swap>, tokens_in: u64, min_sol_out: u64) -> Result<()> {
+        instructions::swap::swap_tokens_for_sol(ctx, tokens_in, min_sol_out)
+    }
+
+    pub fn register_referral(ctx: Context<RegisterReferral>, referrer: Pubkey) -> Result<()> {
+        instructions::register_referral::register_referral(ctx, referrer)
+    }
+
+    pub fn set_creator_fee(ctx: Context<SetCreatorFee>, creator_fee_bps: u16) -> Result<()> {
+        instructions::set_creator_fee::set_creator_fee(ctx, creator_fee_bps)
+    }
+
+    pub fn set_graduation_threshold(
+        ctx: Context<SetGraduationThreshold>,
+        graduation_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_graduation_threshold::set_graduation_threshold(ctx, graduation_threshold)
+    }
+
+    pub fn set_price_floor(ctx: Context<SetPriceFloor>, floor_price: u64, deposit: u64) -> Result<()> {
+        instructions::set_price_floor::set_price_floor(ctx, floor_price, deposit)
+    }
+
+    pub fn set_trading_flags(
+        ctx: Context<SetTradingFlags>,
+        buys_enabled: bool,
+        sells_enabled: bool,
+    ) -> Result<()> {
+        instructions::set_trading_flags::set_trading_flags(ctx, buys_enabled, sells_enabled)
+    }
+
+    pub fn refresh_market_cap(ctx: Context<RefreshMarketCap>) -> Result<()> {
+        instructions::refresh_market_cap::refresh_market_cap(ctx)
+    }
+
+    pub fn get_curve_state(ctx: Context<GetCurveState>) -> Result<()> {
+        instructions::get_curve_state::get_curve_state(ctx)
+    }
+
+    pub fn get_display_price(ctx: Context<GetDisplayPrice>) -> Result<()> {
+        instructions::get_display_price::get_display_price(ctx)
+    }
+
+    pub fn get_graduation_eta(ctx: Context<GetGraduationEta>) -> Result<()> {
+        instructions::get_graduation_eta::get_graduation_eta(ctx)
+    }
+
+    pub fn creator_summary<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetCreatorSummary<'info>>,
+    ) -> Result<()> {
+        instructions::creator_summary::creator_summary(ctx)
+    }
+
+    pub fn verify_reserve_ratio(ctx: Context<VerifyReserveRatio>, max_deviation_bps: u64) -> Result<()> {
+        instructions::verify_reserve_ratio::verify_reserve_ratio(ctx, max_deviation_bps)
+    }
+
+    pub fn verify_solvency(ctx: Context<VerifySolvency>) -> Result<()> {
+        instructions::verify_solvency::verify_solvency(ctx)
+    }
+
+    pub fn reconcile_vaults(ctx: Context<ReconcileVaults>) -> Result<()> {
+        instructions::reconcile_vaults::reconcile_vaults(ctx)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::claim_rewards::claim_rewards(ctx)
+    }
+}
+
+/// Reject a buy that would take the user's combined yes and no shares past the market cap
+fn check_position_size(market: &Market, user_position: &UserPosition, amount: u64) -> Result<()> {
+    if market.max_shares_per_user == 0 {
+        return Ok(());
+    }
+
+    let total_shares = user_position
+        .yes_shares
+        .checked_add(user_position.no_shares)
+        .and_then(|shares| shares.checked_add(amount))
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(total_shares <= market.max_shares_per_user, ErrorCode::PositionSizeExceeded);
+
+    Ok(())
+}
+
+/// Maximum length of an `admin_note` note
+pub const MAX_ADMIN_NOTE_LEN: usize = 200;
+
+fn admin_action_event(admin: Pubkey, action_code: u16, note: String, timestamp: i64) -> Result<AdminActionEvent> {
+    require!(note.len() <= MAX_ADMIN_NOTE_LEN, ErrorCode::AdminNoteTooLong);
+
+    Ok(AdminActionEvent {
+        admin,
+        action_code,
+        note,
+        timestamp,
+    })
+}
+
+/// A market can be cancelled only while unresolved and before anyone has bought shares
+fn check_cancellable(market: &Market) -> Result<()> {
+    require!(!market.resolved, ErrorCode::MarketAlreadyResolved);
+    require!(
+        market.total_yes_shares == 0 && market.total_no_shares == 0,
+        ErrorCode::MarketHasTrades
+    );
+    Ok(())
+}
+
+/// Push an unresolved market's end time later, returning the previous end time.
+/// Only the market authority or its oracle may extend, and the total lifetime
+/// stays within the Config duration bounds.
+fn extend_market_end_time(
+    market: &mut Market,
+    config: &Config,
+    signer: &Pubkey,
+    new_end_time: i64,
+) -> Result<i64> {
+    require!(
+        *signer == market.authority || (market.oracle != Pubkey::default() && *signer == market.oracle),
+        ErrorCode::Unauthorized
+    );
+    require!(!market.resolved, ErrorCode::MarketAlreadyResolved);
+    require!(new_end_time > market.end_time, ErrorCode::InvalidEndTime);
+    config.check_market_duration(market.created_at, new_end_time)?;
+
+    let old_end_time = market.end_time;
+    market.end_time = new_end_time;
+
+    Ok(old_end_time)
+}
+
+/// Replace an unresolved market's oracle, returning the previous one. Only the market
+/// authority or the current oracle may rotate it.
+fn rotate_market_oracle(market: &mut Market, signer: &Pubkey, new_oracle: Pubkey) -> Result<Pubkey> {
+    require!(
+        *signer == market.authority || (market.oracle != Pubkey::default() && *signer == market.oracle),
+        ErrorCode::Unauthorized
+    );
+    require!(!market.resolved, ErrorCode::MarketAlreadyResolved);
+
+    let old_oracle = market.oracle;
+    market.oracle = new_oracle;
+
+    Ok(old_oracle)
+}
+
+/// Maximum length of the resolution evidence link
+pub const MAX_EVIDENCE_URI_LEN: usize = 200;
+
+/// Mark the market resolved and build its `MarketResolved` event
+fn record_resolution(
+    market: &mut Market,
+    market_key: Pubkey,
+    outcome: bool,
+    evidence_uri: String,
+    resolved_at: i64,
+) -> Result<MarketResolved> {
+    require!(evidence_uri.len() <= MAX_EVIDENCE_URI_LEN, ErrorCode::EvidenceUriTooLong);
+
+    market.resolved = true;
+    market.outcome = Some(outcome);
+    market.evidence_uri = evidence_uri;
+    market.claim_deadline = resolved_at
+        .checked_add(CLAIM_WINDOW)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(MarketResolved {
+        market: market_key,
+        outcome,
+        evidence_uri: market.evidence_uri.clone(),
+        resolved_at,
+    })
+}
+
+/// Carve the resolver's reward out of a resolved market's liquidity. The Config cap
+/// keeps it to a small fraction, and winners share what is left.
+fn take_resolution_reward(market: &mut Market, reward_bps: u16) -> Result<u64> {
+    let reward = (market.total_liquidity as u128 * reward_bps as u128 / 10_000) as u64;
+
+    market.total_liquidity = market
+        .total_liquidity
+        .checked_sub(reward)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(reward)
+}
+
+/// Take the resolution reward and pay it from the market vault to the resolver's token account
+fn pay_resolution_reward<'info>(
+    market: &mut Account<'info, Market>,
+    market_vault: &Account<'info, TokenAccount>,
+    resolver_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    reward_bps: u16,
+) -> Result<u64> {
+    // Out of the pool winners share
+    let reward = take_resolution_reward(market, reward_bps)?;
+    if reward > 0 {
+        let seeds = &[
+            b"market".as_ref(),
+            &market.market_id.to_le_bytes(),
+            &[market.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: market_vault.to_account_info(),
+            to: resolver_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        };
+        let cpi_program = token_program.to_account_info();
+        token::transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), reward)?;
+        msg!("Resolution reward of {} paid to {}", reward, resolver_token_account.owner);
+    }
+
+    Ok(reward)
+}
+
+/// Nobody traded, so there is nothing to settle: void the market and hand the seed liquidity
+/// back to its authority. Returns whether the market was voided.
+fn void_if_untraded<'info>(
+    market: &mut Account<'info, Market>,
+    market_vault: &Account<'info, TokenAccount>,
+    authority_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    now: i64,
+) -> Result<bool> {
+    if market.total_yes_shares != 0 || market.total_no_shares != 0 {
+        return Ok(false);
+    }
+
+    let market_key = market.key();
+    let event = record_void(market, market_key, now)?;
+
+    if event.refund > 0 {
+        let seeds = &[
+            b"market".as_ref(),
+            &market.market_id.to_le_bytes(),
+            &[market.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: market_vault.to_account_info(),
+            to: authority_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        };
+        let cpi_program = token_program.to_account_info();
+        token::transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), event.refund)?;
+    }
+
+    emit!(event);
+    Ok(true)
+}
+
+/// Close out an untraded market with no outcome, releasing its locked seed liquidity
+fn record_void(market: &mut Market, market_key: Pubkey, voided_at: i64) -> Result<MarketVoidedEvent> {
+    let refund = market.locked_liquidity;
+
+    market.resolved = true;
+    market.outcome = None;
+    market.locked_liquidity = 0;
+    market.total_liquidity = market
+        .total_liquidity
+        .checked_sub(refund)
+        .ok_or(ErrorCode::MathOverflow)?;
+    market.claim_deadline = voided_at;
+
+    Ok(MarketVoidedEvent {
+        market: market_key,
+        authority: market.authority,
+        refund,
+        voided_at,
+    })
+}
+
+/// Seconds after resolution during which winners can claim (90 days)
+pub const CLAIM_WINDOW: i64 = 90 * 24 * 60 * 60;
+
+fn check_claim_window(market: &Market, now: i64) -> Result<()> {
+    require!(now <= market.claim_deadline, ErrorCode::ClaimDeadlinePassed);
+    Ok(())
+}
+
+/// Unclaimed funds may only be swept from a resolved market whose claim window has closed
+fn check_sweep_allowed(market: &Market, now: i64) -> Result<()> {
+    require!(market.resolved, ErrorCode::MarketNotResolved);
+    require!(now > market.claim_deadline, ErrorCode::ClaimWindowOpen);
+    Ok(())
+}
+
+/// Oldest oracle signature, in seconds, accepted by `resolve_with_signature`
+pub const MAX_ORACLE_SIGNATURE_AGE: i64 = 300;
+
+/// Message the oracle signs: `market_id (u64 LE) || outcome (u8) || timestamp (i64 LE)`
+fn oracle_resolution_message(market_id: u64, outcome: bool, timestamp: i64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(17);
+    message.extend_from_slice(&market_id.to_le_bytes());
+    message.push(outcome as u8);
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message
+}
+
+/// Reject signatures made before the market ended, in the future, or too long ago
+fn check_oracle_timestamp(timestamp: i64, now: i64, end_time: i64) -> Result<()> {
+    require!(timestamp >= end_time, ErrorCode::StaleOracleSignature);
+    require!(timestamp <= now, ErrorCode::StaleOracleSignature);
+    require!(now - timestamp <= MAX_ORACLE_SIGNATURE_AGE, ErrorCode::StaleOracleSignature);
+    Ok(())
+}
+
+/// Check that `ix` is a single-signature ed25519 verification of `message` by `signer`,
+/// with all data carried inline in that instruction.
+fn verify_ed25519_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    const HEADER_LEN: usize = 16;
+    const PUBKEY_LEN: usize = 32;
+    const SIGNATURE_LEN: usize = 64;
+
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::InvalidOracleSignature);
+    require!(ix.accounts.is_empty(), ErrorCode::InvalidOracleSignature);
+
+    let data = &ix.data;
+    require!(data.len() >= HEADER_LEN && data[0] == 1, ErrorCode::InvalidOracleSignature);
+
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let signature_offset = read_u16(2) as usize;
+    let signature_ix_index = read_u16(4);
+    let pubkey_offset = read_u16(6) as usize;
+    let pubkey_ix_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix_index = read_u16(14);
+
+    // u16::MAX means "this instruction"; anything else could point at attacker-controlled data
+    require!(
+        signature_ix_index == u16::MAX && pubkey_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        ErrorCode::InvalidOracleSignature
+    );
+    require!(
+        data.len() >= signature_offset + SIGNATURE_LEN
+            && data.len() >= pubkey_offset + PUBKEY_LEN
+            && data.len() >= message_offset + message_size,
+        ErrorCode::InvalidOracleSignature
+    );
+
+    require!(
+        &data[pubkey_offset..pubkey_offset + PUBKEY_LEN] == signer.as_ref(),
+        ErrorCode::InvalidOracleSignature
+    );
+    require!(
+        &data[message_offset..message_offset + message_size] == message,
+        ErrorCode::InvalidOracleSignature
+    );
+
+    Ok(())
+}
+
+/// Number of accounts supplied per market to `claim_all_winnings`
+pub const CLAIM_ACCOUNTS_PER_MARKET: usize = 4;
+
+/// Maximum markets settled in one `claim_all_winnings` call, keeping the batch within compute limits
+pub const MAX_CLAIM_BATCH: usize = 8;
+
+/// Fee kept from each winnings claim, accrued on the market for its authority (1%)
+pub const WINNINGS_FEE_BPS: u64 = 100;
+
+/// Deduct the winnings fee from a payout and accrue it on the market, returning the net payout
+fn take_winnings_fee(market: &mut Market, payout: u64) -> Result<u64> {
+    let fee = (payout as u128 * WINNINGS_FEE_BPS as u128 / 10_000) as u64;
+
+    market.accrued_fees = market
+        .accrued_fees
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(payout - fee)
+}
+
+/// Take `amount` out of a resolved market's accrued fees
+fn withdraw_accrued_fees(market: &mut Market, amount: u64) -> Result<()> {
+    require!(market.resolved, ErrorCode::MarketNotResolved);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(amount <= market.accrued_fees, ErrorCode::InsufficientAccruedFees);
+
+    market.accrued_fees -= amount;
+
+    Ok(())
+}
+
+/// Liquidity that can leave the vault right now. The authority's seed liquidity is
+/// locked until resolution, after which it joins the pool paid out to winners.
+fn withdrawable_liquidity(market: &Market) -> u64 {
+    if market.resolved {
+        market.total_liquidity
+    } else {
+        market.total_liquidity.saturating_sub(market.locked_liquidity)
+    }
+}
+
+/// Shares a position is paid out on and the total they are a fraction of
+fn payout_shares(market: &Market, user_position: &UserPosition) -> Result<(u64, u64)> {
+    let outcome = market.outcome.ok_or(ErrorCode::MarketNotResolved)?;
+    let (winning_shares, losing_shares) = if outcome {
+        (user_position.yes_shares, user_position.no_shares)
+    } else {
+        (user_position.no_shares, user_position.yes_shares)
+    };
+    let (total_winning_shares, total_losing_shares) = if outcome {
+        (market.total_yes_shares, market.total_no_shares)
+    } else {
+        (market.total_no_shares, market.total_yes_shares)
+    };
+
+    // Nobody backed the winning side, so refund the whole pool to the other side
+    let (shares, total_shares) = if total_winning_shares == 0 {
+        (losing_shares, total_losing_shares)
+    } else {
+        (winning_shares, total_winning_shares)
+    };
+
+    if shares > 0 {
+        require!(total_shares > 0, ErrorCode::NoWinningShares);
+    }
+
+    Ok((shares, total_shares))
+}
+
+/// Payout owed to a position on a resolved market, or zero if it holds no winning shares
+fn calculate_winnings_payout(market: &Market, user_position: &UserPosition) -> Result<u64> {
+    let (shares, total_shares) = payout_shares(market, user_position)?;
+    if shares == 0 {
+        return Ok(0);
+    }
+
+    let payout = (shares as u128)
+        .checked_mul(market.total_liquidity as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        / total_shares as u128;
+
+    u64::try_from(payout).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+/// Book a claim against the payout pool. Each payout rounds down and the truncated
+/// fractions are carried into `dust`, so claims plus dust never exceed the pool.
+fn record_winnings_claim(market: &mut Market, user_position: &UserPosition) -> Result<u64> {
+    let payout = calculate_winnings_payout(market, user_position)?;
+    let (shares, total_shares) = payout_shares(market, user_position)?;
+    if shares == 0 {
+        return Ok(0);
+    }
+
+    let remainder = (shares as u128 * market.total_liquidity as u128) % total_shares as u128
+        + market.dust_remainder as u128;
+    market.dust = market
+        .dust
+        .checked_add((remainder / total_shares as u128) as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    market.dust_remainder = (remainder % total_shares as u128) as u64;
+
+    market.claimed_winnings = market
+        .claimed_winnings
+        .checked_add(payout)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        market.claimed_winnings.saturating_add(market.dust) <= market.total_liquidity,
+        ErrorCode::VaultOverdrawn
+    );
+
+    Ok(payout)
+}
+
+/// Mark-to-market value of a position's yes and no shares at current prices
+fn calculate_position_value(market: &Market, user_position: &UserPosition) -> Result<PositionValue> {
+    let yes_price = calculate_share_price(
+        market.total_yes_shares,
+        market.total_no_shares,
+        market.total_liquidity,
+        true,
+        market.min_price,
+        market.max_price,
+    )?;
+    let no_price = calculate_share_price(
+        market.total_yes_shares,
+        market.total_no_shares,
+        market.total_liquidity,
+        false,
+        market.min_price,
+        market.max_price,
+    )?;
+
+    let yes_value = calculate_share_proceeds(user_position.yes_shares, yes_price);
+    let no_value = calculate_share_proceeds(user_position.no_shares, no_price);
+
+    Ok(PositionValue {
+        yes_shares: user_position.yes_shares,
+        no_shares: user_position.no_shares,
+        yes_price,
+        no_price,
+        yes_value,
+        no_value,
+        total_value: yes_value.checked_add(no_value).ok_or(ErrorCode::MathOverflow)?,
+    })
+}
+
+/// Cost of `amount` micro-shares at `price`, rounded up so no buy is free
+fn calculate_share_cost(amount: u64, price: u64) -> u64 {
+    let scaled = amount as u128 * price as u128;
+    ((scaled + SHARE_SCALE as u128 - 1) / SHARE_SCALE as u128) as u64
+}
+
+/// Proceeds of `amount` micro-shares at `price`, rounded down so a buy and sell never profit
+fn calculate_share_proceeds(amount: u64, price: u64) -> u64 {
+    (amount as u128 * price as u128 / SHARE_SCALE as u128) as u64
+}
+
+/// Largest share amount, up to `max_amount`, whose cost at `price` fits within `max_cost`
+fn calculate_max_shares_for_cost(max_amount: u64, price: u64, max_cost: u64) -> u64 {
+    let mut low = 0u64;
+    let mut high = max_amount;
+
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if calculate_share_cost(mid, price) <= max_cost {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    low
+}
+
+/// Maximum positions summed in one `aggregate_positions` call
+pub const MAX_AGGREGATE_POSITIONS: usize = 16;
+
+fn sum_position_values(values: &[PositionValue]) -> Result<AggregateExposure> {
+    let mut exposure = AggregateExposure::default();
+
+    for value in values {
+        exposure.total_yes_shares = exposure.total_yes_shares
+            .checked_add(value.yes_shares)
+            .ok_or(ErrorCode::MathOverflow)?;
+        exposure.total_no_shares = exposure.total_no_shares
+            .checked_add(value.no_shares)
+            .ok_or(ErrorCode::MathOverflow)?;
+        exposure.total_value = exposure.total_value
+            .checked_add(value.total_value)
+            .ok_or(ErrorCode::MathOverflow)?;
+        exposure.position_count += 1;
+    }
+
+    Ok(exposure)
+}
+
+/// Share prices are quoted in micro-units, so 1_000_000 is a price of 1.0
+pub const PRICE_SCALE: u64 = 1_000_000;
+
+/// Share amounts are in micro-shares, so 1_000_000 is one whole share and
+/// 500_000 is half of one. A whole share at price 1.0 costs `PRICE_SCALE` base units.
+pub const SHARE_SCALE: u64 = 1_000_000;
+
+/// Default price floor (0.01) for markets that don't need more extreme probabilities
+pub const DEFAULT_MIN_PRICE: u64 = 10_000;
+
+/// Default price ceiling (0.99)
+pub const DEFAULT_MAX_PRICE: u64 = 990_000;
+
+/// Smallest seed liquidity that can price shares at both ends of the band: a share at
+/// `min_price`, and the opposite side of one at `max_price`, must cost at least one
+/// base unit, so the liquidity has to span `PRICE_SCALE / tick` units for the tighter tick.
+fn min_viable_liquidity(min_price: u64, max_price: u64) -> u64 {
+    let tightest_tick = min_price.min(PRICE_SCALE - max_price);
+    (PRICE_SCALE + tightest_tick - 1) / tightest_tick
+}
+
+/// Reject markets whose seed liquidity is too small to support their price band
+fn check_viable_liquidity(initial_liquidity: u64, min_price: u64, max_price: u64) -> Result<()> {
+    require!(
+        initial_liquidity >= min_viable_liquidity(min_price, max_price),
+        ErrorCode::LiquidityBelowViableMinimum
+    );
+    Ok(())
+}
+
+fn calculate_share_price(
+    yes_shares: u64,
+    no_shares: u64,
+    liquidity: u64,
+    is_yes: bool,
+    min_price: u64,
+    max_price: u64,
+) -> Result<u64> {
+    if liquidity == 0 {
+        return Ok(500_000); // 0.5 price in micro-units
+    }
+
+    let total_shares = yes_shares + no_shares;
+    if total_shares == 0 {
+        return Ok(500_000); // 0.5 price in micro-units
+    }
+
+    let target_shares = if is_yes { yes_shares } else { no_shares };
+    let price = (target_shares as u128 * PRICE_SCALE as u128 / total_shares as u128) as u64;
+    
+    // Clamp to the market's configured price band
+    Ok(price.max(min_price).min(max_price))
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Config::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminNote<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct InitializeMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = market,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuthorityStats::LEN,
+        seeds = [b"authority_stats", authority.key().as_ref()],
+        bump
+    )]
+    pub authority_stats: Account<'info, AuthorityStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BuyShares<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SellShares<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump = user_position.bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(mut, close = authority)]
+    pub market: Account<'info, Market>,
+    
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"authority_stats", authority.key().as_ref()],
+        bump = authority_stats.bump
+    )]
+    pub authority_stats: Account<'info, AuthorityStats>,
+    
+    #[account(
+        mut,
+        token::authority = authority
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendEndTime<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketOracle<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    pub authority: Signer<'info>,
+    
+    /// Receives the resolution reward, or the seed refund of an untraded market
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = market_vault.mint
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveWithSignature<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    /// Submits the oracle's signature and is paid the resolution reward
+    pub resolver: Signer<'info>,
+    
+    #[account(
+        mut,
+        token::authority = resolver,
+        token::mint = market_vault.mint
+    )]
+    pub resolver_token_account: Account<'info, TokenAccount>,
+    
+    /// Receives the seed refund if the market is voided
+    #[account(
+        mut,
+        token::authority = market.authority,
+        token::mint = market_vault.mint
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: Address is checked to be the instructions sysvar
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump = user_position.bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawMarketFees<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        token::authority = authority
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        token::mint = market_vault.mint
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAllWinnings<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct GetPositionValue<'info> {
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"position", market.key().as_ref(), user_position.user.as_ref()],
+        bump = user_position.bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+}
+
+/// Return data of `get_position_value`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct PositionValue {
+    pub yes_shares: u64,
+    pub no_shares: u64,
+    pub yes_price: u64,
+    pub no_price: u64,
+    pub yes_value: u64,
+    pub no_value: u64,
+    pub total_value: u64,
+}
+
+#[derive(Accounts)]
+pub struct AggregatePositions<'info> {
+    /// Wallet whose positions are summed; every position passed must belong to it
+    pub user: SystemAccount<'info>,
+}
+
+/// Return data of `aggregate_positions`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct AggregateExposure {
+    pub total_yes_shares: u64,
+    pub total_no_shares: u64,
+    pub total_value: u64,
+    pub position_count: u32,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Market {
+    pub authority: Pubkey,
+    pub market_id: u64,
+    #[max_len(200)]
+    pub question: String,
+    #[max_len(1000)]
+    pub description: String,
+    pub end_time: i64,
+    pub created_at: i64,
+    pub total_yes_shares: u64,
+    pub total_no_shares: u64,
+    pub total_liquidity: u64,
+    pub locked_liquidity: u64,
+    pub resolved: bool,
+    pub outcome: Option<bool>,
+    pub min_price: u64,
+    pub max_price: u64,
+    pub oracle: Pubkey,
+    #[max_len(200)]
+    pub evidence_uri: String,
+    pub accrued_fees: u64,
+    pub claim_deadline: i64, // set at resolution
+    pub max_shares_per_user: u64, // 0 = unlimited
+    pub claimed_winnings: u64,
+    pub dust: u64, // rounding left in the vault by claims
+    pub dust_remainder: u64, // fraction of a unit not yet in dust, over the winning shares
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserPosition {
+    pub user: Pubkey,
+    pub market: Pubkey,
+    pub yes_shares: u64,
+    pub no_shares: u64,
+    pub bump: u8,
+}
+
+#[event]
+pub struct MarketCreated {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub market_id: u64,
+    pub question: String,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct SharesPurchased {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub is_yes: bool,
+    pub price: u64,
+    pub cost: u64,
+}
+
+#[event]
+pub struct SharesSold {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub is_yes: bool,
+    pub price: u64,
+    pub payout: u64,
+}
+
+#[event]
+pub struct AdminActionEvent {
+    pub admin: Pubkey,
+    pub action_code: u16,
+    pub note: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketResolved {
+    pub market: Pubkey,
+    pub outcome: bool,
+    pub evidence_uri: String,
+    pub resolved_at: i64,
+}
+
+#[event]
+pub struct MarketCancelled {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub refund: u64,
+}
+
+#[event]
+pub struct MarketVoidedEvent {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub refund: u64,
+    pub voided_at: i64,
+}
+
+#[event]
+pub struct MarketEndTimeExtended {
+    pub market: Pubkey,
+    pub old_end_time: i64,
+    pub new_end_time: i64,
+}
+
+#[event]
+pub struct MarketOracleRotated {
+    pub market: Pubkey,
+    pub old_oracle: Pubkey,
+    pub new_oracle: Pubkey,
+}
+
+#[event]
+pub struct MarketFeesWithdrawn {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct UnclaimedSwept {
+    pub market: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WinningsClaimed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("End time must be in the future")]
+    InvalidEndTime,
+    #[msg("Question is too long")]
+    QuestionTooLong,
+    #[msg("Description is too long")]
+    DescriptionTooLong,
+    #[msg("Invalid liquidity amount")]
+    InvalidLiquidity,
+    #[msg("Market is resolved")]
+    MarketResolved,
+    #[msg("Market has expired")]
+    MarketExpired,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Insufficient shares")]
+    InsufficientShares,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Market is already resolved")]
+    MarketAlreadyResolved,
+    #[msg("Market has not expired yet")]
+    MarketNotExpired,
+    #[msg("Market is not resolved")]
+    MarketNotResolved,
+    #[msg("No winning shares")]
+    NoWinningShares,
+    #[msg("Invalid accounts supplied for claim")]
+    InvalidClaimAccounts,
+    #[msg("Too many markets in one claim")]
+    ClaimBatchTooLarge,
+    #[msg("Price floor must be positive and below the ceiling, which must be below 1.0")]
+    InvalidPriceBounds,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Cost exceeds the maximum allowed")]
+    SlippageExceeded,
+    #[msg("Seed liquidity is locked until the market resolves")]
+    LiquidityLocked,
+    #[msg("Market has no oracle")]
+    OracleNotSet,
+    #[msg("Invalid oracle signature")]
+    InvalidOracleSignature,
+    #[msg("Oracle signature timestamp is stale")]
+    StaleOracleSignature,
+    #[msg("Invalid accounts supplied for positions")]
+    InvalidPositionAccounts,
+    #[msg("Too many positions in one call")]
+    TooManyPositions,
+    #[msg("Evidence URI is too long")]
+    EvidenceUriTooLong,
+    #[msg("Amount exceeds the market's accrued fees")]
+    InsufficientAccruedFees,
+    #[msg("The claim deadline has passed")]
+    ClaimDeadlinePassed,
+    #[msg("The claim window is still open")]
+    ClaimWindowOpen,
+    #[msg("Market already has trades")]
+    MarketHasTrades,
+    #[msg("Position would exceed the market's per-user share cap")]
+    PositionSizeExceeded,
+    #[msg("Initial liquidity can't support the market's price band")]
+    LiquidityBelowViableMinimum,
+    #[msg("Admin note is too long")]
+    AdminNoteTooLong,
+    #[msg("Claim would pay out more than the market holds")]
+    VaultOverdrawn,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved_market(outcome: bool, total_yes_shares: u64, total_no_shares: u64, total_liquidity: u64) -> Market {
+        Market {
+            authority: Pubkey::new_unique(),
+            market_id: 0,
+            question: String::new(),
+            description: String::new(),
+            end_time: 0,
+            created_at: 0,
+            total_yes_shares,
+            total_no_shares,
+            total_liquidity,
+            locked_liquidity: 0,
+            resolved: true,
+            outcome: Some(outcome),
+            min_price: DEFAULT_MIN_PRICE,
+            max_price: DEFAULT_MAX_PRICE,
+            oracle: Pubkey::default(),
+            evidence_uri: String::new(),
+            accrued_fees: 0,
+            claim_deadline: 0,
+            max_shares_per_user: 0,
+            claimed_winnings: 0,
+            dust: 0,
+            dust_remainder: 0,
+            bump: 255,
+            vault_bump: 254,
+        }
+    }
+
+    fn position(yes_shares: u64, no_shares: u64) -> UserPosition {
+        UserPosition {
+            user: Pubkey::new_unique(),
+            market: Pubkey::new_unique(),
+            yes_shares,
+            no_shares,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_claim_payouts_across_three_markets() {
+        let claims = [
+            (resolved_market(true, 100, 100, 1_000), position(50, 0)),
+            (resolved_market(false, 100, 200, 3_000), position(0, 200)),
+            (resolved_market(true, 100, 100, 1_000), position(0, 100)),
+        ];
+
+        let payouts: Vec<u64> = claims
+            .iter()
+            .map(|(market, position)| calculate_winnings_payout(market, position).unwrap())
+            .collect();
+
+        // The third position only holds losing shares and is skipped
+        assert_eq!(payouts, vec![500, 3_000, 0]);
+        assert_eq!(payouts.iter().sum::<u64>(), 3_500);
+    }
+
+    #[test]
+    fn test_many_claimants_never_overdraw_vault() {
+        // 7 winners on 1_000 shares split 1_001 units: every payout truncates
+        let mut market = resolved_market(true, 1_000, 0, 1_001);
+        let mut positions: Vec<UserPosition> = (0..6).map(|_| position(143, 0)).collect();
+        positions.push(position(142, 0));
+
+        let paid: u64 = positions
+            .iter()
+            .map(|pos| record_winnings_claim(&mut market, pos).unwrap())
+            .sum();
+
+        assert_eq!(paid, 1_000);
+        assert_eq!(market.claimed_winnings, 1_000);
+        assert_eq!(market.dust, 1);
+        assert_eq!(market.dust_remainder, 0);
+        assert_eq!(paid + market.dust, market.total_liquidity);
+    }
+
+    #[test]
+    fn test_resolver_receives_bounded_reward() {
+        let mut market = resolved_market(true, 100, 100, 1_000_000);
+
+        let reward = take_resolution_reward(&mut market, state::MAX_RESOLUTION_REWARD_BPS).unwrap();
+        assert_eq!(reward, 10_000);
+        assert_eq!(market.total_liquidity, 990_000);
+
+        // Winners split the rest, and the vault still covers reward and payouts
+        let paid = record_winnings_claim(&mut market, &position(60, 0)).unwrap()
+            + record_winnings_claim(&mut market, &position(40, 0)).unwrap();
+        assert_eq!(paid, 990_000);
+        assert_eq!(paid + reward, 1_000_000);
+
+        let mut unrewarded = resolved_market(true, 100, 100, 1_000_000);
+        assert_eq!(take_resolution_reward(&mut unrewarded, 0).unwrap(), 0);
+        assert_eq!(unrewarded.total_liquidity, 1_000_000);
+    }
+
+    #[test]
+    fn test_claim_payout_requires_resolution() {
+        let mut market = resolved_market(true, 100, 100, 1_000);
+        market.outcome = None;
+
+        assert!(calculate_winnings_payout(&market, &position(10, 0)).is_err());
+    }
+
+    #[test]
+    fn test_share_price_default_bounds() {
+        // 999 of 1000 shares on yes would price at 0.999, clamped to 0.99
+        let price = calculate_share_price(999, 1, 1_000, true, DEFAULT_MIN_PRICE, DEFAULT_MAX_PRICE).unwrap();
+        assert_eq!(price, DEFAULT_MAX_PRICE);
+
+        let price = calculate_share_price(999, 1, 1_000, false, DEFAULT_MIN_PRICE, DEFAULT_MAX_PRICE).unwrap();
+        assert_eq!(price, DEFAULT_MIN_PRICE);
+    }
+
+    #[test]
+    fn test_share_price_custom_bounds() {
+        let price = calculate_share_price(999, 1, 1_000, true, 1_000, 999_000).unwrap();
+        assert_eq!(price, 999_000);
+
+        let price = calculate_share_price(999, 1, 1_000, false, 1_000, 999_000).unwrap();
+        assert_eq!(price, 1_000);
+
+        // Narrow bands clamp prices that would otherwise sit inside the default band
+        let price = calculate_share_price(700, 300, 1_000, true, 400_000, 600_000).unwrap();
+        assert_eq!(price, 600_000);
+    }
+
+    #[test]
+    fn test_position_value_round_trip() {
+        let mut market = resolved_market(true, 750, 250, 1_000);
+        market.resolved = false;
+        market.outcome = None;
+
+        let value = calculate_position_value(&market, &position(100, 40)).unwrap();
+        let decoded = PositionValue::try_from_slice(&value.try_to_vec().unwrap()).unwrap();
+
+        assert_eq!(decoded.yes_price, 750_000);
+        assert_eq!(decoded.no_price, 250_000);
+        assert_eq!(decoded.yes_value, 75);
+        assert_eq!(decoded.no_value, 10);
+        assert_eq!(decoded.total_value, 85);
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn test_max_shares_for_cost_fits_budget() {
+        let price = 333_333;
+        let max_cost = 1_000;
+
+        let amount = calculate_max_shares_for_cost(u64::MAX / PRICE_SCALE, price, max_cost);
+        assert!(calculate_share_cost(amount, price) <= max_cost);
+        assert!(calculate_share_cost(amount + 1, price) > max_cost);
+    }
+
+    #[test]
+    fn test_max_shares_for_cost_bounded_by_requested_amount() {
+        // The whole order fits, so nothing is trimmed
+        assert_eq!(calculate_max_shares_for_cost(10, 500_000, 1_000), 10);
+
+        // Costs round up, so nothing fits a zero budget
+        let amount = calculate_max_shares_for_cost(10, 500_000, 0);
+        assert_eq!(amount, 0);
+        assert_eq!(calculate_share_cost(1, 500_000), 1);
+    }
+
+    #[test]
+    fn test_half_share_purchase() {
+        let half_share = SHARE_SCALE / 2;
+
+        // Half a share at 0.5 costs a quarter of a whole token unit
+        assert_eq!(calculate_share_cost(half_share, 500_000), 250_000);
+        assert_eq!(calculate_share_proceeds(half_share, 500_000), 250_000);
+    }
+
+    #[test]
+    fn test_sub_unit_round_trip_never_profits() {
+        for amount in [1, 3, 7, 999, SHARE_SCALE / 3] {
+            for price in [DEFAULT_MIN_PRICE, 333_333, 500_000, DEFAULT_MAX_PRICE] {
+                let cost = calculate_share_cost(amount, price);
+                let proceeds = calculate_share_proceeds(amount, price);
+
+                assert!(cost > 0);
+                assert!(proceeds <= cost);
+                assert!(cost - proceeds <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_seed_liquidity_locked_until_resolution() {
+        let mut market = resolved_market(true, 100, 100, 1_500);
+        market.resolved = false;
+        market.outcome = None;
+        market.locked_liquidity = 1_000;
+
+        // Only liquidity added by traders can be paid out before resolution
+        assert_eq!(withdrawable_liquidity(&market), 500);
+
+        market.total_liquidity = 1_000;
+        assert_eq!(withdrawable_liquidity(&market), 0);
+    }
+
+    #[test]
+    fn test_seed_liquidity_released_to_winners_on_resolution() {
+        let mut market = resolved_market(true, 100, 100, 1_500);
+        market.locked_liquidity = 1_000;
+
+        assert_eq!(withdrawable_liquidity(&market), 1_500);
+        assert_eq!(calculate_winnings_payout(&market, &position(100, 0)).unwrap(), 1_500);
+    }
+
+    fn ed25519_ix(signer: &Pubkey, message: &[u8]) -> Instruction {
+        // Header, then signature, pubkey and message laid out inline
+        let signature_offset: u16 = 16;
+        let pubkey_offset: u16 = signature_offset + 64;
+        let message_offset: u16 = pubkey_offset + 32;
+
+        let mut data = vec![1u8, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            pubkey_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[7u8; 64]);
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    #[test]
+    fn test_oracle_signature_accepted() {
+        let oracle = Pubkey::new_unique();
+        let message = oracle_resolution_message(42, true, 1_000);
+
+        assert!(verify_ed25519_instruction(&ed25519_ix(&oracle, &message), &oracle, &message).is_ok());
+    }
+
+    #[test]
+    fn test_oracle_signature_wrong_key_rejected() {
+        let oracle = Pubkey::new_unique();
+        let impostor = Pubkey::new_unique();
+        let message = oracle_resolution_message(42, true, 1_000);
+
+        assert!(verify_ed25519_instruction(&ed25519_ix(&impostor, &message), &oracle, &message).is_err());
+    }
+
+    #[test]
+    fn test_oracle_signature_wrong_outcome_rejected() {
+        let oracle = Pubkey::new_unique();
+        let signed = oracle_resolution_message(42, true, 1_000);
+        let claimed = oracle_resolution_message(42, false, 1_000);
+
+        assert!(verify_ed25519_instruction(&ed25519_ix(&oracle, &signed), &oracle, &claimed).is_err());
+    }
+
+    #[test]
+    fn test_oracle_timestamp_staleness() {
+        let end_time = 1_000;
+
+        assert!(check_oracle_timestamp(1_100, 1_200, end_time).is_ok());
+        assert!(check_oracle_timestamp(1_100, 1_100 + MAX_ORACLE_SIGNATURE_AGE + 1, end_time).is_err());
+        assert!(check_oracle_timestamp(999, 1_000, end_time).is_err());
+        assert!(check_oracle_timestamp(1_300, 1_200, end_time).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_three_positions() {
+        let mut market = resolved_market(true, 750, 250, 1_000);
+        market.resolved = false;
+        market.outcome = None;
+
+        let values: Vec<PositionValue> = [position(100, 0), position(0, 40), position(20, 20)]
+            .iter()
+            .map(|position| calculate_position_value(&market, position).unwrap())
+            .collect();
+
+        let exposure = sum_position_values(&values).unwrap();
+        let decoded = AggregateExposure::try_from_slice(&exposure.try_to_vec().unwrap()).unwrap();
+
+        assert_eq!(decoded.total_yes_shares, 120);
+        assert_eq!(decoded.total_no_shares, 60);
+        // 100 * 0.75 + 40 * 0.25 + (20 * 0.75 + 20 * 0.25)
+        assert_eq!(decoded.total_value, 75 + 10 + 20);
+        assert_eq!(decoded.position_count, 3);
+    }
+
+    #[test]
+    fn test_evidence_uri_round_trips() {
+        let mut market = resolved_market(true, 100, 100, 1_000);
+        market.resolved = false;
+        market.outcome = None;
+        let market_key = Pubkey::new_unique();
+        let uri = "https://example.com/resolution/42".to_string();
+
+        let event = record_resolution(&mut market, market_key, false, uri.clone(), 1_234).unwrap();
+
+        assert_eq!(market.evidence_uri, uri);
+        assert_eq!(market.outcome, Some(false));
+        assert_eq!(event.evidence_uri, uri);
+        assert_eq!(event.market, market_key);
+        assert_eq!(event.resolved_at, 1_234);
+    }
+
+    #[test]
+    fn test_evidence_uri_too_long_rejected() {
+        let mut market = resolved_market(true, 100, 100, 1_000);
+        market.resolved = false;
+
+        let uri = "a".repeat(MAX_EVIDENCE_URI_LEN + 1);
+        assert!(record_resolution(&mut market, Pubkey::new_unique(), true, uri, 0).is_err());
+        assert!(!market.resolved);
+    }
+
+    fn open_market(created_at: i64, end_time: i64) -> Market {
+        let mut market = resolved_market(true, 0, 0, 1_000);
+        market.resolved = false;
+        market.outcome = None;
+        market.created_at = created_at;
+        market.end_time = end_time;
+        market.oracle = Pubkey::new_unique();
+        market
+    }
+
+    fn duration_config() -> Config {
+        Config {
+            admin: Pubkey::new_unique(),
+            max_markets_per_authority: 0,
+            max_creator_fee_bps: DEFAULT_MAX_CREATOR_FEE_BPS,
+            min_market_duration: DEFAULT_MIN_MARKET_DURATION,
+            max_market_duration: DEFAULT_MAX_MARKET_DURATION,
+            referral_levels: 1,
+            unique_token_names: false,
+            high_impact_threshold_bps: DEFAULT_HIGH_IMPACT_THRESHOLD_BPS,
+            min_initial_liquidity: DEFAULT_MIN_INITIAL_LIQUIDITY,
+            max_allowed_slippage_bps: 0,
+            snipe_block_delay: 0,
+            snipe_max_buy: DEFAULT_SNIPE_MAX_BUY,
+            min_holders_for_graduation: 0,
+            fee_free_threshold_lamports: 0,
+            treasury: Pubkey::new_unique(),
+            max_open_markets: 0,
+            open_markets: 0,
+            partner_fee_mint: Pubkey::default(),
+            partner_fee_rate: 0,
+            partner_fee_discount_bps: 0,
+            hold_rebate_enabled: false,
+            quote_mint_allowlist_enabled: false,
+            allowed_quote_mints: [Pubkey::default(); MAX_ALLOWED_QUOTE_MINTS],
+            resolution_reward_bps: 0,
+            max_creator_total_supply: 0,
+            impact_fee_coefficient: 0,
+            internal_pool_graduation: false,
+            pool_swap_fee_bps: DEFAULT_POOL_SWAP_FEE_BPS,
+            launch_fee: 0,
+            min_graduation_sol: 0,
+            graduation_reward: 0,
+            reward_vesting_period: DEFAULT_REWARD_VESTING_PERIOD,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_admin_action_event_round_trip() {
+        let admin = Pubkey::new_unique();
+        let event = admin_action_event(admin, 3, "froze mint after spam report".to_string(), 1_700_000_000).unwrap();
+
+        let decoded = AdminActionEvent::try_from_slice(&event.try_to_vec().unwrap()).unwrap();
+        assert_eq!(decoded.admin, admin);
+        assert_eq!(decoded.action_code, 3);
+        assert_eq!(decoded.note, "froze mint after spam report");
+        assert_eq!(decoded.timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_admin_note_length_limit() {
+        let admin = Pubkey::new_unique();
+
+        assert!(admin_action_event(admin, 0, "x".repeat(MAX_ADMIN_NOTE_LEN), 0).is_ok());
+        assert!(admin_action_event(admin, 0, "x".repeat(MAX_ADMIN_NOTE_LEN + 1), 0).is_err());
+    }
+
+    #[test]
+    fn test_extend_end_time_forward() {
+        let mut market = open_market(0, 10_000);
+        let authority = market.authority;
+        let oracle = market.oracle;
+
+        assert_eq!(extend_market_end_time(&mut market, &duration_config(), &authority, 20_000).unwrap(), 10_000);
+        assert_eq!(extend_market_end_time(&mut market, &duration_config(), &oracle, 30_000).unwrap(), 20_000);
+        assert_eq!(market.end_time, 30_000);
+    }
+
+    #[test]
+    fn test_extend_end_time_backward_rejected() {
+        let mut market = open_market(0, 10_000);
+        let authority = market.authority;
+
+        assert!(extend_market_end_time(&mut market, &duration_config(), &authority, 9_999).is_err());
+        assert!(extend_market_end_time(&mut market, &duration_config(), &authority, 10_000).is_err());
+        assert_eq!(market.end_time, 10_000);
+    }
+
+    #[test]
+    fn test_extend_end_time_after_resolution_rejected() {
+        let mut market = open_market(0, 10_000);
+        market.resolved = true;
+        market.outcome = Some(true);
+        let authority = market.authority;
+
+        assert!(extend_market_end_time(&mut market, &duration_config(), &authority, 20_000).is_err());
+    }
+
+    #[test]
+    fn test_extend_end_time_bounded_by_max_duration() {
+        let mut market = open_market(0, 10_000);
+        let authority = market.authority;
+
+        assert!(extend_market_end_time(&mut market, &duration_config(), &authority, DEFAULT_MAX_MARKET_DURATION + 1).is_err());
+        assert!(extend_market_end_time(&mut market, &duration_config(), &authority, DEFAULT_MAX_MARKET_DURATION).is_ok());
+    }
+
+    #[test]
+    fn test_extend_end_time_requires_authority_or_oracle() {
+        let mut market = open_market(0, 10_000);
+
+        assert!(extend_market_end_time(&mut market, &duration_config(), &Pubkey::new_unique(), 20_000).is_err());
+    }
+
+    #[test]
+    fn test_oracle_rotation() {
+        let mut market = open_market(0, 10_000);
+        let authority = market.authority;
+        let first_oracle = market.oracle;
+        let (second_oracle, third_oracle) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(rotate_market_oracle(&mut market, &authority, second_oracle).unwrap(), first_oracle);
+        // The old oracle lost its say; the new one can hand over in turn
+        assert!(rotate_market_oracle(&mut market, &first_oracle, third_oracle).is_err());
+        assert_eq!(rotate_market_oracle(&mut market, &second_oracle, third_oracle).unwrap(), second_oracle);
+        assert_eq!(market.oracle, third_oracle);
+
+        assert!(rotate_market_oracle(&mut market, &Pubkey::new_unique(), first_oracle).is_err());
+    }
+
+    #[test]
+    fn test_oracle_rotation_after_resolution_rejected() {
+        let mut market = open_market(0, 10_000);
+        market.resolved = true;
+        market.outcome = Some(true);
+        let authority = market.authority;
+        let oracle = market.oracle;
+
+        let already_resolved: Error = ErrorCode::MarketAlreadyResolved.into();
+        assert_eq!(rotate_market_oracle(&mut market, &authority, Pubkey::new_unique()).unwrap_err(), already_resolved);
+        assert_eq!(market.oracle, oracle);
+    }
+
+    #[test]
+    fn test_zero_winning_shares_refunds_other_side() {
+        let market = resolved_market(true, 0, 200, 1_000);
+
+        assert_eq!(calculate_winnings_payout(&market, &position(0, 50)).unwrap(), 250);
+        assert_eq!(calculate_winnings_payout(&market, &position(0, 150)).unwrap(), 750);
+        assert_eq!(calculate_winnings_payout(&market, &position(0, 0)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_zero_shares_on_both_sides_pays_nothing() {
+        let market = resolved_market(false, 0, 0, 1_000);
+
+        assert_eq!(calculate_winnings_payout(&market, &position(0, 0)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fees_accrue_across_claims() {
+        let mut market = resolved_market(true, 100, 100, 1_000);
+
+        let payouts: Vec<u64> = [position(50, 0), position(30, 0), position(20, 0)]
+            .iter()
+            .map(|pos| {
+                let gross = calculate_winnings_payout(&market, pos).unwrap();
+                take_winnings_fee(&mut market, gross).unwrap()
+            })
+            .collect();
+
+        assert_eq!(payouts, vec![495, 297, 198]);
+        assert_eq!(market.accrued_fees, 10);
+        assert_eq!(payouts.iter().sum::<u64>() + market.accrued_fees, 1_000);
+    }
+
+    #[test]
+    fn test_withdraw_accrued_fees() {
+        let mut market = resolved_market(true, 100, 100, 1_000);
+        take_winnings_fee(&mut market, 1_000).unwrap();
+
+        assert!(withdraw_accrued_fees(&mut market, 11).is_err());
+        assert!(withdraw_accrued_fees(&mut market, 10).is_ok());
+        assert_eq!(market.accrued_fees, 0);
+        assert!(withdraw_accrued_fees(&mut market, 1).is_err());
+    }
+
+    #[test]
+    fn test_fees_not_withdrawable_before_resolution() {
+        let mut market = resolved_market(true, 100, 100, 1_000);
+        market.accrued_fees = 10;
+        market.resolved = false;
+
+        assert!(withdraw_accrued_fees(&mut market, 10).is_err());
+    }
+
+    fn market_resolved_at(resolved_at: i64) -> Market {
+        let mut market = resolved_market(true, 100, 100, 1_000);
+        market.resolved = false;
+        record_resolution(&mut market, Pubkey::new_unique(), true, String::new(), resolved_at).unwrap();
+        market
+    }
+
+    #[test]
+    fn test_claim_before_deadline_unaffected() {
+        let market = market_resolved_at(1_000);
+
+        assert_eq!(market.claim_deadline, 1_000 + CLAIM_WINDOW);
+        assert!(check_claim_window(&market, 1_000).is_ok());
+        assert!(check_claim_window(&market, market.claim_deadline).is_ok());
+        assert_eq!(calculate_winnings_payout(&market, &position(50, 0)).unwrap(), 500);
+        assert!(check_sweep_allowed(&market, market.claim_deadline).is_err());
+    }
+
+    #[test]
+    fn test_sweep_after_deadline() {
+        let market = market_resolved_at(1_000);
+        let after = market.claim_deadline + 1;
+
+        assert!(check_claim_window(&market, after).is_err());
+        assert!(check_sweep_allowed(&market, after).is_ok());
+    }
+
+    #[test]
+    fn test_sweep_requires_resolution() {
+        let mut market = resolved_market(true, 100, 100, 1_000);
+        market.resolved = false;
+
+        assert!(check_sweep_allowed(&market, i64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_resolving_untraded_market_refunds_seed_liquidity() {
+        let mut market = open_market(0, 10_000);
+        market.locked_liquidity = 1_000;
+        let market_key = Pubkey::new_unique();
+
+        let event = record_void(&mut market, market_key, 10_000).unwrap();
+
+        assert_eq!(event.market, market_key);
+        assert_eq!(event.authority, market.authority);
+        assert_eq!(event.refund, 1_000);
+        assert!(market.resolved);
+        assert_eq!(market.outcome, None);
+        assert_eq!(market.locked_liquidity, 0);
+        assert_eq!(market.total_liquidity, 0);
+
+        // A voided market has nothing left to claim
+        assert!(check_claim_window(&market, 10_001).is_err());
+    }
+
+    #[test]
+    fn test_min_viable_liquidity_for_default_band() {
+        // A [0.01, 0.99] band prices in 1% ticks, so 100 base units is the floor
+        assert_eq!(min_viable_liquidity(DEFAULT_MIN_PRICE, DEFAULT_MAX_PRICE), 100);
+
+        assert!(check_viable_liquidity(100, DEFAULT_MIN_PRICE, DEFAULT_MAX_PRICE).is_ok());
+        assert!(check_viable_liquidity(99, DEFAULT_MIN_PRICE, DEFAULT_MAX_PRICE).is_err());
+    }
+
+    #[test]
+    fn test_min_viable_liquidity_follows_tighter_end() {
+        // A 0.999 ceiling leaves a 0.1% tick on the other side
+        assert_eq!(min_viable_liquidity(DEFAULT_MIN_PRICE, 999_000), 1_000);
+        // Ticks that don't divide the scale round the minimum up
+        assert_eq!(min_viable_liquidity(300_000, 700_000), 4);
+
+        assert!(check_viable_liquidity(999, DEFAULT_MIN_PRICE, 999_000).is_err());
+        assert!(check_viable_liquidity(1_000, DEFAULT_MIN_PRICE, 999_000).is_ok());
+    }
+
+    #[test]
+    fn test_position_size_cap() {
+        let mut market = open_market(0, 10_000);
+        market.max_shares_per_user = 100;
+        let user_position = position(40, 20);
+
+        assert!(check_position_size(&market, &user_position, 40).is_ok());
+        assert!(check_position_size(&market, &user_position, 41).is_err());
+
+        market.max_shares_per_user = 0;
+        assert!(check_position_size(&market, &user_position, u64::MAX / 2).is_ok());
+    }
+
+    #[test]
+    fn test_cancel_before_any_trade() {
+        let market = open_market(0, 10_000);
+
+        assert!(check_cancellable(&market).is_ok());
+    }
+
+    #[test]
+    fn test_cancel_rejected_after_trade_or_resolution() {
+        let mut market = open_market(0, 10_000);
+        market.total_no_shares = 1;
+        assert!(check_cancellable(&market).is_err());
+
+        let mut market = open_market(0, 10_000);
+        market.resolved = true;
+        assert!(check_cancellable(&market).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+```rust
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use crate::utils::math::MathUtils;
+
+/// Default swap fee charged by program-owned pools (0.3%)
+pub const DEFAULT_POOL_SWAP_FEE_BPS: u16 = 30;
+
+/// Highest swap fee a pool may charge (10%)
+pub const MAX_POOL_SWAP_FEE_BPS: u16 = 1_000;
+
+/// Constant-product pool a graduated curve migrates into when it skips Raydium.
+/// Tokens sit in the pool's token vault; SOL is held as lamports on this account.
+#[account]
+pub struct AmmPool {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub token_reserves: u64,
+    pub sol_reserves: u64,
+    pub swap_fee_bps: u16, // LP fee, left in the reserves so k grows
+    pub created_at: i64,
+    pub bump: u8,
+    pub token_vault_bump: u8,
+}
+
+impl AmmPool {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // bonding_curve
+        8 + // token_reserves
+        8 + // sol_reserves
+        2 + // swap_fee_bps
+        8 + // created_at
+        1 + // bump
+        1; // token_vault_bump
+
+    /// Seed the pool with the reserves migrated from a graduated curve
+    pub fn initialize(
+        &mut self,
+        token_reserves: u64,
+        sol_reserves: u64,
+        swap_fee_bps: u16,
+        now: i64,
+    ) -> Result<()> {
+        require!(token_reserves > 0 && sol_reserves > 0, PumpError::InsufficientPoolLiquidity);
+
+        self.token_reserves = token_reserves;
+        self.sol_reserves = sol_reserves;
+        self.swap_fee_bps = swap_fee_bps.min(MAX_POOL_SWAP_FEE_BPS);
+        self.created_at = now;
+
+        Ok(())
+    }
+
+    /// Tokens paid out for `sol_in`, after the swap fee
+    pub fn quote_buy(&self, sol_in: u64) -> Result<u64> {
+        let fee = MathUtils::calculate_fee(sol_in, self.swap_fee_bps as u64)?;
+        swap_out(self.sol_reserves, self.token_reserves, sol_in - fee)
+    }
+
+    /// Lamports paid out for `tokens_in`, after the swap fee
+    pub fn quote_sell(&self, tokens_in: u64) -> Result<u64> {
+        let fee = MathUtils::calculate_fee(tokens_in, self.swap_fee_bps as u64)?;
+        swap_out(self.token_reserves, self.sol_reserves, tokens_in - fee)
+    }
+
+    /// Swap SOL for tokens, returning the tokens paid out. The whole `sol_in`, fee
+    /// included, joins the reserves.
+    pub fn buy(&mut self, sol_in: u64, min_tokens_out: u64) -> Result<u64> {
+        let tokens_out = self.quote_buy(sol_in)?;
+        require!(tokens_out > 0, PumpError::InsufficientTokenAmount);
+        require!(tokens_out >= min_tokens_out, PumpError::PoolSlippageExceeded);
+
+        let k_before = self.k();
+        self.sol_reserves = self.sol_reserves
+            .checked_add(sol_in)
+            .ok_or(PumpError::MathOverflow)?;
+        self.token_reserves -= tokens_out;
+        require!(self.k() >= k_before, PumpError::InvalidCalculation);
+
+        Ok(tokens_out)
+    }
+
+    /// Swap tokens for SOL, returning the lamports paid out
+    pub fn sell(&mut self, tokens_in: u64, min_sol_out: u64) -> Result<u64> {
+        let sol_out = self.quote_sell(tokens_in)?;
+        require!(sol_out > 0, PumpError::InvalidCalculation);
+        require!(sol_out >= min_sol_out, PumpError::PoolSlippageExceeded);
+
+        let k_before = self.k();
+        self.token_reserves = self.token_reserves
+            .checked_add(tokens_in)
+            .ok_or(PumpError::MathOverflow)?;
+        self.sol_reserves -= sol_out;
+        require!(self.k() >= k_before, PumpError::InvalidCalculation);
+
+        Ok(sol_out)
+    }
+
+    fn k(&self) -> u128 {
+        self.token_reserves as u128 * self.sol_reserves as u128
+    }
+}
+
+/// Constant-product output for `amount_in` added to `reserve_in`. Always strictly less
+/// than `reserve_out`, so a swap can't drain the pool.
+fn swap_out(reserve_in: u64, reserve_out: u64, amount_in: u64) -> Result<u64> {
+    let numerator = (reserve_out as u128)
+        .checked_mul(amount_in as u128)
+        .ok_or(PumpError::MathOverflow)?;
+    let denominator = (reserve_in as u128)
+        .checked_add(amount_in as u128)
+        .ok_or(PumpError::MathOverflow)?;
+    require!(denominator > 0, PumpError::InsufficientPoolLiquidity);
+
+    Ok((numerator / denominator) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pool seeded the way an 85 SOL curve with 206.9M tokens left graduates
+    fn pool() -> AmmPool {
+        pool_with_fee(DEFAULT_POOL_SWAP_FEE_BPS)
+    }
+
+    fn pool_with_fee(swap_fee_bps: u16) -> AmmPool {
+        let mut pool = AmmPool {
+            mint: Pubkey::new_unique(),
+            bonding_curve: Pubkey::new_unique(),
+            token_reserves: 0,
+            sol_reserves: 0,
+            swap_fee_bps: 0,
+            created_at: 0,
+            bump: 255,
+            token_vault_bump: 255,
+        };
+        pool.initialize(206_900_000_000_000, 85_000_000_000, swap_fee_bps, 1_000).unwrap();
+        pool
+    }
+
+    #[test]
+    fn test_graduated_reserves_seed_pool() {
+        let pool = pool();
+        assert_eq!(pool.token_reserves, 206_900_000_000_000);
+        assert_eq!(pool.sol_reserves, 85_000_000_000);
+        assert_eq!(pool.swap_fee_bps, DEFAULT_POOL_SWAP_FEE_BPS);
+
+        let mut empty = pool.clone();
+        assert!(empty.initialize(0, 85_000_000_000, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_swap_against_pool() {
+        let mut pool = pool();
+        let k_before = pool.k();
+
+        let quoted = pool.quote_buy(1_000_000_000).unwrap();
+        let tokens_out = pool.buy(1_000_000_000, quoted).unwrap();
+        assert_eq!(tokens_out, quoted);
+        assert_eq!(pool.sol_reserves, 86_000_000_000);
+        assert_eq!(pool.token_reserves, 206_900_000_000_000 - tokens_out);
+        assert!(pool.k() > k_before);
+
+        // Selling straight back returns less than was paid, the difference being fees and rounding
+        let sol_out = pool.sell(tokens_out, 0).unwrap();
+        assert!(sol_out < 1_000_000_000);
+        assert!(sol_out > 990_000_000);
+    }
+
+    #[test]
+    fn test_swap_respects_min_out() {
+        let mut pool = pool();
+        let quoted = pool.quote_buy(1_000_000_000).unwrap();
+
+        let slippage: Error = PumpError::PoolSlippageExceeded.into();
+        assert_eq!(pool.buy(1_000_000_000, quoted + 1).unwrap_err(), slippage);
+        assert_eq!(pool.sol_reserves, 85_000_000_000);
+    }
+
+    #[test]
+    fn test_swap_cannot_drain_pool() {
+        let mut pool = pool();
+        let tokens_out = pool.buy(100_000_000_000_000_000, 0).unwrap();
+        assert!(tokens_out < 206_900_000_000_000);
+        assert!(pool.token_reserves > 0);
+    }
+
+    #[test]
+    fn test_swap_fee_accrues_to_reserves() {
+        let mut feeless = pool_with_fee(0);
+        let mut charged = pool_with_fee(100);
+
+        let feeless_out = feeless.buy(1_000_000_000, 0).unwrap();
+        let charged_out = charged.buy(1_000_000_000, 0).unwrap();
+
+        // Both pools keep the whole deposit, but the fee buys no tokens
+        assert_eq!(charged.sol_reserves, feeless.sol_reserves);
+        assert!(charged_out < feeless_out);
+        assert_eq!(charged.token_reserves - feeless.token_reserves, feeless_out - charged_out);
+        assert!(charged.k() > feeless.k());
+    }
+
+    #[test]
+    fn test_swap_fee_grows_constant_product() {
+        let mut pool = pool_with_fee(100);
+        let mut k = pool.k();
+
+        for _ in 0..5 {
+            let tokens_out = pool.buy(2_000_000_000, 0).unwrap();
+            assert!(pool.k() > k);
+            k = pool.k();
+
+            pool.sell(tokens_out / 2, 0).unwrap();
+            assert!(pool.k() > k);
+            k = pool.k();
+        }
+    }
+
+    #[test]
+    fn test_swap_fee_capped() {
+        assert_eq!(pool_with_fee(u16::MAX).swap_fee_bps, MAX_POOL_SWAP_FEE_BPS);
+    }
+}
+```
\ No newline at end of file
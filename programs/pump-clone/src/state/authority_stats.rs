@@ -0,0 +1,137 @@
+```rust
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+
+/// Running totals for everything a single authority has created.
+#[account]
+pub struct AuthorityStats {
+    pub authority: Pubkey,
+    pub markets_created: u64,
+    pub bump: u8,
+    pub total_supply_launched: u64,
+}
+
+impl AuthorityStats {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // markets_created
+        1 + // bump
+        8; // total_supply_launched
+
+    /// Count a new market against the authority, enforcing `max_markets` unless it is 0
+    pub fn record_market(&mut self, max_markets: u64) -> Result<()> {
+        if max_markets > 0 {
+            require!(self.markets_created < max_markets, PumpError::MarketLimitReached);
+        }
+
+        self.markets_created = self
+            .markets_created
+            .checked_add(1)
+            .ok_or(PumpError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Hand back a cancelled market's slot, so replacing a mistaken market doesn't cost
+    /// the authority part of its quota
+    pub fn release_market(&mut self) {
+        self.markets_created = self.markets_created.saturating_sub(1);
+    }
+
+    /// Add a launched token's supply to the creator's running total, enforcing
+    /// `max_total_supply` unless it is 0
+    pub fn record_launch(&mut self, token_total_supply: u64, max_total_supply: u64) -> Result<()> {
+        let total_supply_launched = self
+            .total_supply_launched
+            .checked_add(token_total_supply)
+            .ok_or(PumpError::MathOverflow)?;
+
+        if max_total_supply > 0 {
+            require!(
+                total_supply_launched <= max_total_supply,
+                PumpError::CreatorSupplyLimitReached
+            );
+        }
+
+        self.total_supply_launched = total_supply_launched;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> AuthorityStats {
+        AuthorityStats {
+            authority: Pubkey::new_unique(),
+            markets_created: 0,
+            bump: 255,
+            total_supply_launched: 0,
+        }
+    }
+
+    #[test]
+    fn test_market_cap_enforced() {
+        let mut stats = stats();
+        for _ in 0..3 {
+            assert!(stats.record_market(3).is_ok());
+        }
+
+        assert!(stats.record_market(3).is_err());
+        assert_eq!(stats.markets_created, 3);
+    }
+
+    #[test]
+    fn test_released_market_frees_quota() {
+        let mut stats = stats();
+        assert!(stats.record_market(1).is_ok());
+        assert!(stats.record_market(1).is_err());
+
+        stats.release_market();
+        assert_eq!(stats.markets_created, 0);
+        assert!(stats.record_market(1).is_ok());
+    }
+
+    #[test]
+    fn test_zero_cap_is_unlimited() {
+        let mut stats = stats();
+        for _ in 0..100 {
+            assert!(stats.record_market(0).is_ok());
+        }
+        assert_eq!(stats.markets_created, 100);
+    }
+
+    #[test]
+    fn test_launch_supply_cap_crossed() {
+        let mut stats = stats();
+        assert!(stats.record_launch(400, 1_000).is_ok());
+        assert!(stats.record_launch(600, 1_000).is_ok());
+        assert_eq!(stats.total_supply_launched, 1_000);
+
+        let limit_reached: Error = PumpError::CreatorSupplyLimitReached.into();
+        assert_eq!(stats.record_launch(1, 1_000).unwrap_err(), limit_reached);
+        assert_eq!(stats.total_supply_launched, 1_000);
+    }
+
+    #[test]
+    fn test_launch_supply_cap_crossed_by_one_launch() {
+        let mut stats = stats();
+        assert!(stats.record_launch(999, 1_000).is_ok());
+        assert!(stats.record_launch(2, 1_000).is_err());
+        assert_eq!(stats.total_supply_launched, 999);
+    }
+
+    #[test]
+    fn test_zero_supply_cap_is_unlimited() {
+        let mut stats = stats();
+        for _ in 0..10 {
+            assert!(stats.record_launch(1_000_000_000_000_000, 0).is_ok());
+        }
+        assert_eq!(stats.total_supply_launched, 10_000_000_000_000_000);
+        assert!(stats.record_launch(u64::MAX, 0).is_err());
+    }
+}
+```
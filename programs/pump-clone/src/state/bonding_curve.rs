@@ -1,6 +1,133 @@
 ```rust
 use anchor_lang::prelude::*;
 
+/// Real SOL reserves at which a curve completes, unless the creator picks another target
+pub const DEFAULT_GRADUATION_THRESHOLD: u64 = 85_000_000_000; // 85 SOL in lamports
+
+/// Flat trade fee used when a creator doesn't configure a fee curve (1%)
+pub const DEFAULT_TRADE_FEE_BPS: u16 = 100;
+
+/// Highest trade fee a fee curve may reach (10%)
+pub const MAX_TRADE_FEE_BPS: u16 = 1_000;
+
+/// Scale applied to `OrderFilled::new_price` (lamports per token, times 1e9)
+pub const SPOT_PRICE_SCALE: u128 = 1_000_000_000;
+
+/// Returned by `graduation_eta` when nothing has traded yet, so no rate is known
+pub const GRADUATION_ETA_UNKNOWN: u64 = u64::MAX;
+
+/// Result of a buy or sell, returned to CPI callers via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct OrderFilled {
+    pub token_amount: u64,
+    /// SOL paid by the buyer, or received by the seller, in lamports
+    pub sol_amount: u64,
+    /// Creator and trade fees charged on the fill, in lamports
+    pub fee: u64,
+    /// Spot price after the fill, scaled by `SPOT_PRICE_SCALE`
+    pub new_price: u64,
+}
+
+/// Layout version of `CurveState`, bumped whenever its fields change
+pub const CURVE_STATE_VERSION: u8 = 1;
+
+/// Curve snapshot returned by `get_curve_state`, so clients don't decode the account layout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct CurveState {
+    pub version: u8,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub complete: bool,
+    pub progress_percentage: u8,
+    /// In lamports
+    pub market_cap: u64,
+    pub holder_count: u64,
+}
+
+/// Result of `verify_reserve_ratio`, returned via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ReserveRatioCheck {
+    /// Distance of the current sol/token reserve ratio from the launch ratio, in bps of the launch ratio
+    pub deviation_bps: u64,
+    /// Whether `deviation_bps` exceeds the threshold the caller passed
+    pub flagged: bool,
+}
+
+/// Which criterion completes the curve and lets it graduate; `graduation_threshold`
+/// is in lamports for `SolThreshold` and in base token units for `SupplySold`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraduationMode {
+    #[default]
+    SolThreshold,
+    SupplySold,
+}
+
+/// Aggregate over a creator's curves, returned by `creator_summary` via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct CreatorSummary {
+    pub total_sol_reserves: u64,
+    /// Creator fees paid out across the curves, in lamports
+    pub total_fees_accrued: u64,
+    pub graduated_count: u32,
+}
+
+impl CreatorSummary {
+    /// Fold one curve into the summary, rejecting curves `creator` doesn't own
+    pub fn add(&mut self, creator: &Pubkey, curve: &BondingCurve) -> Result<()> {
+        require_keys_eq!(curve.creator, *creator, ErrorCode::NotCurveCreator);
+
+        self.total_sol_reserves = self
+            .total_sol_reserves
+            .checked_add(curve.real_sol_reserves)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        self.total_fees_accrued = self
+            .total_fees_accrued
+            .checked_add(curve.creator_fees_accrued)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        if curve.complete {
+            self.graduated_count = self
+                .graduated_count
+                .checked_add(1)
+                .ok_or(ErrorCode::ArithmeticError)?;
+        }
+        Ok(())
+    }
+}
+
+/// Trade fee that moves linearly from `start_bps` at launch to `end_bps` at graduation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct FeeCurve {
+    pub start_bps: u16,
+    pub end_bps: u16,
+}
+
+impl Default for FeeCurve {
+    fn default() -> Self {
+        Self {
+            start_bps: DEFAULT_TRADE_FEE_BPS,
+            end_bps: DEFAULT_TRADE_FEE_BPS,
+        }
+    }
+}
+
+/// Deepens the curve by multiplying the virtual token reserves by `multiplier` on every
+/// `interval`-th trade. The SOL side is left alone: each of its lamports is backed by real
+/// reserves, so scaling it would promise sellers SOL the curve doesn't hold. The spot price
+/// drops by `multiplier` at each step.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct DepthScaling {
+    pub interval: u64, // 0 = off
+    pub multiplier: u8,
+}
+
+impl DepthScaling {
+    pub fn is_valid(&self) -> bool {
+        self.interval == 0 || self.multiplier >= 2
+    }
+}
+
 #[account]
 pub struct BondingCurve {
     pub creator: Pubkey,
@@ -11,7 +138,41 @@ pub struct BondingCurve {
     pub real_sol_reserves: u64,
     pub token_total_supply: u64,
     pub complete: bool,
+    pub self_trade_guard: bool,
     pub bump: u8,
+    pub sol_vault_bump: u8,
+    pub curve_vault_bump: u8,
+    pub creator_fee_bps: u16,
+    pub creator_fee_exempt: bool,
+    pub graduation_threshold: u64,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub fee_curve: FeeCurve,
+    pub sol_vault_initialized: bool,
+    pub buys_enabled: bool,
+    pub sells_enabled: bool,
+    pub completion_timestamp: i64, // 0 until the curve completes
+    pub trade_count: u64,
+    pub depth_scaling: DepthScaling,
+    pub creator_fees_accrued: u64,
+    pub floor_price: u64, // scaled by SPOT_PRICE_SCALE, 0 = no floor
+    pub floor_reserve: u64,
+    pub created_at: i64,
+    pub total_sol_volume: u64, // buys and sells through the curve, in lamports
+    pub launch_slot: u64,
+    pub trading_start: i64,
+    pub trading_end: i64, // 0 = open-ended
+    pub burn_remainder: bool,
+    pub holder_count: u64, // wallets holding tokens bought through the curve
+    pub creator_sell_locked_until_graduation: bool,
+    pub graduation_mode: GraduationMode,
+    pub floor_vault_bump: u8,
+    pub graduating: bool, // set while graduate_token's CPIs run
+    pub graduated: bool,
+    pub graduation_timestamp: i64,
+    pub amm_pool: Pubkey, // default until graduated to Raydium
+    pub sequence: u64, // last sequence number stamped on this curve's trade events
 }
 
 impl BondingCurve {
@@ -24,7 +185,41 @@ impl BondingCurve {
         8 + // real_sol_reserves
         8 + // token_total_supply
         1 + // complete
-        1; // bump
+        1 + // self_trade_guard
+        1 + // bump
+        1 + // sol_vault_bump
+        1 + // curve_vault_bump
+        2 + // creator_fee_bps
+        1 + // creator_fee_exempt
+        8 + // graduation_threshold
+        8 + // initial_virtual_token_reserves
+        8 + // initial_virtual_sol_reserves
+        8 + // initial_real_token_reserves
+        2 + 2 + // fee_curve
+        1 + // sol_vault_initialized
+        1 + // buys_enabled
+        1 + // sells_enabled
+        8 + // completion_timestamp
+        8 + // trade_count
+        8 + 1 + // depth_scaling
+        8 + // creator_fees_accrued
+        8 + // floor_price
+        8 + // floor_reserve
+        8 + // created_at
+        8 + // total_sol_volume
+        8 + // launch_slot
+        8 + // trading_start
+        8 + // trading_end
+        1 + // burn_remainder
+        8 + // holder_count
+        1 + // creator_sell_locked_until_graduation
+        1 + // graduation_mode
+        1 + // floor_vault_bump
+        1 + // graduating
+        1 + // graduated
+        8 + // graduation_timestamp
+        32 + // amm_pool
+        8; // sequence
 
     pub fn calculate_buy_price(&self, token_amount: u64) -> Result<u64> {
         if self.complete {
@@ -120,10 +315,36 @@ impl BondingCurve {
         Ok(())
     }
 
-    pub fn check_completion(&mut self) -> Result<bool> {
-        const GRADUATION_THRESHOLD: u64 = 85_000_000_000; // 85 SOL in lamports
+    /// Tokens bought out of the curve's real reserves and not yet sold back
+    pub fn tokens_sold(&self) -> u64 {
+        self.initial_real_token_reserves
+            .saturating_sub(self.real_token_reserves)
+    }
+
+    /// Progress toward `graduation_threshold`, in the unit the graduation mode counts:
+    /// lamports of real SOL reserves, or base units of tokens sold
+    pub fn graduation_progress(&self) -> u64 {
+        match self.graduation_mode {
+            GraduationMode::SolThreshold => self.real_sol_reserves,
+            GraduationMode::SupplySold => self.tokens_sold(),
+        }
+    }
+
+    /// What's left before the curve graduates, in the same unit as `graduation_progress`
+    pub fn remaining_to_graduation(&self) -> u64 {
+        self.graduation_threshold.saturating_sub(self.graduation_progress())
+    }
 
-        if self.real_sol_reserves >= GRADUATION_THRESHOLD {
+    /// Whether the curve has met its graduation criterion
+    pub fn graduation_reached(&self) -> bool {
+        self.graduation_progress() >= self.graduation_threshold
+    }
+
+    pub fn check_completion(&mut self, now: i64) -> Result<bool> {
+        if self.graduation_reached() {
+            if !self.complete {
+                self.completion_timestamp = now;
+            }
             self.complete = true;
             return Ok(true);
         }
@@ -152,20 +373,485 @@ impl BondingCurve {
     }
 
     pub fn get_progress_percentage(&self) -> Result<u8> {
-        const GRADUATION_THRESHOLD: u64 = 85_000_000_000;
-
-        if self.real_sol_reserves >= GRADUATION_THRESHOLD {
+        if self.graduation_reached() {
             return Ok(100);
         }
 
-        let progress = (self.real_sol_reserves as u128)
+        let progress = (self.graduation_progress() as u128)
             .checked_mul(100)
             .ok_or(ErrorCode::ArithmeticError)?
-            .checked_div(GRADUATION_THRESHOLD as u128)
+            .checked_div(self.graduation_threshold as u128)
             .ok_or(ErrorCode::ArithmeticError)?;
 
         Ok(progress as u8)
     }
+
+    /// Count a trade, deepening the curve when the count reaches the next scaling step.
+    /// Returns whether the reserves were rescaled.
+    pub fn count_trade(&mut self) -> Result<bool> {
+        self.trade_count = self
+            .trade_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticError)?;
+
+        let DepthScaling { interval, multiplier } = self.depth_scaling;
+        if interval == 0 || self.trade_count % interval != 0 {
+            return Ok(false);
+        }
+
+        // Stop deepening once the reserves can't grow further rather than failing trades
+        match self.virtual_token_reserves.checked_mul(multiplier as u64) {
+            Some(virtual_token_reserves) => {
+                self.virtual_token_reserves = virtual_token_reserves;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Add a trade's SOL to the curve's lifetime volume
+    pub fn record_volume(&mut self, sol_amount: u64) -> Result<()> {
+        self.total_sol_volume = self
+            .total_sol_volume
+            .checked_add(sol_amount)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        Ok(())
+    }
+
+    /// Estimated seconds until the curve graduates, assuming it keeps filling at its average
+    /// rate since launch: SOL volume for `SolThreshold`, tokens sold for `SupplySold`.
+    /// `GRADUATION_ETA_UNKNOWN` if nothing has traded.
+    pub fn graduation_eta(&self, now: i64) -> Result<u64> {
+        if self.graduation_reached() {
+            return Ok(0);
+        }
+        let filled = match self.graduation_mode {
+            GraduationMode::SolThreshold => self.total_sol_volume,
+            GraduationMode::SupplySold => self.tokens_sold(),
+        };
+        let elapsed = now.saturating_sub(self.created_at).max(0) as u128;
+        if filled == 0 || elapsed == 0 {
+            return Ok(GRADUATION_ETA_UNKNOWN);
+        }
+
+        let remaining = self.remaining_to_graduation() as u128;
+        let eta = remaining
+            .checked_mul(elapsed)
+            .ok_or(ErrorCode::ArithmeticError)?
+            / filled as u128;
+        Ok(u64::try_from(eta).unwrap_or(GRADUATION_ETA_UNKNOWN))
+    }
+
+    /// Adjust the holder count when a wallet's tracked balance crosses zero
+    pub fn update_holder_count(&mut self, was_holder: bool, is_holder: bool) -> Result<()> {
+        match (was_holder, is_holder) {
+            (false, true) => {
+                self.holder_count = self
+                    .holder_count
+                    .checked_add(1)
+                    .ok_or(ErrorCode::ArithmeticError)?;
+            }
+            (true, false) => self.holder_count = self.holder_count.saturating_sub(1),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Track a creator fee paid out on a trade
+    pub fn record_creator_fee(&mut self, creator_fee: u64) -> Result<()> {
+        self.creator_fees_accrued = self
+            .creator_fees_accrued
+            .checked_add(creator_fee)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        Ok(())
+    }
+
+    /// Creator fee charged on trades, capped by the protocol maximum in force right now
+    pub fn effective_creator_fee_bps(&self, max_creator_fee_bps: u16) -> u16 {
+        self.creator_fee_bps.min(max_creator_fee_bps)
+    }
+
+    /// Whether `trader` skips trading fees: only the creator, and only if opted in
+    pub fn is_fee_exempt(&self, trader: &Pubkey) -> bool {
+        self.creator_fee_exempt && *trader == self.creator
+    }
+
+    /// Trade fee at the curve's current graduation progress
+    pub fn trade_fee_bps(&self) -> Result<u64> {
+        let progress = self.get_progress_percentage()? as i64;
+        let start = self.fee_curve.start_bps as i64;
+        let end = self.fee_curve.end_bps as i64;
+
+        let fee_bps = start + (end - start) * progress / 100;
+
+        Ok(fee_bps as u64)
+    }
+
+    /// Trade fee plus a surcharge for a trade moving `sol_amount` through the curve. The
+    /// surcharge is the trade's share of the SOL reserves it ends up against, in bps, scaled by
+    /// `impact_fee_coefficient` (10_000 = 1:1). The total never exceeds `MAX_TRADE_FEE_BPS`.
+    pub fn impact_adjusted_fee_bps(
+        &self,
+        base_fee_bps: u64,
+        sol_amount: u64,
+        impact_fee_coefficient: u16,
+    ) -> Result<u64> {
+        if impact_fee_coefficient == 0 || sol_amount == 0 {
+            return Ok(base_fee_bps);
+        }
+
+        let reserves = (self.virtual_sol_reserves as u128)
+            .checked_add(sol_amount as u128)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        let impact_bps = (sol_amount as u128) * 10_000 / reserves;
+        let surcharge_bps = impact_bps * impact_fee_coefficient as u128 / 10_000;
+
+        let fee_bps = (base_fee_bps as u128 + surcharge_bps).min(MAX_TRADE_FEE_BPS as u128);
+        Ok(fee_bps as u64)
+    }
+
+    /// Minimum real SOL the curve must hold: every token bought from the curve
+    /// is valued at the launch price, the lowest price the curve can quote.
+    pub fn sol_reserve_floor(&self) -> Result<u64> {
+        if self.initial_virtual_token_reserves == 0 {
+            return Ok(0);
+        }
+
+        let outstanding_tokens = self
+            .initial_real_token_reserves
+            .saturating_sub(self.real_token_reserves);
+
+        let floor = (outstanding_tokens as u128)
+            .checked_mul(self.initial_virtual_sol_reserves as u128)
+            .ok_or(ErrorCode::ArithmeticError)?
+            .checked_div(self.initial_virtual_token_reserves as u128)
+            .ok_or(ErrorCode::ArithmeticError)?;
+
+        Ok(floor as u64)
+    }
+
+    pub fn check_sol_reserve_floor(&self) -> Result<()> {
+        require!(
+            self.real_sol_reserves >= self.sol_reserve_floor()?,
+            ErrorCode::InsufficientReservesForSell
+        );
+        Ok(())
+    }
+
+    /// SOL the curve would pay, before fees, if every token bought from it were sold back
+    pub fn max_redemption(&self) -> Result<u64> {
+        let outstanding_tokens = self
+            .initial_real_token_reserves
+            .saturating_sub(self.real_token_reserves);
+        if outstanding_tokens == 0 {
+            return Ok(0);
+        }
+
+        let k = (self.virtual_token_reserves as u128)
+            .checked_mul(self.virtual_sol_reserves as u128)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        let sol_after = k
+            .checked_div(self.virtual_token_reserves as u128 + outstanding_tokens as u128)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        let redemption = (self.virtual_sol_reserves as u128).saturating_sub(sol_after);
+
+        u64::try_from(redemption).map_err(|_| error!(ErrorCode::ArithmeticError))
+    }
+
+    /// Every lamport added to or taken from the real reserves moves the virtual reserves
+    /// by the same amount, so `virtual_sol_reserves == initial_virtual_sol_reserves +
+    /// real_sol_reserves`. Depth scaling only touches the token side, so it keeps this too.
+    pub fn check_virtual_sol_backing(&self) -> Result<()> {
+        let expected = self
+            .initial_virtual_sol_reserves
+            .checked_add(self.real_sol_reserves)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        require!(self.virtual_sol_reserves >= expected, ErrorCode::VirtualReservesDrift);
+        Ok(())
+    }
+
+    /// Real SOL reserves must back a full redemption of outstanding tokens. Depth scaling
+    /// only lowers the redemption, so the shortfall allowed covers nothing but rounding:
+    /// each trade's constant-product division may favour the trader by a lamport.
+    pub fn check_redemption_backed(&self) -> Result<()> {
+        let tolerance = self.trade_count;
+        require!(
+            self.real_sol_reserves.saturating_add(tolerance) >= self.max_redemption()?,
+            ErrorCode::ReservesDesynced
+        );
+        Ok(())
+    }
+
+    /// The curve's token account holds exactly the real token reserves; buys, sells and
+    /// graduation all move tokens through it
+    pub fn check_token_reserve(&self, held: u64) -> Result<()> {
+        require!(held == self.real_token_reserves, ErrorCode::TokenReserveMismatch);
+        Ok(())
+    }
+
+    /// Whether the vault's spendable lamports cover a full redemption of outstanding tokens
+    pub fn verify_solvency(&self, vault_lamports: u64, rent_minimum: u64) -> Result<bool> {
+        Ok(vault_lamports.saturating_sub(rent_minimum) >= self.max_redemption()?)
+    }
+
+    pub fn check_buys_enabled(&self) -> Result<()> {
+        require!(self.buys_enabled, ErrorCode::BuysDisabled);
+        Ok(())
+    }
+
+    pub fn check_sells_enabled(&self) -> Result<()> {
+        require!(self.sells_enabled, ErrorCode::SellsDisabled);
+        Ok(())
+    }
+
+    /// A creator who opted into the lock can't sell until the curve reaches graduation
+    pub fn check_creator_can_sell(&self, seller: &Pubkey) -> Result<()> {
+        if self.creator_sell_locked_until_graduation && *seller == self.creator {
+            require!(self.graduation_reached(), ErrorCode::CreatorSellLocked);
+        }
+        Ok(())
+    }
+
+    /// Trades are only accepted from `trading_start` up to, but not including, `trading_end`
+    pub fn check_trading_window(&self, now: i64) -> Result<()> {
+        require!(now >= self.trading_start, ErrorCode::TradingWindowClosed);
+        require!(
+            self.trading_end == 0 || now < self.trading_end,
+            ErrorCode::TradingWindowClosed
+        );
+        Ok(())
+    }
+
+    /// Enter the migration, rejecting a curve that is already migrating or migrated
+    pub fn begin_graduation(&mut self) -> Result<()> {
+        require!(!self.graduated, ErrorCode::AlreadyGraduated);
+        require!(!self.graduating, ErrorCode::GraduationInProgress);
+
+        self.graduating = true;
+        Ok(())
+    }
+
+    /// Drop tokens burned at graduation from the tracked supply
+    pub fn burn_supply(&mut self, amount: u64) -> Result<()> {
+        self.token_total_supply = self
+            .token_total_supply
+            .checked_sub(amount)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        Ok(())
+    }
+
+    pub fn finish_graduation(&mut self, amm_pool: Pubkey, now: i64) {
+        self.graduating = false;
+        self.graduated = true;
+        self.graduation_timestamp = now;
+        self.amm_pool = amm_pool;
+    }
+
+    /// Move the graduation target; only allowed before the first trade
+    pub fn set_graduation_threshold(
+        &mut self,
+        graduation_threshold: u64,
+        min_graduation_sol: u64,
+    ) -> Result<()> {
+        require!(self.real_sol_reserves == 0, ErrorCode::TradingAlreadyStarted);
+        self.check_graduation_threshold(graduation_threshold, min_graduation_sol)?;
+
+        self.graduation_threshold = graduation_threshold;
+
+        Ok(())
+    }
+
+    /// A `SolThreshold` curve must graduate with at least `min_graduation_sol` lamports so
+    /// the AMM gets meaningful liquidity. `SupplySold` thresholds count tokens, so only the
+    /// non-zero check applies to them.
+    pub fn check_graduation_threshold(&self, graduation_threshold: u64, min_graduation_sol: u64) -> Result<()> {
+        require!(graduation_threshold > 0, ErrorCode::InvalidGraduationThreshold);
+        if self.graduation_mode == GraduationMode::SolThreshold {
+            require!(
+                graduation_threshold >= min_graduation_sol,
+                ErrorCode::GraduationThresholdBelowMinimum
+            );
+        }
+        Ok(())
+    }
+
+    /// Re-derive the SOL vault address from the stored canonical bump
+    pub fn sol_vault_address(&self, bonding_curve: &Pubkey) -> Result<Pubkey> {
+        Pubkey::create_program_address(
+            &[b"sol_vault", bonding_curve.as_ref(), &[self.sol_vault_bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(ErrorCode::InvalidBump))
+    }
+
+    /// Re-derive the floor vault address from the stored canonical bump
+    pub fn floor_vault_address(&self, bonding_curve: &Pubkey) -> Result<Pubkey> {
+        Pubkey::create_program_address(
+            &[b"floor_vault", bonding_curve.as_ref(), &[self.floor_vault_bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(ErrorCode::InvalidBump))
+    }
+
+    /// Current spot price from virtual reserves, scaled by `SPOT_PRICE_SCALE`
+    pub fn spot_price(&self) -> Result<u64> {
+        if self.virtual_token_reserves == 0 {
+            return Ok(0);
+        }
+
+        let price = (self.virtual_sol_reserves as u128)
+            .checked_mul(SPOT_PRICE_SCALE)
+            .ok_or(ErrorCode::ArithmeticError)?
+            .checked_div(self.virtual_token_reserves as u128)
+            .ok_or(ErrorCode::ArithmeticError)?;
+
+        u64::try_from(price).map_err(|_| error!(ErrorCode::ArithmeticError))
+    }
+
+    /// Human-readable price: SOL per whole token, to 9 decimal places (lamports per whole token).
+    /// `decimals` is the mint's decimals, so front-ends don't each redo the scaling.
+    pub fn display_price(&self, decimals: u8) -> Result<u64> {
+        if self.virtual_token_reserves == 0 {
+            return Ok(0);
+        }
+
+        let one_token = 10u128
+            .checked_pow(decimals as u32)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        let price = (self.virtual_sol_reserves as u128)
+            .checked_mul(one_token)
+            .ok_or(ErrorCode::ArithmeticError)?
+            / self.virtual_token_reserves as u128;
+
+        u64::try_from(price).map_err(|_| error!(ErrorCode::ArithmeticError))
+    }
+
+    /// Lamports the creator's floor reserve pays for `token_amount`, if the sell should
+    /// route there: the curve would pay at or below the floor price and the reserve covers it
+    pub fn floor_payout(&self, token_amount: u64, curve_sol_amount: u64) -> Result<Option<u64>> {
+        if self.floor_price == 0 {
+            return Ok(None);
+        }
+
+        let floor_sol_amount = (token_amount as u128)
+            .checked_mul(self.floor_price as u128)
+            .ok_or(ErrorCode::ArithmeticError)?
+            / SPOT_PRICE_SCALE;
+        let floor_sol_amount =
+            u64::try_from(floor_sol_amount).map_err(|_| error!(ErrorCode::ArithmeticError))?;
+
+        if curve_sol_amount > floor_sol_amount || floor_sol_amount > self.floor_reserve {
+            return Ok(None);
+        }
+        Ok(Some(floor_sol_amount))
+    }
+
+    /// Tokens `sol_amount` would buy at the current spot price, with no price impact
+    pub fn tokens_at_spot(&self, sol_amount: u64) -> Result<u64> {
+        let spot_price = self.spot_price()?;
+        if spot_price == 0 {
+            return Ok(0);
+        }
+
+        let tokens = (sol_amount as u128)
+            .checked_mul(SPOT_PRICE_SCALE)
+            .ok_or(ErrorCode::ArithmeticError)?
+            / spot_price as u128;
+        u64::try_from(tokens).map_err(|_| error!(ErrorCode::ArithmeticError))
+    }
+
+    /// Lamports `token_amount` would fetch at the current spot price, with no price impact
+    pub fn sol_at_spot(&self, token_amount: u64) -> Result<u64> {
+        let sol = (token_amount as u128)
+            .checked_mul(self.spot_price()? as u128)
+            .ok_or(ErrorCode::ArithmeticError)?
+            / SPOT_PRICE_SCALE;
+        u64::try_from(sol).map_err(|_| error!(ErrorCode::ArithmeticError))
+    }
+
+    /// Fill result for a trade that has already been applied to the reserves
+    pub fn order_filled(&self, token_amount: u64, sol_amount: u64, fee: u64) -> Result<OrderFilled> {
+        Ok(OrderFilled {
+            token_amount,
+            sol_amount,
+            fee,
+            new_price: self.spot_price()?,
+        })
+    }
+
+    pub fn curve_state(&self) -> Result<CurveState> {
+        Ok(CurveState {
+            version: CURVE_STATE_VERSION,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            complete: self.complete,
+            progress_percentage: self.get_progress_percentage()?,
+            market_cap: self.get_market_cap()?,
+            holder_count: self.holder_count,
+        })
+    }
+
+    /// How far `virtual_sol_reserves / virtual_token_reserves` has moved from the launch
+    /// ratio, in bps, saturating at `u64::MAX`. Buys move it up along the curve, so compare
+    /// against a threshold above the graduation price rather than zero.
+    pub fn reserve_ratio_deviation_bps(&self) -> Result<u64> {
+        // Cross-multiply so neither ratio is truncated: |vs * ivt - ivs * vt| / (ivs * vt)
+        let current = (self.virtual_sol_reserves as u128)
+            .checked_mul(self.initial_virtual_token_reserves as u128)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        let initial = (self.initial_virtual_sol_reserves as u128)
+            .checked_mul(self.virtual_token_reserves as u128)
+            .ok_or(ErrorCode::ArithmeticError)?;
+        if initial == 0 {
+            return Ok(if current == 0 { 0 } else { u64::MAX });
+        }
+
+        let deviation = current.abs_diff(initial)
+            .checked_mul(10_000)
+            .ok_or(ErrorCode::ArithmeticError)?
+            / initial;
+        Ok(u64::try_from(deviation).unwrap_or(u64::MAX))
+    }
+
+    pub fn check_reserve_ratio(&self, max_deviation_bps: u64) -> Result<ReserveRatioCheck> {
+        let deviation_bps = self.reserve_ratio_deviation_bps()?;
+        Ok(ReserveRatioCheck {
+            deviation_bps,
+            flagged: deviation_bps > max_deviation_bps,
+        })
+    }
+
+    /// Next sequence number for this curve's trade events. Each trade takes one and stamps
+    /// it on every event it emits, so indexers can spot gaps per curve.
+    pub fn next_sequence(&mut self) -> Result<u64> {
+        self.sequence = self.sequence.checked_add(1).ok_or(ErrorCode::ArithmeticError)?;
+        Ok(self.sequence)
+    }
+
+    pub fn reserves_updated_event(&self, slot: u64, sequence: u64) -> ReservesUpdatedEvent {
+        ReservesUpdatedEvent {
+            mint: self.mint,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            slot,
+            sequence,
+        }
+    }
+}
+
+#[event]
+pub struct ReservesUpdatedEvent {
+    pub mint: Pubkey,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub slot: u64,
+    pub sequence: u64,
 }
 
 #[error_code]
@@ -176,5 +862,892 @@ pub enum ErrorCode {
     InsufficientTokenReserves,
     #[msg("Arithmetic error")]
     ArithmeticError,
+    #[msg("Invalid PDA bump")]
+    InvalidBump,
+    #[msg("Trading has already started on this curve")]
+    TradingAlreadyStarted,
+    #[msg("Invalid graduation threshold")]
+    InvalidGraduationThreshold,
+    #[msg("Sell would leave SOL reserves below the solvency floor")]
+    InsufficientReservesForSell,
+    #[msg("Buys are currently disabled for this token")]
+    BuysDisabled,
+    #[msg("Sells are currently disabled for this token")]
+    SellsDisabled,
+    #[msg("Bonding curve is not owned by the signer")]
+    NotCurveCreator,
+    #[msg("Trading is outside this token's scheduled window")]
+    TradingWindowClosed,
+    #[msg("Creator can't sell before graduation")]
+    CreatorSellLocked,
+    #[msg("SOL reserves no longer back the outstanding tokens")]
+    ReservesDesynced,
+    #[msg("Virtual SOL reserves fell behind the real reserves")]
+    VirtualReservesDrift,
+    #[msg("Graduation threshold is below the protocol minimum")]
+    GraduationThresholdBelowMinimum,
+    #[msg("Token has already graduated")]
+    AlreadyGraduated,
+    #[msg("Graduation is already in progress")]
+    GraduationInProgress,
+    #[msg("Curve token account doesn't hold the real token reserves")]
+    TokenReserveMismatch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve() -> BondingCurve {
+        BondingCurve {
+            creator: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 800_000_000_000_000,
+            real_sol_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+            self_trade_guard: false,
+            bump: 255,
+            sol_vault_bump: 255,
+            curve_vault_bump: 255,
+            creator_fee_bps: 0,
+            creator_fee_exempt: false,
+            graduation_threshold: DEFAULT_GRADUATION_THRESHOLD,
+            initial_virtual_token_reserves: 1_073_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_real_token_reserves: 800_000_000_000_000,
+            fee_curve: FeeCurve::default(),
+            sol_vault_initialized: false,
+            buys_enabled: true,
+            sells_enabled: true,
+            completion_timestamp: 0,
+            trade_count: 0,
+            depth_scaling: DepthScaling::default(),
+            creator_fees_accrued: 0,
+            floor_price: 0,
+            floor_reserve: 0,
+            created_at: 0,
+            total_sol_volume: 0,
+            launch_slot: 0,
+            trading_start: 0,
+            trading_end: 0,
+            burn_remainder: false,
+            holder_count: 0,
+            creator_sell_locked_until_graduation: false,
+            graduation_mode: GraduationMode::SolThreshold,
+            floor_vault_bump: 255,
+            graduating: false,
+            graduated: false,
+            graduation_timestamp: 0,
+            amm_pool: Pubkey::default(),
+            sequence: 0,
+        }
+    }
+
+    #[test]
+    fn test_reentrant_graduation_rejected() {
+        let mut curve = curve();
+        assert!(curve.begin_graduation().is_ok());
+
+        // A reentrant call mid-CPI reads the persisted guard
+        let mut reentrant = BondingCurve::try_from_slice(&curve.try_to_vec().unwrap()).unwrap();
+        assert!(reentrant.begin_graduation().is_err());
+
+        let amm_pool = Pubkey::new_unique();
+        curve.finish_graduation(amm_pool, 1_000);
+        assert!(!curve.graduating);
+        assert!(curve.graduated);
+        assert_eq!(curve.graduation_timestamp, 1_000);
+        assert_eq!(curve.amm_pool, amm_pool);
+        assert!(curve.begin_graduation().is_err());
+    }
+
+    #[test]
+    fn test_burned_remainder_leaves_supply() {
+        let mut curve = curve();
+        curve.burn_remainder = true;
+
+        // The curve still holds its 80%; 80% of that seeds the AMM and the rest is burned
+        let curve_tokens = curve.real_token_reserves;
+        let token_liquidity = curve_tokens * 80 / 100;
+        curve.burn_supply(curve_tokens - token_liquidity).unwrap();
+
+        // Only the creator's allocation and the AMM's tokens remain in circulation
+        assert_eq!(
+            curve.token_total_supply,
+            1_000_000_000_000_000 - (curve_tokens - token_liquidity)
+        );
+        assert!(curve.burn_supply(curve.token_total_supply + 1).is_err());
+    }
+
+    #[test]
+    fn test_sequence_increases_per_trade() {
+        let other = curve();
+        let mut curve = curve();
+
+        let sequences: Vec<u64> = (0..3).map(|_| curve.next_sequence().unwrap()).collect();
+
+        assert_eq!(sequences, vec![1, 2, 3]);
+        assert_eq!(curve.sequence, 3);
+        assert_eq!(other.sequence, 0);
+    }
+
+    #[test]
+    fn test_len_matches_serialized_size() {
+        assert_eq!(BondingCurve::LEN, 8 + curve().try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn test_reserves_updated_event_after_buy() {
+        let mut curve = curve();
+        curve.update_reserves_buy(1_000_000, 500).unwrap();
+
+        let event = curve.reserves_updated_event(42, 7);
+        assert_eq!(event.mint, curve.mint);
+        assert_eq!(event.virtual_token_reserves, 1_073_000_000_000_000 - 1_000_000);
+        assert_eq!(event.virtual_sol_reserves, 30_000_000_000 + 500);
+        assert_eq!(event.real_token_reserves, 800_000_000_000_000 - 1_000_000);
+        assert_eq!(event.real_sol_reserves, 500);
+        assert_eq!(event.slot, 42);
+        assert_eq!(event.sequence, 7);
+    }
+
+    #[test]
+    fn test_reserves_updated_event_after_sell() {
+        let mut curve = curve();
+        curve.update_reserves_buy(1_000_000, 500).unwrap();
+        curve.update_reserves_sell(400_000, 200).unwrap();
+
+        let event = curve.reserves_updated_event(43, 8);
+        assert_eq!(event.virtual_token_reserves, 1_073_000_000_000_000 - 600_000);
+        assert_eq!(event.virtual_sol_reserves, 30_000_000_000 + 300);
+        assert_eq!(event.real_token_reserves, 800_000_000_000_000 - 600_000);
+        assert_eq!(event.real_sol_reserves, 300);
+        assert_eq!(event.slot, 43);
+        assert_eq!(event.sequence, 8);
+    }
+
+    #[test]
+    fn test_sol_vault_address_uses_canonical_bump() {
+        let bonding_curve = Pubkey::new_unique();
+        let (canonical, bump) = Pubkey::find_program_address(
+            &[b"sol_vault", bonding_curve.as_ref()],
+            &crate::ID,
+        );
+
+        let mut curve = curve();
+        curve.sol_vault_bump = bump;
+        assert_eq!(curve.sol_vault_address(&bonding_curve).unwrap(), canonical);
+    }
+
+    #[test]
+    fn test_sol_vault_address_rejects_non_canonical_bump() {
+        let bonding_curve = Pubkey::new_unique();
+        let (canonical, bump) = Pubkey::find_program_address(
+            &[b"sol_vault", bonding_curve.as_ref()],
+            &crate::ID,
+        );
+
+        let mut curve = curve();
+        for other_bump in (0..bump).rev() {
+            curve.sol_vault_bump = other_bump;
+            // Any other bump either fails to derive or lands on a different address
+            match curve.sol_vault_address(&bonding_curve) {
+                Ok(address) => assert_ne!(address, canonical),
+                Err(_) => continue,
+            }
+        }
+    }
+
+    #[test]
+    fn test_floor_vault_address_uses_canonical_bump() {
+        let bonding_curve = Pubkey::new_unique();
+        let (canonical, bump) = Pubkey::find_program_address(
+            &[b"floor_vault", bonding_curve.as_ref()],
+            &crate::ID,
+        );
+
+        let mut curve = curve();
+        curve.floor_vault_bump = bump;
+        assert_eq!(curve.floor_vault_address(&bonding_curve).unwrap(), canonical);
+
+        // The vault trades sign with is only ever the canonical one
+        for other_bump in (0..bump).rev() {
+            curve.floor_vault_bump = other_bump;
+            if let Ok(address) = curve.floor_vault_address(&bonding_curve) {
+                assert_ne!(address, canonical);
+            }
+        }
+    }
+
+    #[test]
+    fn test_creator_fee_within_bounds() {
+        let mut curve = curve();
+        curve.creator_fee_bps = 50;
+        assert_eq!(curve.effective_creator_fee_bps(100), 50);
+    }
+
+    #[test]
+    fn test_creator_fee_clamped_to_max() {
+        let mut curve = curve();
+        curve.creator_fee_bps = 500;
+        assert_eq!(curve.effective_creator_fee_bps(100), 100);
+    }
+
+    #[test]
+    fn test_lowered_protocol_max_caps_existing_fee() {
+        let mut curve = curve();
+        curve.creator_fee_bps = 100;
+        assert_eq!(curve.effective_creator_fee_bps(100), 100);
+
+        // The stored fee is untouched, but the effective fee follows the new max
+        assert_eq!(curve.effective_creator_fee_bps(25), 25);
+        assert_eq!(curve.creator_fee_bps, 100);
+    }
+
+    #[test]
+    fn test_creator_fee_exemption_opt_in() {
+        let mut curve = curve();
+        let creator = curve.creator;
+        let other = Pubkey::new_unique();
+
+        assert!(!curve.is_fee_exempt(&creator));
+
+        curve.creator_fee_exempt = true;
+        assert!(curve.is_fee_exempt(&creator));
+        assert!(!curve.is_fee_exempt(&other));
+    }
+
+    #[test]
+    fn test_set_graduation_threshold_before_trading() {
+        let mut curve = curve();
+        assert!(curve.set_graduation_threshold(50_000_000_000, 0).is_ok());
+        assert_eq!(curve.graduation_threshold, 50_000_000_000);
+
+        curve.real_sol_reserves = 25_000_000_000;
+        assert_eq!(curve.get_progress_percentage().unwrap(), 50);
+    }
+
+    #[test]
+    fn test_set_graduation_threshold_rejected_after_first_buy() {
+        let mut curve = curve();
+        curve.update_reserves_buy(1_000_000, 500).unwrap();
+
+        assert!(curve.set_graduation_threshold(50_000_000_000, 0).is_err());
+        assert_eq!(curve.graduation_threshold, DEFAULT_GRADUATION_THRESHOLD);
+    }
+
+    #[test]
+    fn test_graduation_threshold_below_minimum_rejected() {
+        let mut curve = curve();
+        let min_graduation_sol = 40_000_000_000;
+
+        let below_minimum: Error = ErrorCode::GraduationThresholdBelowMinimum.into();
+        assert_eq!(
+            curve.set_graduation_threshold(min_graduation_sol - 1, min_graduation_sol).unwrap_err(),
+            below_minimum
+        );
+        assert_eq!(curve.graduation_threshold, DEFAULT_GRADUATION_THRESHOLD);
+
+        assert!(curve.set_graduation_threshold(min_graduation_sol, min_graduation_sol).is_ok());
+        assert_eq!(curve.graduation_threshold, min_graduation_sol);
+    }
+
+    #[test]
+    fn test_graduation_minimum_ignores_supply_thresholds() {
+        let mut curve = curve();
+        curve.graduation_mode = GraduationMode::SupplySold;
+
+        assert!(curve.check_graduation_threshold(1_000, 40_000_000_000).is_ok());
+        assert!(curve.check_graduation_threshold(0, 0).is_err());
+    }
+
+    fn buy(curve: &mut BondingCurve, token_amount: u64) {
+        let sol_amount = curve.calculate_buy_price(token_amount).unwrap();
+        curve.update_reserves_buy(token_amount, sol_amount).unwrap();
+    }
+
+    fn sell(curve: &mut BondingCurve, token_amount: u64) -> Result<()> {
+        let sol_amount = curve.calculate_sell_price(token_amount)?;
+        curve.update_reserves_sell(token_amount, sol_amount)?;
+        curve.check_sol_reserve_floor()
+    }
+
+    #[test]
+    fn test_sell_cascade_stays_above_floor() {
+        let mut curve = curve();
+        buy(&mut curve, 100_000_000_000_000);
+        assert!(curve.check_sol_reserve_floor().is_ok());
+
+        // Selling back all but the last slice never dips below the floor
+        for _ in 0..9 {
+            assert!(sell(&mut curve, 10_000_000_000_000).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_sell_cascade_hits_floor() {
+        let mut curve = curve();
+        buy(&mut curve, 100_000_000_000_000);
+
+        // Simulate 0.1 SOL of accounting drift out of the reserves
+        curve.real_sol_reserves -= 100_000_000;
+
+        let mut completed = 0;
+        for _ in 0..10 {
+            if sell(&mut curve, 10_000_000_000_000).is_err() {
+                break;
+            }
+            completed += 1;
+        }
+
+        assert_eq!(completed, 3);
+    }
+
+    #[test]
+    fn test_fee_curve_rising() {
+        let mut curve = curve();
+        curve.fee_curve = FeeCurve { start_bps: 100, end_bps: 300 };
+
+        curve.real_sol_reserves = 0;
+        assert_eq!(curve.trade_fee_bps().unwrap(), 100);
+
+        curve.real_sol_reserves = DEFAULT_GRADUATION_THRESHOLD / 2;
+        assert_eq!(curve.trade_fee_bps().unwrap(), 200);
+
+        curve.real_sol_reserves = DEFAULT_GRADUATION_THRESHOLD;
+        assert_eq!(curve.trade_fee_bps().unwrap(), 300);
+    }
+
+    #[test]
+    fn test_fee_curve_falling() {
+        let mut curve = curve();
+        curve.fee_curve = FeeCurve { start_bps: 300, end_bps: 100 };
+
+        curve.real_sol_reserves = 0;
+        assert_eq!(curve.trade_fee_bps().unwrap(), 300);
+
+        curve.real_sol_reserves = DEFAULT_GRADUATION_THRESHOLD / 2;
+        assert_eq!(curve.trade_fee_bps().unwrap(), 200);
+
+        curve.real_sol_reserves = DEFAULT_GRADUATION_THRESHOLD;
+        assert_eq!(curve.trade_fee_bps().unwrap(), 100);
+    }
+
+    #[test]
+    fn test_fee_curve_follows_supply_sold_progress() {
+        let mut curve = curve();
+        curve.fee_curve = FeeCurve { start_bps: 100, end_bps: 300 };
+        curve.graduation_mode = GraduationMode::SupplySold;
+        curve.graduation_threshold = 400_000_000_000_000;
+
+        // SOL past the lamport threshold says nothing about tokens sold
+        curve.update_reserves_buy(0, DEFAULT_GRADUATION_THRESHOLD).unwrap();
+        assert_eq!(curve.get_progress_percentage().unwrap(), 0);
+        assert_eq!(curve.trade_fee_bps().unwrap(), 100);
+
+        curve.update_reserves_buy(200_000_000_000_000, 0).unwrap();
+        assert_eq!(curve.get_progress_percentage().unwrap(), 50);
+        assert_eq!(curve.trade_fee_bps().unwrap(), 200);
+
+        curve.update_reserves_buy(200_000_000_000_000, 0).unwrap();
+        assert_eq!(curve.get_progress_percentage().unwrap(), 100);
+        assert_eq!(curve.trade_fee_bps().unwrap(), 300);
+    }
+
+    #[test]
+    fn test_disabling_buys_leaves_sells_open() {
+        let mut curve = curve();
+        curve.buys_enabled = false;
+
+        assert!(curve.check_buys_enabled().is_err());
+        assert!(curve.check_sells_enabled().is_ok());
+    }
+
+    #[test]
+    fn test_disabling_sells_leaves_buys_open() {
+        let mut curve = curve();
+        curve.sells_enabled = false;
+
+        assert!(curve.check_buys_enabled().is_ok());
+        assert!(curve.check_sells_enabled().is_err());
+    }
+
+    #[test]
+    fn test_order_filled_round_trips_through_return_data() {
+        let mut curve = curve();
+        curve.update_reserves_buy(1_000_000, 500).unwrap();
+
+        let filled = curve.order_filled(1_000_000, 510, 10).unwrap();
+        let data = filled.try_to_vec().unwrap();
+
+        // A router reads the same bytes back from get_return_data
+        let decoded = OrderFilled::try_from_slice(&data).unwrap();
+        assert_eq!(decoded, filled);
+        assert_eq!(decoded.new_price, curve.spot_price().unwrap());
+        assert_eq!(data.len(), 32);
+    }
+
+    #[test]
+    fn test_curve_state_decodes_from_return_data() {
+        let mut curve = curve();
+        curve.update_reserves_buy(1_000_000, 500).unwrap();
+        curve.holder_count = 3;
+
+        let state = curve.curve_state().unwrap();
+        let decoded = CurveState::try_from_slice(&state.try_to_vec().unwrap()).unwrap();
+
+        assert_eq!(decoded, state);
+        assert_eq!(decoded.version, CURVE_STATE_VERSION);
+        assert_eq!(decoded.real_sol_reserves, curve.real_sol_reserves);
+        assert_eq!(decoded.market_cap, curve.get_market_cap().unwrap());
+        assert_eq!(decoded.holder_count, 3);
+        assert!(!decoded.complete);
+    }
+
+    #[test]
+    fn test_spot_price_rises_after_buy() {
+        let mut curve = curve();
+        let before = curve.spot_price().unwrap();
+        curve.update_reserves_buy(1_000_000_000_000, 30_000_000).unwrap();
+
+        assert!(curve.spot_price().unwrap() > before);
+    }
+
+    #[test]
+    fn test_completion_timestamp_set_once() {
+        let mut curve = curve();
+        assert!(!curve.check_completion(100).unwrap());
+        assert_eq!(curve.completion_timestamp, 0);
+
+        curve.real_sol_reserves = DEFAULT_GRADUATION_THRESHOLD;
+        assert!(curve.check_completion(200).unwrap());
+        assert!(curve.check_completion(300).unwrap());
+        assert_eq!(curve.completion_timestamp, 200);
+    }
+
+    #[test]
+    fn test_depth_rescale_scales_token_side_only() {
+        let mut curve = curve();
+        curve.depth_scaling = DepthScaling { interval: 3, multiplier: 2 };
+        curve.update_reserves_buy(1_234_567_891, 37_123).unwrap();
+
+        assert!(!curve.count_trade().unwrap());
+        assert!(!curve.count_trade().unwrap());
+
+        let (sol_before, token_before) = (curve.virtual_sol_reserves, curve.virtual_token_reserves);
+        let price_before = curve.spot_price().unwrap();
+        assert!(curve.count_trade().unwrap());
+
+        assert_eq!(curve.virtual_sol_reserves, sol_before);
+        assert_eq!(curve.virtual_token_reserves, token_before * 2);
+        assert!(curve.spot_price().unwrap() <= price_before / 2 + 1);
+        curve.check_virtual_sol_backing().unwrap();
+    }
+
+    #[test]
+    fn test_rescale_then_sell_stays_backed() {
+        let mut curve = curve();
+        curve.depth_scaling = DepthScaling { interval: 1, multiplier: 2 };
+
+        // A 10 SOL buy, then the rescale it triggers
+        let sol_amount = 10_000_000_000;
+        let token_amount = (curve.virtual_token_reserves as u128 * sol_amount as u128
+            / (curve.virtual_sol_reserves as u128 + sol_amount as u128)) as u64;
+        curve.update_reserves_buy(token_amount, sol_amount).unwrap();
+        assert!(curve.count_trade().unwrap());
+
+        // Selling every token back can't take more than the 10 SOL the curve holds
+        assert!(curve.max_redemption().unwrap() <= curve.real_sol_reserves);
+        let payout = curve.calculate_sell_price(token_amount).unwrap();
+        assert!(payout <= curve.real_sol_reserves);
+
+        curve.update_reserves_sell(token_amount, payout).unwrap();
+        curve.count_trade().unwrap();
+        curve.check_virtual_sol_backing().unwrap();
+        assert_eq!(curve.real_token_reserves, curve.initial_real_token_reserves);
+    }
+
+    #[test]
+    fn test_depth_rescale_reduces_price_impact() {
+        let mut shallow = curve();
+        let mut deep = curve();
+        deep.depth_scaling = DepthScaling { interval: 1, multiplier: 2 };
+        assert!(deep.count_trade().unwrap());
+
+        let tokens = 10_000_000_000_000;
+        assert!(deep.calculate_buy_price(tokens).unwrap() < shallow.calculate_buy_price(tokens).unwrap());
+        assert!(!shallow.count_trade().unwrap());
+    }
+
+    #[test]
+    fn test_depth_scaling_validation() {
+        assert!(DepthScaling::default().is_valid());
+        assert!(DepthScaling { interval: 10, multiplier: 2 }.is_valid());
+        assert!(!DepthScaling { interval: 10, multiplier: 1 }.is_valid());
+    }
+
+    #[test]
+    fn test_display_price_six_decimals() {
+        let mut curve = curve();
+        curve.virtual_token_reserves = 1_000_000_000_000_000; // 1B whole tokens
+
+        // 30 SOL over 1B tokens is 0.00000003 SOL, i.e. 30 lamports, per token
+        assert_eq!(curve.display_price(6).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_display_price_nine_decimals() {
+        let mut curve = curve();
+        curve.virtual_token_reserves = 1_000_000_000_000_000; // 1M whole tokens
+
+        assert_eq!(curve.display_price(9).unwrap(), 30_000);
+        assert_eq!(curve.display_price(9).unwrap(), curve.display_price(6).unwrap() * 1_000);
+    }
+
+    #[test]
+    fn test_creator_summary_aggregates_owned_curves() {
+        let creator = Pubkey::new_unique();
+        let mut curves = [curve(), curve(), curve()];
+        for (i, curve) in curves.iter_mut().enumerate() {
+            curve.creator = creator;
+            curve.real_sol_reserves = 1_000 * (i as u64 + 1);
+            curve.record_creator_fee(10 * (i as u64 + 1)).unwrap();
+        }
+        curves[2].complete = true;
+
+        let mut summary = CreatorSummary::default();
+        for curve in &curves {
+            summary.add(&creator, curve).unwrap();
+        }
+
+        assert_eq!(
+            summary,
+            CreatorSummary {
+                total_sol_reserves: 6_000,
+                total_fees_accrued: 60,
+                graduated_count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_creator_summary_rejects_foreign_curve() {
+        let mut summary = CreatorSummary::default();
+        assert!(summary.add(&Pubkey::new_unique(), &curve()).is_err());
+        assert_eq!(summary, CreatorSummary::default());
+    }
+
+    #[test]
+    fn test_sell_below_floor_routes_to_floor_reserve() {
+        let mut curve = curve();
+        curve.floor_price = curve.spot_price().unwrap();
+        curve.floor_reserve = 1_000_000_000;
+
+        // Selling moves the price down, so the curve pays less than the floor
+        let token_amount = 1_000_000_000_000;
+        let curve_sol_amount = 27_900_000;
+        let floor_sol_amount = (token_amount as u128 * curve.floor_price as u128 / SPOT_PRICE_SCALE) as u64;
+        assert!(curve_sol_amount < floor_sol_amount);
+
+        assert_eq!(
+            curve.floor_payout(token_amount, curve_sol_amount).unwrap(),
+            Some(floor_sol_amount)
+        );
+
+        // An empty reserve falls back to the curve
+        curve.floor_reserve = floor_sol_amount - 1;
+        assert_eq!(curve.floor_payout(token_amount, curve_sol_amount).unwrap(), None);
+    }
+
+    #[test]
+    fn test_sell_above_floor_uses_curve() {
+        let mut curve = curve();
+        curve.floor_price = curve.spot_price().unwrap() / 2;
+        curve.floor_reserve = 1_000_000_000;
+
+        assert_eq!(curve.floor_payout(1_000_000_000_000, 27_900_000).unwrap(), None);
+
+        curve.floor_price = 0;
+        assert_eq!(curve.floor_payout(1_000_000_000_000, 0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_spot_values_invert_spot_price() {
+        let curve = curve();
+        let spot_price = curve.spot_price().unwrap();
+
+        assert_eq!(curve.sol_at_spot(1_000_000_000_000).unwrap(), spot_price * 1_000);
+        assert_eq!(curve.tokens_at_spot(spot_price * 1_000).unwrap(), 1_000_000_000_000);
+    }
+
+    #[test]
+    fn test_graduation_eta_scales_with_volume_rate() {
+        let mut curve = curve();
+        curve.graduation_threshold = 100_000;
+        curve.real_sol_reserves = 40_000;
+
+        // 1_000 lamports/s over the last 100s leaves 60s to fill the remaining 60_000
+        curve.record_volume(100_000).unwrap();
+        assert_eq!(curve.graduation_eta(100).unwrap(), 60);
+
+        // Ten times the volume, a tenth of the wait
+        curve.record_volume(900_000).unwrap();
+        assert_eq!(curve.graduation_eta(100).unwrap(), 6);
+
+        // The same volume spread over longer means a slower rate
+        assert_eq!(curve.graduation_eta(1_000).unwrap(), 60);
+    }
+
+    #[test]
+    fn test_graduation_eta_follows_supply_sold_rate() {
+        let mut curve = curve();
+        curve.graduation_mode = GraduationMode::SupplySold;
+        curve.graduation_threshold = 400_000_000_000_000;
+
+        // Lamports in the reserves don't make a token target any closer
+        curve.real_sol_reserves = DEFAULT_GRADUATION_THRESHOLD;
+        curve.record_volume(DEFAULT_GRADUATION_THRESHOLD).unwrap();
+        assert_eq!(curve.graduation_eta(100).unwrap(), GRADUATION_ETA_UNKNOWN);
+
+        // A quarter of the target sold in 100s leaves 300s for the rest
+        curve.real_token_reserves -= 100_000_000_000_000;
+        assert_eq!(curve.graduation_eta(100).unwrap(), 300);
+
+        curve.real_token_reserves -= 300_000_000_000_000;
+        assert_eq!(curve.graduation_eta(100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_graduation_eta_without_volume_is_unknown() {
+        let mut curve = curve();
+        assert_eq!(curve.graduation_eta(100).unwrap(), GRADUATION_ETA_UNKNOWN);
+
+        curve.record_volume(1).unwrap();
+        assert_eq!(curve.graduation_eta(0).unwrap(), GRADUATION_ETA_UNKNOWN);
+
+        curve.real_sol_reserves = curve.graduation_threshold;
+        assert_eq!(curve.graduation_eta(100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_trading_window_bounds() {
+        let mut curve = curve();
+        curve.trading_start = 1_000;
+        curve.trading_end = 2_000;
+
+        assert!(curve.check_trading_window(999).is_err());
+        assert!(curve.check_trading_window(1_000).is_ok());
+        assert!(curve.check_trading_window(1_999).is_ok());
+        assert!(curve.check_trading_window(2_000).is_err());
+    }
+
+    #[test]
+    fn test_trading_window_open_ended() {
+        let mut curve = curve();
+        curve.trading_start = 1_000;
+
+        assert!(curve.check_trading_window(999).is_err());
+        assert!(curve.check_trading_window(i64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_holder_count_tracks_balances_crossing_zero() {
+        let mut curve = curve();
+
+        curve.update_holder_count(false, true).unwrap();
+        curve.update_holder_count(false, true).unwrap();
+        curve.update_holder_count(true, true).unwrap();
+        assert_eq!(curve.holder_count, 2);
+
+        curve.update_holder_count(true, false).unwrap();
+        curve.update_holder_count(false, false).unwrap();
+        assert_eq!(curve.holder_count, 1);
+    }
+
+    #[test]
+    fn test_locked_creator_cannot_sell_before_graduation() {
+        let mut curve = curve();
+        curve.creator_sell_locked_until_graduation = true;
+        let creator = curve.creator;
+
+        assert!(curve.check_creator_can_sell(&creator).is_err());
+        assert!(curve.check_creator_can_sell(&Pubkey::new_unique()).is_ok());
+
+        // Only graduation lifts the lock, not the completion flag on its own
+        curve.complete = true;
+        assert!(curve.check_creator_can_sell(&creator).is_err());
+
+        curve.real_sol_reserves = curve.graduation_threshold;
+        assert!(curve.check_creator_can_sell(&creator).is_ok());
+    }
+
+    #[test]
+    fn test_unlocked_creator_can_sell() {
+        let curve = curve();
+        assert!(curve.check_creator_can_sell(&curve.creator).is_ok());
+    }
+
+    #[test]
+    fn test_reserves_back_redemption_after_buy() {
+        let mut curve = curve();
+        let sol_amount = 1_000_000_000;
+        let token_amount = (curve.virtual_token_reserves as u128 * sol_amount as u128
+            / (curve.virtual_sol_reserves as u128 + sol_amount as u128)) as u64;
+        curve.update_reserves_buy(token_amount, sol_amount).unwrap();
+
+        let redemption = curve.max_redemption().unwrap();
+        assert!(redemption <= curve.real_sol_reserves);
+        assert!(curve.real_sol_reserves - redemption <= 1);
+        assert!(curve.check_redemption_backed().is_ok());
+        assert!(curve.verify_solvency(sol_amount + 890_880, 890_880).unwrap());
+    }
+
+    #[test]
+    fn test_skewed_reserves_flagged() {
+        let mut curve = curve();
+        curve.update_reserves_buy(30_000_000_000_000, 1_000_000_000).unwrap();
+
+        // Accounting drift: the curve claims less SOL than its tokens redeem for
+        curve.real_sol_reserves /= 2;
+        assert!(curve.check_redemption_backed().is_err());
+
+        // A vault short of the redemption value is insolvent
+        let redemption = curve.max_redemption().unwrap();
+        assert!(!curve.verify_solvency(redemption - 1, 0).unwrap());
+        assert!(!curve.verify_solvency(redemption, 1).unwrap());
+        assert!(curve.verify_solvency(redemption + 1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_token_reserve_must_match_held_tokens() {
+        let curve = curve();
+        assert!(curve.check_token_reserve(curve.real_token_reserves).is_ok());
+        let e: Error = ErrorCode::TokenReserveMismatch.into();
+        assert_eq!(curve.check_token_reserve(curve.real_token_reserves - 1).unwrap_err(), e);
+    }
+
+    #[test]
+    fn test_untraded_curve_owes_nothing() {
+        let curve = curve();
+        assert_eq!(curve.max_redemption().unwrap(), 0);
+        assert!(curve.verify_solvency(0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_sol_threshold_mode_completes_on_reserves() {
+        let mut curve = curve();
+        curve.graduation_threshold = 1_000_000;
+
+        curve.update_reserves_buy(1_000, 999_999).unwrap();
+        assert!(!curve.check_completion(100).unwrap());
+
+        curve.update_reserves_buy(0, 1).unwrap();
+        assert!(curve.check_completion(100).unwrap());
+    }
+
+    #[test]
+    fn test_supply_sold_mode_completes_on_tokens() {
+        let mut curve = curve();
+        curve.graduation_mode = GraduationMode::SupplySold;
+        curve.graduation_threshold = 500_000_000_000_000;
+
+        // SOL past the default threshold doesn't count in this mode
+        curve.update_reserves_buy(499_999_999_999_999, DEFAULT_GRADUATION_THRESHOLD).unwrap();
+        assert!(!curve.check_completion(100).unwrap());
+
+        curve.update_reserves_buy(1, 0).unwrap();
+        assert_eq!(curve.tokens_sold(), 500_000_000_000_000);
+        assert!(curve.check_completion(100).unwrap());
+    }
+
+    #[test]
+    fn test_virtual_sol_tracks_real_over_buys() {
+        let mut curve = curve();
+
+        for (token_amount, sol_amount) in [(1_000_000, 500), (35_000_000_000, 1_000_000), (1, 1)] {
+            curve.update_reserves_buy(token_amount, sol_amount).unwrap();
+            curve.check_virtual_sol_backing().unwrap();
+            assert_eq!(
+                curve.virtual_sol_reserves,
+                curve.initial_virtual_sol_reserves + curve.real_sol_reserves
+            );
+        }
+
+        curve.update_reserves_sell(1_000_000, 400).unwrap();
+        assert!(curve.check_virtual_sol_backing().is_ok());
+    }
+
+    #[test]
+    fn test_virtual_sol_drift_flagged() {
+        let mut curve = curve();
+        curve.update_reserves_buy(1_000_000, 500).unwrap();
+
+        // Real reserves credited without moving the curve
+        curve.real_sol_reserves += 1;
+        assert!(curve.check_virtual_sol_backing().is_err());
+    }
+
+    #[test]
+    fn test_large_buy_pays_more_impact_fee() {
+        let curve = curve();
+        let base_fee_bps = DEFAULT_TRADE_FEE_BPS as u64;
+
+        let small = curve.impact_adjusted_fee_bps(base_fee_bps, 10_000_000, 10_000).unwrap();
+        let large = curve.impact_adjusted_fee_bps(base_fee_bps, 10_000_000_000, 10_000).unwrap();
+
+        // 0.01 SOL against 30 SOL barely moves the price; 10 SOL is a quarter of the post-trade reserves
+        assert_eq!(small, base_fee_bps + 3);
+        assert!(large > small);
+        assert_eq!(large, MAX_TRADE_FEE_BPS as u64);
+
+        let damped = curve.impact_adjusted_fee_bps(base_fee_bps, 10_000_000_000, 1_000).unwrap();
+        assert_eq!(damped, base_fee_bps + 250);
+    }
+
+    #[test]
+    fn test_zero_impact_coefficient_keeps_base_fee() {
+        let curve = curve();
+        let fee_bps = curve.impact_adjusted_fee_bps(100, 10_000_000_000, 0).unwrap();
+        assert_eq!(fee_bps, 100);
+    }
+
+    #[test]
+    fn test_reserve_ratio_at_launch_is_unflagged() {
+        let curve = curve();
+        let check = curve.check_reserve_ratio(0).unwrap();
+
+        assert_eq!(check, ReserveRatioCheck { deviation_bps: 0, flagged: false });
+    }
+
+    #[test]
+    fn test_reserve_ratio_follows_normal_trading() {
+        let mut curve = curve();
+        // Double the SOL side and halve the token side, as a buy along the curve would
+        curve.virtual_sol_reserves = 60_000_000_000;
+        curve.virtual_token_reserves = 536_500_000_000_000;
+
+        // The ratio is 4x the launch ratio: 30_000 bps above it
+        let check = curve.check_reserve_ratio(200_000).unwrap();
+        assert_eq!(check.deviation_bps, 30_000);
+        assert!(!check.flagged);
+    }
+
+    #[test]
+    fn test_skewed_reserve_ratio_flagged() {
+        let mut curve = curve();
+        // SOL side inflated with no matching token outflow
+        curve.virtual_sol_reserves = 3_000_000_000_000;
+
+        let check = curve.check_reserve_ratio(200_000).unwrap();
+        assert_eq!(check.deviation_bps, 990_000);
+        assert!(check.flagged);
+
+        // Token side drained to nothing
+        curve.virtual_token_reserves = 0;
+        assert_eq!(curve.reserve_ratio_deviation_bps().unwrap(), u64::MAX);
+    }
 }
 ```
\ No newline at end of file
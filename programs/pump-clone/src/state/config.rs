@@ -0,0 +1,630 @@
+```rust
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use crate::utils::math::BPS_DIVISOR;
+use super::amm_pool::MAX_POOL_SWAP_FEE_BPS;
+use super::referral::MAX_REFERRAL_LEVELS;
+
+/// Default ceiling on creator-set trading fees (1%)
+pub const DEFAULT_MAX_CREATOR_FEE_BPS: u16 = 100;
+
+/// Default share of real token reserves a single buy can take before it's flagged (10%)
+pub const DEFAULT_HIGH_IMPACT_THRESHOLD_BPS: u16 = 1_000;
+
+/// Default smallest seed liquidity for a new market, in base units (1 token at 6 decimals)
+pub const DEFAULT_MIN_INITIAL_LIQUIDITY: u64 = 1_000_000;
+
+/// Default largest buy allowed during the anti-snipe window, in lamports (0.5 SOL)
+pub const DEFAULT_SNIPE_MAX_BUY: u64 = 500_000_000;
+
+/// Lamports in one SOL, the unit `partner_fee_rate` is quoted against
+const LAMPORTS_PER_SOL: u128 = 1_000_000_000;
+
+/// Largest share of a market's liquidity paid to whoever resolves it (1%)
+pub const MAX_RESOLUTION_REWARD_BPS: u16 = 100;
+
+/// Number of quote mints the market allow-list can hold
+pub const MAX_ALLOWED_QUOTE_MINTS: usize = 4;
+
+/// Default shortest market lifetime, in seconds (1 hour)
+pub const DEFAULT_MIN_MARKET_DURATION: i64 = 60 * 60;
+
+/// Default longest market lifetime, in seconds (1 year)
+pub const DEFAULT_MAX_MARKET_DURATION: i64 = 365 * 24 * 60 * 60;
+
+/// Default time graduation rewards wait before they can be claimed, in seconds (1 day)
+pub const DEFAULT_REWARD_VESTING_PERIOD: i64 = 24 * 60 * 60;
+
+/// Protocol-wide settings, stored in a single PDA at seeds `[b"config"]`.
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub max_markets_per_authority: u64, // 0 = unlimited
+    pub max_creator_fee_bps: u16,
+    pub min_market_duration: i64,
+    pub max_market_duration: i64, // 0 = unlimited
+    pub referral_levels: u8, // 1 or 2
+    pub unique_token_names: bool,
+    pub high_impact_threshold_bps: u16, // 0 = never flag
+    pub min_initial_liquidity: u64,
+    pub max_allowed_slippage_bps: u16, // 0 = no cap
+    pub snipe_block_delay: u64, // 0 = no anti-snipe window
+    pub snipe_max_buy: u64,
+    pub min_holders_for_graduation: u64, // 0 = no requirement
+    pub fee_free_threshold_lamports: u64, // 0 = every trade pays fees
+    pub treasury: Pubkey,
+    pub max_open_markets: u64, // 0 = unlimited
+    pub open_markets: u64, // markets created and not yet resolved or cancelled
+    pub partner_fee_mint: Pubkey, // default = no partner token
+    pub partner_fee_rate: u64, // partner token base units per SOL of fees
+    pub partner_fee_discount_bps: u16,
+    pub hold_rebate_enabled: bool,
+    pub quote_mint_allowlist_enabled: bool,
+    pub allowed_quote_mints: [Pubkey; MAX_ALLOWED_QUOTE_MINTS], // default key = empty slot
+    pub resolution_reward_bps: u16, // 0 = no reward
+    pub max_creator_total_supply: u64, // 0 = unlimited
+    pub impact_fee_coefficient: u16, // bps of price impact added to the trade fee, 0 = off
+    pub internal_pool_graduation: bool, // graduate into a program-owned pool instead of Raydium
+    pub pool_swap_fee_bps: u16, // fee new program-owned pools charge on swaps
+    pub launch_fee: u64, // lamports paid to the treasury per create_token, 0 = free
+    pub min_graduation_sol: u64, // lowest SOL graduation threshold a curve may use, 0 = no floor
+    pub graduation_reward: u64, // lamports paid to whoever calls graduate_token, before the cap
+    pub reward_vesting_period: i64, // seconds a graduation reward waits before it can be claimed
+    pub bump: u8,
+}
+
+impl Config {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        8 + // max_markets_per_authority
+        2 + // max_creator_fee_bps
+        8 + // min_market_duration
+        8 + // max_market_duration
+        1 + // referral_levels
+        1 + // unique_token_names
+        2 + // high_impact_threshold_bps
+        8 + // min_initial_liquidity
+        2 + // max_allowed_slippage_bps
+        8 + // snipe_block_delay
+        8 + // snipe_max_buy
+        8 + // min_holders_for_graduation
+        8 + // fee_free_threshold_lamports
+        32 + // treasury
+        8 + // max_open_markets
+        8 + // open_markets
+        32 + // partner_fee_mint
+        8 + // partner_fee_rate
+        2 + // partner_fee_discount_bps
+        1 + // hold_rebate_enabled
+        1 + // quote_mint_allowlist_enabled
+        32 * MAX_ALLOWED_QUOTE_MINTS + // allowed_quote_mints
+        2 + // resolution_reward_bps
+        8 + // max_creator_total_supply
+        2 + // impact_fee_coefficient
+        1 + // internal_pool_graduation
+        2 + // pool_swap_fee_bps
+        8 + // launch_fee
+        8 + // min_graduation_sol
+        8 + // graduation_reward
+        8 + // reward_vesting_period
+        1; // bump
+
+    /// Check that a market running from `now` to `end_time` lasts an allowed length
+    pub fn check_market_duration(&self, now: i64, end_time: i64) -> Result<()> {
+        let duration = end_time.checked_sub(now).ok_or(PumpError::MathOverflow)?;
+
+        require!(duration >= self.min_market_duration, PumpError::MarketDurationTooShort);
+        if self.max_market_duration > 0 {
+            require!(duration <= self.max_market_duration, PumpError::MarketDurationTooLong);
+        }
+
+        Ok(())
+    }
+
+    pub fn check_initial_liquidity(&self, initial_liquidity: u64) -> Result<()> {
+        require!(
+            initial_liquidity >= self.min_initial_liquidity,
+            PumpError::InsufficientInitialLiquidity
+        );
+        Ok(())
+    }
+
+    /// Trades of less than `fee_free_threshold_lamports` pay no creator or trade fee
+    pub fn is_fee_free(&self, sol_amount: u64) -> bool {
+        sol_amount < self.fee_free_threshold_lamports
+    }
+
+    /// Protocol funds may only be routed to accounts owned by the configured treasury
+    pub fn check_treasury(&self, treasury: Pubkey) -> Result<()> {
+        require_keys_eq!(treasury, self.treasury, PumpError::TreasuryMismatch);
+        Ok(())
+    }
+
+    /// Lamports a token launch owes the treasury. A non-zero fee needs the configured
+    /// treasury account to be passed.
+    pub fn launch_fee_due(&self, treasury: Option<Pubkey>) -> Result<u64> {
+        if self.launch_fee == 0 {
+            return Ok(0);
+        }
+
+        self.check_treasury(treasury.ok_or(PumpError::TreasuryMismatch)?)?;
+        Ok(self.launch_fee)
+    }
+
+    /// Count a newly opened market, enforcing `max_open_markets` unless it is 0
+    pub fn open_market(&mut self) -> Result<()> {
+        if self.max_open_markets > 0 {
+            require!(self.open_markets < self.max_open_markets, PumpError::OpenMarketLimitReached);
+        }
+
+        self.open_markets = self.open_markets.checked_add(1).ok_or(PumpError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Free the slot of a market that was resolved, voided or cancelled
+    pub fn close_market(&mut self) {
+        self.open_markets = self.open_markets.saturating_sub(1);
+    }
+
+    /// Markets must be denominated in an allowed mint while the allow-list is on
+    pub fn check_quote_mint(&self, mint: &Pubkey) -> Result<()> {
+        if self.quote_mint_allowlist_enabled {
+            require!(
+                *mint != Pubkey::default() && self.allowed_quote_mints.contains(mint),
+                PumpError::QuoteMintNotAllowed
+            );
+        }
+        Ok(())
+    }
+
+    /// Add `mint` to the quote allow-list, or remove it when `allowed` is false
+    pub fn set_allowed_quote_mint(&mut self, mint: Pubkey, allowed: bool) -> Result<()> {
+        let existing = self.allowed_quote_mints.iter().position(|m| *m == mint);
+        match (existing, allowed) {
+            (Some(_), true) => {}
+            (Some(index), false) => self.allowed_quote_mints[index] = Pubkey::default(),
+            (None, true) => {
+                let slot = self
+                    .allowed_quote_mints
+                    .iter()
+                    .position(|m| *m == Pubkey::default())
+                    .ok_or(PumpError::QuoteMintListFull)?;
+                self.allowed_quote_mints[slot] = mint;
+            }
+            (None, false) => {}
+        }
+        Ok(())
+    }
+
+    pub fn accepts_partner_fee(&self, mint: &Pubkey) -> bool {
+        self.partner_fee_mint != Pubkey::default() && *mint == self.partner_fee_mint
+    }
+
+    /// Partner tokens charged in place of `fee_lamports` of SOL fees, after the discount
+    pub fn partner_fee_amount(&self, fee_lamports: u64) -> Result<u64> {
+        let full = (fee_lamports as u128)
+            .checked_mul(self.partner_fee_rate as u128)
+            .ok_or(PumpError::MathOverflow)?
+            / LAMPORTS_PER_SOL;
+        let discounted = full * (BPS_DIVISOR - self.partner_fee_discount_bps as u64) as u128
+            / BPS_DIVISOR as u128;
+        u64::try_from(discounted).map_err(|_| error!(PumpError::MathOverflow))
+    }
+
+    /// Cap buys to `snipe_max_buy` for the first `snipe_block_delay` slots after launch
+    pub fn check_snipe_cap(&self, launch_slot: u64, slot: u64, sol_amount: u64) -> Result<()> {
+        if slot < launch_slot.saturating_add(self.snipe_block_delay) {
+            require!(sol_amount <= self.snipe_max_buy, PumpError::SnipeCapExceeded);
+        }
+        Ok(())
+    }
+
+    /// Protocol-wide slippage ceiling, applied whatever bound the caller passed.
+    /// `spot_amount` is what the trade would return at the pre-trade spot price.
+    pub fn check_slippage(&self, spot_amount: u64, realized_amount: u64) -> Result<()> {
+        if self.max_allowed_slippage_bps == 0 || spot_amount == 0 {
+            return Ok(());
+        }
+
+        // Compared cross-multiplied so a fractional bps over the cap doesn't round down to it
+        let shortfall = spot_amount.saturating_sub(realized_amount) as u128;
+        require!(
+            shortfall * BPS_DIVISOR as u128
+                <= self.max_allowed_slippage_bps as u128 * spot_amount as u128,
+            PumpError::SlippageCapExceeded
+        );
+        Ok(())
+    }
+}
+
+/// Fields an admin can change with `update_config`; `None` leaves a field as is
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateConfigArgs {
+    pub max_markets_per_authority: Option<u64>,
+    pub max_creator_fee_bps: Option<u16>,
+    pub min_market_duration: Option<i64>,
+    pub max_market_duration: Option<i64>,
+    pub referral_levels: Option<u8>,
+    pub unique_token_names: Option<bool>,
+    pub high_impact_threshold_bps: Option<u16>,
+    pub min_initial_liquidity: Option<u64>,
+    pub max_allowed_slippage_bps: Option<u16>,
+    pub snipe_block_delay: Option<u64>,
+    pub snipe_max_buy: Option<u64>,
+    pub min_holders_for_graduation: Option<u64>,
+    pub fee_free_threshold_lamports: Option<u64>,
+    pub max_open_markets: Option<u64>,
+    pub partner_fee_mint: Option<Pubkey>,
+    pub partner_fee_rate: Option<u64>,
+    pub partner_fee_discount_bps: Option<u16>,
+    pub hold_rebate_enabled: Option<bool>,
+    pub quote_mint_allowlist_enabled: Option<bool>,
+    pub resolution_reward_bps: Option<u16>,
+    pub max_creator_total_supply: Option<u64>,
+    pub impact_fee_coefficient: Option<u16>,
+    pub internal_pool_graduation: Option<bool>,
+    pub pool_swap_fee_bps: Option<u16>,
+    pub launch_fee: Option<u64>,
+    pub min_graduation_sol: Option<u64>,
+    pub graduation_reward: Option<u64>,
+    pub reward_vesting_period: Option<i64>,
+}
+
+impl Config {
+    pub fn apply(&mut self, args: UpdateConfigArgs) {
+        if let Some(max_markets_per_authority) = args.max_markets_per_authority {
+            self.max_markets_per_authority = max_markets_per_authority;
+        }
+        if let Some(max_creator_fee_bps) = args.max_creator_fee_bps {
+            self.max_creator_fee_bps = max_creator_fee_bps;
+        }
+        if let Some(min_market_duration) = args.min_market_duration {
+            self.min_market_duration = min_market_duration;
+        }
+        if let Some(max_market_duration) = args.max_market_duration {
+            self.max_market_duration = max_market_duration;
+        }
+        if let Some(referral_levels) = args.referral_levels {
+            self.referral_levels = referral_levels.clamp(1, MAX_REFERRAL_LEVELS);
+        }
+        if let Some(unique_token_names) = args.unique_token_names {
+            self.unique_token_names = unique_token_names;
+        }
+        if let Some(high_impact_threshold_bps) = args.high_impact_threshold_bps {
+            self.high_impact_threshold_bps = high_impact_threshold_bps;
+        }
+        if let Some(min_initial_liquidity) = args.min_initial_liquidity {
+            self.min_initial_liquidity = min_initial_liquidity;
+        }
+        if let Some(max_allowed_slippage_bps) = args.max_allowed_slippage_bps {
+            self.max_allowed_slippage_bps = max_allowed_slippage_bps;
+        }
+        if let Some(snipe_block_delay) = args.snipe_block_delay {
+            self.snipe_block_delay = snipe_block_delay;
+        }
+        if let Some(snipe_max_buy) = args.snipe_max_buy {
+            self.snipe_max_buy = snipe_max_buy;
+        }
+        if let Some(min_holders_for_graduation) = args.min_holders_for_graduation {
+            self.min_holders_for_graduation = min_holders_for_graduation;
+        }
+        if let Some(fee_free_threshold_lamports) = args.fee_free_threshold_lamports {
+            self.fee_free_threshold_lamports = fee_free_threshold_lamports;
+        }
+        if let Some(max_open_markets) = args.max_open_markets {
+            self.max_open_markets = max_open_markets;
+        }
+        if let Some(partner_fee_mint) = args.partner_fee_mint {
+            self.partner_fee_mint = partner_fee_mint;
+        }
+        if let Some(partner_fee_rate) = args.partner_fee_rate {
+            self.partner_fee_rate = partner_fee_rate;
+        }
+        if let Some(partner_fee_discount_bps) = args.partner_fee_discount_bps {
+            self.partner_fee_discount_bps = partner_fee_discount_bps.min(BPS_DIVISOR as u16);
+        }
+        if let Some(hold_rebate_enabled) = args.hold_rebate_enabled {
+            self.hold_rebate_enabled = hold_rebate_enabled;
+        }
+        if let Some(quote_mint_allowlist_enabled) = args.quote_mint_allowlist_enabled {
+            self.quote_mint_allowlist_enabled = quote_mint_allowlist_enabled;
+        }
+        if let Some(resolution_reward_bps) = args.resolution_reward_bps {
+            self.resolution_reward_bps = resolution_reward_bps.min(MAX_RESOLUTION_REWARD_BPS);
+        }
+        if let Some(max_creator_total_supply) = args.max_creator_total_supply {
+            self.max_creator_total_supply = max_creator_total_supply;
+        }
+        if let Some(impact_fee_coefficient) = args.impact_fee_coefficient {
+            self.impact_fee_coefficient = impact_fee_coefficient;
+        }
+        if let Some(internal_pool_graduation) = args.internal_pool_graduation {
+            self.internal_pool_graduation = internal_pool_graduation;
+        }
+        if let Some(pool_swap_fee_bps) = args.pool_swap_fee_bps {
+            self.pool_swap_fee_bps = pool_swap_fee_bps.min(MAX_POOL_SWAP_FEE_BPS);
+        }
+        if let Some(launch_fee) = args.launch_fee {
+            self.launch_fee = launch_fee;
+        }
+        if let Some(min_graduation_sol) = args.min_graduation_sol {
+            self.min_graduation_sol = min_graduation_sol;
+        }
+        if let Some(graduation_reward) = args.graduation_reward {
+            self.graduation_reward = graduation_reward;
+        }
+        if let Some(reward_vesting_period) = args.reward_vesting_period {
+            self.reward_vesting_period = reward_vesting_period.max(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            admin: Pubkey::new_unique(),
+            max_markets_per_authority: 0,
+            max_creator_fee_bps: DEFAULT_MAX_CREATOR_FEE_BPS,
+            min_market_duration: DEFAULT_MIN_MARKET_DURATION,
+            max_market_duration: DEFAULT_MAX_MARKET_DURATION,
+            referral_levels: 1,
+            unique_token_names: false,
+            high_impact_threshold_bps: DEFAULT_HIGH_IMPACT_THRESHOLD_BPS,
+            min_initial_liquidity: DEFAULT_MIN_INITIAL_LIQUIDITY,
+            max_allowed_slippage_bps: 0,
+            snipe_block_delay: 0,
+            snipe_max_buy: DEFAULT_SNIPE_MAX_BUY,
+            min_holders_for_graduation: 0,
+            fee_free_threshold_lamports: 0,
+            treasury: Pubkey::new_unique(),
+            max_open_markets: 0,
+            open_markets: 0,
+            partner_fee_mint: Pubkey::default(),
+            partner_fee_rate: 0,
+            partner_fee_discount_bps: 0,
+            hold_rebate_enabled: false,
+            quote_mint_allowlist_enabled: false,
+            allowed_quote_mints: [Pubkey::default(); MAX_ALLOWED_QUOTE_MINTS],
+            resolution_reward_bps: 0,
+            max_creator_total_supply: 0,
+            impact_fee_coefficient: 0,
+            internal_pool_graduation: false,
+            pool_swap_fee_bps: crate::state::DEFAULT_POOL_SWAP_FEE_BPS,
+            launch_fee: 0,
+            min_graduation_sol: 0,
+            graduation_reward: 0,
+            reward_vesting_period: DEFAULT_REWARD_VESTING_PERIOD,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_min_market_duration_boundary() {
+        let config = config();
+        let now = 1_000_000;
+
+        assert!(config.check_market_duration(now, now + DEFAULT_MIN_MARKET_DURATION).is_ok());
+        assert!(config.check_market_duration(now, now + DEFAULT_MIN_MARKET_DURATION - 1).is_err());
+        assert!(config.check_market_duration(now, now + 1).is_err());
+    }
+
+    #[test]
+    fn test_max_market_duration_boundary() {
+        let config = config();
+        let now = 1_000_000;
+
+        assert!(config.check_market_duration(now, now + DEFAULT_MAX_MARKET_DURATION).is_ok());
+        assert!(config.check_market_duration(now, now + DEFAULT_MAX_MARKET_DURATION + 1).is_err());
+    }
+
+    #[test]
+    fn test_zero_max_market_duration_is_unlimited() {
+        let mut config = config();
+        config.apply(UpdateConfigArgs {
+            max_market_duration: Some(0),
+            ..Default::default()
+        });
+
+        assert!(config.check_market_duration(0, i64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_referral_levels_capped_at_two() {
+        let mut config = config();
+
+        for (requested, stored) in [(0, 1), (1, 1), (2, 2), (5, 2)] {
+            config.apply(UpdateConfigArgs {
+                referral_levels: Some(requested),
+                ..Default::default()
+            });
+            assert_eq!(config.referral_levels, stored);
+        }
+    }
+
+    #[test]
+    fn test_min_initial_liquidity_boundary() {
+        let config = config();
+
+        assert!(config.check_initial_liquidity(DEFAULT_MIN_INITIAL_LIQUIDITY).is_ok());
+        assert!(config.check_initial_liquidity(DEFAULT_MIN_INITIAL_LIQUIDITY - 1).is_err());
+        assert!(config.check_initial_liquidity(1).is_err());
+    }
+
+    #[test]
+    fn test_global_slippage_cap_overrides_loose_client_bound() {
+        let mut config = config();
+        config.max_allowed_slippage_bps = 500;
+
+        // The client would accept any fill, but a 40% shortfall against spot still reverts
+        assert!(config.check_slippage(1_000_000, 600_000).is_err());
+        assert!(config.check_slippage(1_000_000, 950_000).is_ok());
+        assert!(config.check_slippage(1_000_000, 949_999).is_err());
+    }
+
+    #[test]
+    fn test_zero_slippage_cap_is_off() {
+        let config = config();
+        assert!(config.check_slippage(1_000_000, 1).is_ok());
+    }
+
+    #[test]
+    fn test_snipe_cap_lifts_after_block_delay() {
+        let mut config = config();
+        config.snipe_block_delay = 3;
+        let launch_slot = 1_000;
+        let big_buy = DEFAULT_SNIPE_MAX_BUY + 1;
+
+        assert!(config.check_snipe_cap(launch_slot, launch_slot, DEFAULT_SNIPE_MAX_BUY).is_ok());
+        assert!(config.check_snipe_cap(launch_slot, launch_slot, big_buy).is_err());
+        assert!(config.check_snipe_cap(launch_slot, launch_slot + 2, big_buy).is_err());
+        assert!(config.check_snipe_cap(launch_slot, launch_slot + 3, big_buy).is_ok());
+    }
+
+    #[test]
+    fn test_snipe_cap_off_by_default() {
+        let config = config();
+        assert!(config.check_snipe_cap(1_000, 1_000, u64::MAX).is_ok());
+    }
+
+
+    #[test]
+    fn test_small_trades_are_fee_free() {
+        let mut config = config();
+        config.fee_free_threshold_lamports = 10_000_000;
+
+        assert!(config.is_fee_free(9_999_999));
+        assert!(!config.is_fee_free(10_000_000));
+        assert!(!config.is_fee_free(1_000_000_000));
+    }
+
+    #[test]
+    fn test_fee_free_off_by_default() {
+        assert!(!config().is_fee_free(0));
+    }
+
+    #[test]
+    fn test_unconfigured_treasury_rejected() {
+        let config = config();
+
+        assert!(config.check_treasury(config.treasury).is_ok());
+        assert!(config.check_treasury(Pubkey::new_unique()).is_err());
+        assert!(config.check_treasury(config.admin).is_err());
+    }
+
+    #[test]
+    fn test_launch_fee_collected_by_treasury() {
+        let mut config = config();
+        config.launch_fee = 100_000_000;
+
+        assert_eq!(config.launch_fee_due(Some(config.treasury)).unwrap(), 100_000_000);
+        assert!(config.launch_fee_due(Some(Pubkey::new_unique())).is_err());
+        assert!(config.launch_fee_due(None).is_err());
+    }
+
+    #[test]
+    fn test_zero_launch_fee_is_free() {
+        let config = config();
+
+        assert_eq!(config.launch_fee_due(None).unwrap(), 0);
+        assert_eq!(config.launch_fee_due(Some(Pubkey::new_unique())).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_open_market_cap_then_freed_slot() {
+        let mut config = config();
+        config.max_open_markets = 2;
+
+        config.open_market().unwrap();
+        config.open_market().unwrap();
+        assert!(config.open_market().is_err());
+        assert_eq!(config.open_markets, 2);
+
+        config.close_market();
+        config.open_market().unwrap();
+        assert_eq!(config.open_markets, 2);
+    }
+
+    #[test]
+    fn test_unlimited_open_markets() {
+        let mut config = config();
+
+        for _ in 0..100 {
+            config.open_market().unwrap();
+        }
+        assert_eq!(config.open_markets, 100);
+    }
+
+    #[test]
+    fn test_partner_fee_discounted() {
+        let mut config = config();
+        config.partner_fee_mint = Pubkey::new_unique();
+        // 50 partner tokens (6 decimals) per SOL, 20% off
+        config.partner_fee_rate = 50_000_000;
+        config.partner_fee_discount_bps = 2_000;
+
+        // 0.01 SOL of fees is worth 0.5 partner tokens, 0.4 after the discount
+        assert_eq!(config.partner_fee_amount(10_000_000).unwrap(), 400_000);
+        assert!(config.accepts_partner_fee(&config.partner_fee_mint));
+        assert!(!config.accepts_partner_fee(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_partner_fee_off_by_default() {
+        let config = config();
+
+        assert!(!config.accepts_partner_fee(&Pubkey::default()));
+    }
+
+    #[test]
+    fn test_quote_mint_allowlist() {
+        let mut config = config();
+        let usdc = Pubkey::new_unique();
+        let junk = Pubkey::new_unique();
+
+        // Off: any mint goes
+        assert!(config.check_quote_mint(&junk).is_ok());
+
+        config.quote_mint_allowlist_enabled = true;
+        config.set_allowed_quote_mint(usdc, true).unwrap();
+        assert!(config.check_quote_mint(&usdc).is_ok());
+        assert!(config.check_quote_mint(&junk).is_err());
+        assert!(config.check_quote_mint(&Pubkey::default()).is_err());
+
+        config.set_allowed_quote_mint(usdc, false).unwrap();
+        assert!(config.check_quote_mint(&usdc).is_err());
+    }
+
+    #[test]
+    fn test_quote_mint_allowlist_capacity() {
+        let mut config = config();
+        for _ in 0..MAX_ALLOWED_QUOTE_MINTS {
+            config.set_allowed_quote_mint(Pubkey::new_unique(), true).unwrap();
+        }
+
+        assert!(config.set_allowed_quote_mint(Pubkey::new_unique(), true).is_err());
+        // Re-adding a listed mint takes no slot
+        let listed = config.allowed_quote_mints[0];
+        assert!(config.set_allowed_quote_mint(listed, true).is_ok());
+    }
+
+    #[test]
+    fn test_resolution_reward_capped_on_update() {
+        let mut config = config();
+
+        config.apply(UpdateConfigArgs { resolution_reward_bps: Some(5_000), ..Default::default() });
+        assert_eq!(config.resolution_reward_bps, MAX_RESOLUTION_REWARD_BPS);
+    }
+
+    #[test]
+    fn test_negative_vesting_period_clamped_on_update() {
+        let mut config = config();
+
+        config.apply(UpdateConfigArgs { reward_vesting_period: Some(-1), ..Default::default() });
+        assert_eq!(config.reward_vesting_period, 0);
+    }
+}
+```
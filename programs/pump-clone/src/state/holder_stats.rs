@@ -0,0 +1,191 @@
+```rust
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use crate::utils::math::BPS_DIVISOR;
+
+const DAY: i64 = 24 * 60 * 60;
+
+/// Share of the sell fee waived once a holder has held for at least the given seconds,
+/// longest tier first
+pub const HOLD_REBATE_TIERS: [(i64, u16); 2] = [(30 * DAY, 7_500), (7 * DAY, 5_000)];
+
+/// Per-wallet, per-mint cost basis and realized profit from trading on the curve.
+///
+/// Only tokens bought through the curve carry a cost basis. Tokens that
+/// arrived by transfer are ignored when a sell is larger than the tracked
+/// balance.
+#[account]
+pub struct HolderStats {
+    pub wallet: Pubkey,
+    pub mint: Pubkey,
+    pub token_balance: u64,
+    pub cost_basis: u64, // lamports paid for `token_balance`
+    pub realized_pnl: i64,
+    pub bump: u8,
+    pub first_buy_ts: i64, // start of the current holding, reset once the balance empties
+}
+
+impl HolderStats {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        32 + // mint
+        8 + // token_balance
+        8 + // cost_basis
+        8 + // realized_pnl
+        1 + // bump
+        8; // first_buy_ts
+
+    /// Start the holding clock if this buy opens a position
+    pub fn start_holding(&mut self, now: i64) {
+        if self.token_balance == 0 {
+            self.first_buy_ts = now;
+        }
+    }
+
+    /// Rebate on the sell fee for the time held at `now`, in basis points of the fee
+    pub fn hold_rebate_bps(&self, now: i64) -> u16 {
+        if self.token_balance == 0 {
+            return 0;
+        }
+
+        let held = now.saturating_sub(self.first_buy_ts);
+        HOLD_REBATE_TIERS
+            .iter()
+            .find(|(min_held, _)| held >= *min_held)
+            .map_or(0, |(_, rebate_bps)| *rebate_bps)
+    }
+
+    /// `fee_bps` reduced by the holder's rebate at `now`
+    pub fn rebated_fee_bps(&self, fee_bps: u64, now: i64) -> u64 {
+        fee_bps * (BPS_DIVISOR - self.hold_rebate_bps(now) as u64) / BPS_DIVISOR
+    }
+
+    pub fn record_buy(&mut self, token_amount: u64, sol_cost: u64) -> Result<()> {
+        self.token_balance = self
+            .token_balance
+            .checked_add(token_amount)
+            .ok_or(PumpError::MathOverflow)?;
+        self.cost_basis = self
+            .cost_basis
+            .checked_add(sol_cost)
+            .ok_or(PumpError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Realize profit or loss on a sell at the weighted-average cost, returning this sell's PnL
+    pub fn record_sell(&mut self, token_amount: u64, sol_proceeds: u64) -> Result<i64> {
+        let tracked = token_amount.min(self.token_balance);
+        if tracked == 0 {
+            return Ok(0);
+        }
+
+        // Only the share of proceeds from tracked tokens counts toward PnL
+        let tracked_proceeds = (sol_proceeds as u128)
+            .checked_mul(tracked as u128)
+            .ok_or(PumpError::MathOverflow)?
+            / token_amount as u128;
+        let cost_removed = (self.cost_basis as u128)
+            .checked_mul(tracked as u128)
+            .ok_or(PumpError::MathOverflow)?
+            / self.token_balance as u128;
+
+        let pnl = i64::try_from(tracked_proceeds as i128 - cost_removed as i128)
+            .map_err(|_| error!(PumpError::MathOverflow))?;
+
+        self.token_balance -= tracked;
+        self.cost_basis -= cost_removed as u64;
+        self.realized_pnl = self
+            .realized_pnl
+            .checked_add(pnl)
+            .ok_or(PumpError::MathOverflow)?;
+
+        Ok(pnl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> HolderStats {
+        HolderStats {
+            wallet: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            token_balance: 0,
+            cost_basis: 0,
+            realized_pnl: 0,
+            bump: 255,
+            first_buy_ts: 0,
+        }
+    }
+
+    #[test]
+    fn test_profitable_sell_at_average_cost() {
+        let mut stats = stats();
+        stats.record_buy(1_000, 10_000).unwrap();
+        stats.record_buy(1_000, 20_000).unwrap();
+
+        assert_eq!(stats.record_sell(1_000, 25_000).unwrap(), 10_000);
+        assert_eq!(stats.realized_pnl, 10_000);
+        assert_eq!(stats.token_balance, 1_000);
+        assert_eq!(stats.cost_basis, 15_000);
+    }
+
+    #[test]
+    fn test_losing_sell_sequence() {
+        let mut stats = stats();
+        stats.record_buy(1_000, 10_000).unwrap();
+
+        assert_eq!(stats.record_sell(500, 3_000).unwrap(), -2_000);
+        assert_eq!(stats.record_sell(500, 4_000).unwrap(), -1_000);
+        assert_eq!(stats.realized_pnl, -3_000);
+        assert_eq!(stats.token_balance, 0);
+        assert_eq!(stats.cost_basis, 0);
+    }
+
+    #[test]
+    fn test_selling_more_than_tracked_ignores_transferred_tokens() {
+        let mut stats = stats();
+        stats.record_buy(1_000, 10_000).unwrap();
+
+        // Half the sold tokens came in by transfer, so half the proceeds are untracked
+        assert_eq!(stats.record_sell(2_000, 30_000).unwrap(), 5_000);
+        assert_eq!(stats.token_balance, 0);
+
+        assert_eq!(stats.record_sell(1_000, 10_000).unwrap(), 0);
+        assert_eq!(stats.realized_pnl, 5_000);
+    }
+
+    #[test]
+    fn test_sell_fee_rebate_tiers() {
+        let mut stats = stats();
+        let bought_at = 1_700_000_000;
+        stats.start_holding(bought_at);
+        stats.record_buy(1_000, 10_000).unwrap();
+
+        assert_eq!(stats.rebated_fee_bps(100, bought_at + 7 * DAY - 1), 100);
+        assert_eq!(stats.rebated_fee_bps(100, bought_at + 7 * DAY), 50);
+        assert_eq!(stats.rebated_fee_bps(100, bought_at + 30 * DAY - 1), 50);
+        assert_eq!(stats.rebated_fee_bps(100, bought_at + 30 * DAY), 25);
+    }
+
+    #[test]
+    fn test_holding_clock_resets_after_exit() {
+        let mut stats = stats();
+        stats.start_holding(0);
+        stats.record_buy(1_000, 10_000).unwrap();
+
+        // Adding to a position keeps the original start
+        stats.start_holding(DAY);
+        stats.record_buy(1_000, 10_000).unwrap();
+        assert_eq!(stats.first_buy_ts, 0);
+
+        stats.record_sell(2_000, 20_000).unwrap();
+        stats.start_holding(10 * DAY);
+        stats.record_buy(1_000, 10_000).unwrap();
+        assert_eq!(stats.hold_rebate_bps(10 * DAY), 0);
+    }
+}
+```
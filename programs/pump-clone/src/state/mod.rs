@@ -0,0 +1,27 @@
+```rust
+pub mod amm_pool;
+pub mod authority_stats;
+pub mod bonding_curve;
+pub mod config;
+pub mod holder_stats;
+pub mod name_registry;
+pub mod order_receipt;
+pub mod referral;
+pub mod reward_account;
+pub mod token_launch;
+pub mod trade_guard;
+pub mod twap_sell;
+
+pub use amm_pool::*;
+pub use authority_stats::*;
+pub use bonding_curve::*;
+pub use config::*;
+pub use holder_stats::*;
+pub use name_registry::*;
+pub use order_receipt::*;
+pub use referral::*;
+pub use reward_account::*;
+pub use token_launch::*;
+pub use trade_guard::*;
+pub use twap_sell::*;
+```
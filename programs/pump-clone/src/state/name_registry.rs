@@ -0,0 +1,61 @@
+```rust
+use anchor_lang::prelude::*;
+
+/// Longest token name, in bytes, accepted by `create_token`, and so the longest registry key
+pub const MAX_TOKEN_NAME_LEN: usize = 32;
+
+/// First-come-first-served claim on a token name, at seeds
+/// `[b"name_registry", normalize_token_name(name)]`.
+///
+/// Only created when `Config::unique_token_names` is on. A second token with
+/// the same normalized name derives the same address, so its `init` fails.
+#[account]
+pub struct NameRegistry {
+    pub name: String,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub bump: u8,
+}
+
+impl NameRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        4 + MAX_TOKEN_NAME_LEN + // name
+        32 + // mint
+        32 + // creator
+        1; // bump
+
+    pub fn address(name: &str) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"name_registry", normalize_token_name(name).as_bytes()],
+            &crate::ID,
+        )
+    }
+}
+
+/// Case- and whitespace-insensitive form of a token name, so "Doge Coin" and
+/// " doge  COIN" reserve the same entry
+pub fn normalize_token_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalization_ignores_case_and_spacing() {
+        assert_eq!(normalize_token_name(" Doge  COIN "), "doge coin");
+        assert_eq!(normalize_token_name("doge coin"), "doge coin");
+    }
+
+    #[test]
+    fn test_duplicate_names_share_a_registry_entry() {
+        // Same address means the second create_token's init is rejected
+        assert_eq!(NameRegistry::address("Doge Coin"), NameRegistry::address("doge  coin"));
+        assert_ne!(NameRegistry::address("Doge Coin"), NameRegistry::address("Doge Coins"));
+    }
+}
+```
@@ -0,0 +1,77 @@
+```rust
+use anchor_lang::prelude::*;
+
+use super::OrderFilled;
+
+/// Seconds during which a retried buy with the same client order id is treated as a duplicate
+pub const ORDER_RECEIPT_WINDOW: i64 = 300;
+
+/// Record of a filled buy, keyed by `(buyer, mint, client_order_id)`.
+#[account]
+pub struct OrderReceipt {
+    pub buyer: Pubkey,
+    pub client_order_id: u64,
+    pub filled_at: i64,
+    /// What the original fill returned, handed back to retries
+    pub fill: OrderFilled,
+}
+
+impl OrderReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // buyer
+        8 + // client_order_id
+        8 + // filled_at
+        32; // fill
+
+    /// Whether `client_order_id` was already filled within the receipt window
+    pub fn is_duplicate(&self, client_order_id: u64, now: i64) -> bool {
+        self.buyer != Pubkey::default()
+            && self.client_order_id == client_order_id
+            && now.saturating_sub(self.filled_at) < ORDER_RECEIPT_WINDOW
+    }
+
+    pub fn record(&mut self, buyer: Pubkey, client_order_id: u64, now: i64, fill: OrderFilled) {
+        self.buyer = buyer;
+        self.client_order_id = client_order_id;
+        self.filled_at = now;
+        self.fill = fill;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receipt() -> OrderReceipt {
+        OrderReceipt {
+            buyer: Pubkey::default(),
+            client_order_id: 0,
+            filled_at: 0,
+            fill: OrderFilled::default(),
+        }
+    }
+
+    #[test]
+    fn test_first_fill_is_not_duplicate() {
+        let receipt = receipt();
+        assert!(!receipt.is_duplicate(7, 1_000));
+    }
+
+    #[test]
+    fn test_retry_within_window_is_duplicate() {
+        let mut receipt = receipt();
+        receipt.record(Pubkey::new_unique(), 7, 1_000, OrderFilled::default());
+
+        assert!(receipt.is_duplicate(7, 1_000));
+        assert!(receipt.is_duplicate(7, 1_000 + ORDER_RECEIPT_WINDOW - 1));
+    }
+
+    #[test]
+    fn test_retry_after_window_is_new_order() {
+        let mut receipt = receipt();
+        receipt.record(Pubkey::new_unique(), 7, 1_000, OrderFilled::default());
+
+        assert!(!receipt.is_duplicate(7, 1_000 + ORDER_RECEIPT_WINDOW));
+    }
+}
+```
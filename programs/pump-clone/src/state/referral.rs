@@ -0,0 +1,101 @@
+```rust
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+
+/// Share of the trade fee paid to a trader's direct referrer (20%)
+pub const REFERRER_SHARE_BPS: u64 = 2_000;
+
+/// Share of the trade fee paid to the referrer's own referrer when two levels are enabled (5%)
+pub const GRANDPARENT_REFERRER_SHARE_BPS: u64 = 500;
+
+/// Deepest referral chain that is paid out, which bounds the accounts a trade touches
+pub const MAX_REFERRAL_LEVELS: u8 = 2;
+
+/// Links a wallet to the wallet that referred it, at seeds `[b"referral", owner]`.
+#[account]
+pub struct ReferralAccount {
+    pub owner: Pubkey,
+    pub referrer: Pubkey,
+    pub bump: u8,
+}
+
+impl ReferralAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // referrer
+        1; // bump
+
+    pub fn has_referrer(&self) -> bool {
+        self.referrer != Pubkey::default()
+    }
+}
+
+/// Lamports of a trade fee owed to each level of the referral chain
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReferralSplit {
+    pub referrer: u64,
+    pub grandparent: u64,
+}
+
+impl ReferralSplit {
+    pub fn total(&self) -> Result<u64> {
+        self.referrer
+            .checked_add(self.grandparent)
+            .ok_or(error!(PumpError::MathOverflow))
+    }
+}
+
+/// Split `trade_fee` between the referrer and, with two levels enabled, the grandparent
+pub fn split_referral_fee(trade_fee: u64, referral_levels: u8, has_grandparent: bool) -> Result<ReferralSplit> {
+    let share = |bps: u64| -> Result<u64> {
+        let amount = (trade_fee as u128)
+            .checked_mul(bps as u128)
+            .ok_or(PumpError::MathOverflow)?
+            / 10_000;
+        Ok(amount as u64)
+    };
+
+    let grandparent = if referral_levels >= MAX_REFERRAL_LEVELS && has_grandparent {
+        share(GRANDPARENT_REFERRER_SHARE_BPS)?
+    } else {
+        0
+    };
+
+    Ok(ReferralSplit {
+        referrer: share(REFERRER_SHARE_BPS)?,
+        grandparent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_level_split() {
+        let split = split_referral_fee(10_000, 1, true).unwrap();
+
+        assert_eq!(split.referrer, 2_000);
+        assert_eq!(split.grandparent, 0);
+        assert_eq!(split.total().unwrap(), 2_000);
+    }
+
+    #[test]
+    fn test_two_level_split() {
+        let split = split_referral_fee(10_000, 2, true).unwrap();
+
+        assert_eq!(split.referrer, 2_000);
+        assert_eq!(split.grandparent, 500);
+        assert!(split.grandparent < split.referrer);
+        assert_eq!(split.total().unwrap(), 2_500);
+    }
+
+    #[test]
+    fn test_two_levels_without_grandparent_pays_referrer_only() {
+        let split = split_referral_fee(10_000, 2, false).unwrap();
+
+        assert_eq!(split, ReferralSplit { referrer: 2_000, grandparent: 0 });
+    }
+}
+```
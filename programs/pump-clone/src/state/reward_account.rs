@@ -0,0 +1,85 @@
+```rust
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+
+/// Graduation rewards owed to one wallet, held in this PDA at seeds `[b"reward", owner]`
+/// until they vest. Every new reward restarts the clock on the whole balance, so a reward
+/// can't be farmed and pulled out straight away.
+#[account]
+pub struct RewardAccount {
+    pub owner: Pubkey,
+    pub accrued: u64, // lamports owed and not yet claimed
+    pub vests_at: i64,
+    pub bump: u8,
+}
+
+impl RewardAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        8 + // accrued
+        8 + // vests_at
+        1; // bump
+
+    /// Add `amount` lamports, claimable `vesting_period` seconds after `now`
+    pub fn accrue(&mut self, amount: u64, now: i64, vesting_period: i64) -> Result<()> {
+        self.accrued = self.accrued.checked_add(amount).ok_or(PumpError::MathOverflow)?;
+        self.vests_at = now.checked_add(vesting_period).ok_or(PumpError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Take the whole vested balance
+    pub fn claim(&mut self, now: i64) -> Result<u64> {
+        require!(self.accrued > 0, PumpError::NoRewardsToClaim);
+        require!(now >= self.vests_at, PumpError::RewardNotVested);
+
+        let amount = self.accrued;
+        self.accrued = 0;
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reward_account() -> RewardAccount {
+        RewardAccount {
+            owner: Pubkey::new_unique(),
+            accrued: 0,
+            vests_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_claim_before_vesting_rejected() {
+        let mut rewards = reward_account();
+        rewards.accrue(500, 1_000, 3_600).unwrap();
+
+        assert!(rewards.claim(1_000).is_err());
+        assert!(rewards.claim(4_599).is_err());
+        assert_eq!(rewards.accrued, 500);
+    }
+
+    #[test]
+    fn test_claim_after_vesting_takes_balance() {
+        let mut rewards = reward_account();
+        rewards.accrue(500, 1_000, 3_600).unwrap();
+
+        assert_eq!(rewards.claim(4_600).unwrap(), 500);
+        assert_eq!(rewards.accrued, 0);
+        assert!(rewards.claim(4_600).is_err());
+    }
+
+    #[test]
+    fn test_new_reward_restarts_vesting() {
+        let mut rewards = reward_account();
+        rewards.accrue(500, 1_000, 3_600).unwrap();
+        rewards.accrue(300, 4_000, 3_600).unwrap();
+
+        assert!(rewards.claim(4_600).is_err());
+        assert_eq!(rewards.claim(7_600).unwrap(), 800);
+    }
+}
+```
\ No newline at end of file
@@ -1,6 +1,36 @@
 ```rust
 use anchor_lang::prelude::*;
 
+use crate::errors::*;
+
+/// URI schemes accepted for a launch's image and social links
+pub const ALLOWED_URI_SCHEMES: [&str; 2] = ["https://", "ipfs://"];
+
+/// Require an allowed scheme followed by something, keeping `javascript:` and other junk off-chain
+pub fn validate_uri(uri: &str) -> Result<()> {
+    let valid = ALLOWED_URI_SCHEMES.iter().any(|scheme| {
+        uri.len() > scheme.len()
+            && uri.get(..scheme.len()).map_or(false, |prefix| prefix.eq_ignore_ascii_case(scheme))
+    });
+    require!(valid, PumpError::InvalidUri);
+    Ok(())
+}
+
+/// Longest name, in UTF-8 bytes, that fits a launch's reserved space
+pub const MAX_LAUNCH_NAME_LEN: usize = 32;
+pub const MAX_LAUNCH_SYMBOL_LEN: usize = 10;
+pub const MAX_LAUNCH_DESCRIPTION_LEN: usize = 200;
+pub const MAX_LAUNCH_LINK_LEN: usize = 100;
+
+/// Bound a string by its UTF-8 byte length, which is what the account space holds. A
+/// character count never exceeds it, so multi-byte text gets fewer characters than the limit.
+pub fn check_text_len(text: &str, max_bytes: usize, error: PumpError) -> Result<()> {
+    if text.len() > max_bytes {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
 #[account]
 pub struct TokenLaunch {
     pub creator: Pubkey,
@@ -38,13 +68,13 @@ impl TokenLaunch {
     pub const LEN: usize = 8 + // discriminator
         32 + // creator
         32 + // mint
-        4 + 32 + // name (max 32 chars)
-        4 + 10 + // symbol (max 10 chars)
-        4 + 200 + // description (max 200 chars)
-        4 + 200 + // image_uri (max 200 chars)
-        1 + 4 + 100 + // website (optional, max 100 chars)
-        1 + 4 + 100 + // telegram (optional, max 100 chars)
-        1 + 4 + 100 + // twitter (optional, max 100 chars)
+        4 + 32 + // name (max 32 bytes)
+        4 + 10 + // symbol (max 10 bytes)
+        4 + 200 + // description (max 200 bytes)
+        4 + 200 + // image_uri (max 200 bytes)
+        1 + 4 + 100 + // website (optional, max 100 bytes)
+        1 + 4 + 100 + // telegram (optional, max 100 bytes)
+        1 + 4 + 100 + // twitter (optional, max 100 bytes)
         8 + // total_supply
         8 + // initial_virtual_token_reserves
         8 + // initial_virtual_sol_reserves
@@ -66,6 +96,7 @@ impl TokenLaunch {
         8 + // market_id
         1; // inverted
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &mut self,
         creator: Pubkey,
@@ -86,6 +117,16 @@ impl TokenLaunch {
         market_id: u64,
         inverted: bool,
     ) -> Result<()> {
+        check_text_len(&name, MAX_LAUNCH_NAME_LEN, PumpError::NameTooLong)?;
+        check_text_len(&symbol, MAX_LAUNCH_SYMBOL_LEN, PumpError::SymbolTooLong)?;
+        check_text_len(&description, MAX_LAUNCH_DESCRIPTION_LEN, PumpError::DescriptionTooLong)?;
+        check_text_len(&image_uri, 200, PumpError::UriTooLong)?;
+        validate_uri(&image_uri)?;
+        for link in [&website, &telegram, &twitter].into_iter().flatten() {
+            check_text_len(link, MAX_LAUNCH_LINK_LEN, PumpError::UriTooLong)?;
+            validate_uri(link)?;
+        }
+
         let clock = Clock::get()?;
         
         self.creator = creator;
@@ -136,14 +177,37 @@ impl TokenLaunch {
         self.real_sol_reserves = real_sol_reserves;
         self.updated_at = clock.unix_timestamp;
 
-        // Calculate market cap based on current reserves
-        if self.virtual_token_reserves > 0 {
-            self.market_cap = (self.virtual_sol_reserves * self.token_total_supply) / self.virtual_token_reserves;
-        }
+        self.market_cap = self.compute_market_cap()?;
 
         Ok(())
     }
 
+    /// Market cap in lamports at current reserves, or 0 before the curve has token reserves
+    pub fn compute_market_cap(&self) -> Result<u64> {
+        if self.virtual_token_reserves == 0 {
+            return Ok(0);
+        }
+
+        let market_cap = (self.virtual_sol_reserves as u128)
+            .checked_mul(self.token_total_supply as u128)
+            .ok_or(PumpError::MathOverflow)?
+            / self.virtual_token_reserves as u128;
+
+        u64::try_from(market_cap).map_err(|_| error!(PumpError::MathOverflow))
+    }
+
+    /// Recompute and persist `market_cap`, stamping `updated_at` with `now`
+    pub fn refresh_market_cap(&mut self, now: i64) -> Result<MarketCapUpdatedEvent> {
+        self.market_cap = self.compute_market_cap()?;
+        self.updated_at = now;
+
+        Ok(MarketCapUpdatedEvent {
+            mint: self.mint,
+            market_cap: self.market_cap,
+            updated_at: now,
+        })
+    }
+
     pub fn complete_launch(&mut self) -> Result<()> {
         let clock = Clock::get()?;
         
@@ -157,7 +221,7 @@ impl TokenLaunch {
     pub fn increment_reply_count(&mut self) -> Result<()> {
         let clock = Clock::get()?;
         
-        self.reply_count = self.reply_count.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.reply_count = self.reply_count.checked_add(1).ok_or(PumpError::MathOverflow)?;
         self.updated_at = clock.unix_timestamp;
 
         Ok(())
@@ -181,21 +245,21 @@ impl TokenLaunch {
         let virtual_token_reserves = self.virtual_token_reserves;
 
         if virtual_token_reserves <= amount {
-            return Err(ProgramError::InsufficientFunds.into());
+            return Err(PumpError::InsufficientTokenReserves.into());
         }
 
         let new_virtual_token_reserves = virtual_token_reserves.checked_sub(amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(PumpError::MathOverflow)?;
 
         let new_virtual_sol_reserves = (virtual_sol_reserves as u128)
             .checked_mul(virtual_token_reserves as u128)
-            .ok_or(ProgramError::ArithmeticOverflow)?
+            .ok_or(PumpError::MathOverflow)?
             .checked_div(new_virtual_token_reserves as u128)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(PumpError::MathOverflow)?;
 
         let sol_amount = (new_virtual_sol_reserves as u64)
             .checked_sub(virtual_sol_reserves)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(PumpError::MathOverflow)?;
 
         Ok(sol_amount)
     }
@@ -209,19 +273,132 @@ impl TokenLaunch {
         let virtual_token_reserves = self.virtual_token_reserves;
 
         let new_virtual_token_reserves = virtual_token_reserves.checked_add(amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(PumpError::MathOverflow)?;
 
         let new_virtual_sol_reserves = (virtual_sol_reserves as u128)
             .checked_mul(virtual_token_reserves as u128)
-            .ok_or(ProgramError::ArithmeticOverflow)?
+            .ok_or(PumpError::MathOverflow)?
             .checked_div(new_virtual_token_reserves as u128)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(PumpError::MathOverflow)?;
 
         let sol_amount = virtual_sol_reserves
             .checked_sub(new_virtual_sol_reserves as u64)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(PumpError::MathOverflow)?;
 
         Ok(sol_amount)
     }
 }
+
+#[event]
+pub struct MarketCapUpdatedEvent {
+    pub mint: Pubkey,
+    pub market_cap: u64,
+    pub updated_at: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch() -> TokenLaunch {
+        TokenLaunch {
+            creator: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            description: String::new(),
+            image_uri: "https://example.com/token.png".to_string(),
+            website: None,
+            telegram: None,
+            twitter: None,
+            total_supply: 1_000_000_000_000_000,
+            initial_virtual_token_reserves: 1_073_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_real_token_reserves: 793_100_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 793_100_000_000_000,
+            real_sol_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+            bump: 255,
+            created_at: 0,
+            updated_at: 0,
+            migration_fee: 0,
+            king_of_hill_timestamp: 0,
+            market_cap: 0,
+            reply_count: 0,
+            nsfw: false,
+            market_id: 0,
+            inverted: false,
+        }
+    }
+
+    #[test]
+    fn test_refreshed_market_cap_matches_computed() {
+        let mut launch = launch();
+        launch.virtual_sol_reserves = 45_000_000_000;
+        launch.virtual_token_reserves = 715_000_000_000_000;
+
+        let event = launch.refresh_market_cap(1_700_000_000).unwrap();
+
+        assert_eq!(launch.market_cap, launch.compute_market_cap().unwrap());
+        assert_eq!(launch.market_cap, 62_937_062_937);
+        assert_eq!(launch.updated_at, 1_700_000_000);
+        assert_eq!(event.market_cap, launch.market_cap);
+    }
+
+    #[test]
+    fn test_market_cap_does_not_overflow_u64_product() {
+        // virtual_sol_reserves * token_total_supply exceeds u64::MAX
+        let launch = launch();
+        assert_eq!(launch.compute_market_cap().unwrap(), 27_958_993_476);
+    }
+
+    #[test]
+    fn test_allowed_uri_schemes_accepted() {
+        assert!(validate_uri("https://example.com/token.png").is_ok());
+        assert!(validate_uri("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").is_ok());
+        assert!(validate_uri("HTTPS://example.com").is_ok());
+    }
+
+    #[test]
+    fn test_other_uri_schemes_rejected() {
+        assert!(validate_uri("javascript:alert(1)").is_err());
+        assert!(validate_uri("http://example.com").is_err());
+        assert!(validate_uri("data:image/png;base64,AAAA").is_err());
+        assert!(validate_uri("example.com").is_err());
+        assert!(validate_uri("https://").is_err());
+        assert!(validate_uri("").is_err());
+        assert!(validate_uri("httpsé://example.com").is_err());
+    }
+
+    #[test]
+    fn test_multibyte_name_at_boundary() {
+        let ascii = "a".repeat(MAX_LAUNCH_NAME_LEN);
+        assert!(check_text_len(&ascii, MAX_LAUNCH_NAME_LEN, PumpError::NameTooLong).is_ok());
+
+        // 32 characters, but 128 bytes: would overflow the reserved space
+        let emoji = "🚀".repeat(MAX_LAUNCH_NAME_LEN);
+        assert!(check_text_len(&emoji, MAX_LAUNCH_NAME_LEN, PumpError::NameTooLong).is_err());
+
+        // 8 four-byte characters fill exactly 32 bytes
+        let fits = "🚀".repeat(MAX_LAUNCH_NAME_LEN / 4);
+        assert!(check_text_len(&fits, MAX_LAUNCH_NAME_LEN, PumpError::NameTooLong).is_ok());
+
+        let over = format!("{}é", "a".repeat(MAX_LAUNCH_NAME_LEN - 1));
+        assert_eq!(over.chars().count(), MAX_LAUNCH_NAME_LEN);
+        assert!(check_text_len(&over, MAX_LAUNCH_NAME_LEN, PumpError::NameTooLong).is_err());
+    }
+
+    #[test]
+    fn test_price_errors_use_crate_codes() {
+        let launch = launch();
+        let insufficient: Error = PumpError::InsufficientTokenReserves.into();
+        let overflow: Error = PumpError::MathOverflow.into();
+
+        assert_eq!(launch.get_buy_price(launch.virtual_token_reserves).unwrap_err(), insufficient);
+        assert_eq!(launch.get_sell_price(u64::MAX).unwrap_err(), overflow);
+    }
+}
 ```
\ No newline at end of file
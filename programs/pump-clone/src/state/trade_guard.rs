@@ -0,0 +1,93 @@
+```rust
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+
+/// Per-wallet, per-mint record of trades made in the current slot.
+///
+/// This is a best-effort wash-trading deterrent: a wallet can still split
+/// its trades across slots or across wallets.
+#[account]
+pub struct TradeGuard {
+    pub wallet: Pubkey,
+    pub mint: Pubkey,
+    pub slot: u64,
+    pub bought: bool,
+    pub sold: bool,
+    pub bump: u8,
+}
+
+impl TradeGuard {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        32 + // mint
+        8 + // slot
+        1 + // bought
+        1 + // sold
+        1; // bump
+
+    /// Record a trade, rejecting it if the wallet already completed a buy and
+    /// sell of this mint within `slot`.
+    pub fn record_trade(&mut self, slot: u64, is_buy: bool) -> Result<()> {
+        if self.slot != slot {
+            self.slot = slot;
+            self.bought = false;
+            self.sold = false;
+        }
+
+        require!(!(self.bought && self.sold), PumpError::SelfTradeLimitExceeded);
+
+        if is_buy {
+            self.bought = true;
+        } else {
+            self.sold = true;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard() -> TradeGuard {
+        TradeGuard {
+            wallet: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            slot: 0,
+            bought: false,
+            sold: false,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_one_cycle_per_slot_allowed() {
+        let mut guard = guard();
+        assert!(guard.record_trade(10, true).is_ok());
+        assert!(guard.record_trade(10, true).is_ok());
+        assert!(guard.record_trade(10, false).is_ok());
+    }
+
+    #[test]
+    fn test_second_cycle_in_slot_rejected() {
+        let mut guard = guard();
+        guard.record_trade(10, true).unwrap();
+        guard.record_trade(10, false).unwrap();
+
+        assert!(guard.record_trade(10, true).is_err());
+        assert!(guard.record_trade(10, false).is_err());
+    }
+
+    #[test]
+    fn test_limit_resets_next_slot() {
+        let mut guard = guard();
+        guard.record_trade(10, true).unwrap();
+        guard.record_trade(10, false).unwrap();
+
+        assert!(guard.record_trade(11, true).is_ok());
+        assert!(guard.record_trade(11, false).is_ok());
+    }
+}
+```
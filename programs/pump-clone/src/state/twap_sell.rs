@@ -0,0 +1,161 @@
+```rust
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+
+/// Most slices a single TWAP sell may be split into
+pub const MAX_TWAP_INTERVALS: u16 = 1_000;
+
+/// A large sell split into equal slices, one per interval, keyed by `(mint, seller)`.
+#[account]
+pub struct TwapSell {
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub intervals: u16,
+    pub interval_seconds: i64,
+    pub start_ts: i64,
+    pub slices_executed: u16,
+    pub amount_sold: u64,
+    pub bump: u8,
+}
+
+impl TwapSell {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // seller
+        32 + // mint
+        8 + // total_amount
+        2 + // intervals
+        8 + // interval_seconds
+        8 + // start_ts
+        2 + // slices_executed
+        8 + // amount_sold
+        1; // bump
+
+    /// Start a schedule selling `total_amount` over `intervals` slices, the first available at `now`
+    pub fn schedule(
+        &mut self,
+        total_amount: u64,
+        intervals: u16,
+        interval_seconds: i64,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            intervals > 0
+                && intervals <= MAX_TWAP_INTERVALS
+                && interval_seconds > 0
+                && total_amount >= intervals as u64,
+            PumpError::InvalidTwapSchedule
+        );
+
+        self.total_amount = total_amount;
+        self.intervals = intervals;
+        self.interval_seconds = interval_seconds;
+        self.start_ts = now;
+        self.slices_executed = 0;
+        self.amount_sold = 0;
+
+        Ok(())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.slices_executed >= self.intervals
+    }
+
+    /// Earliest time the next slice may be sold
+    pub fn next_slice_ts(&self) -> Result<i64> {
+        (self.slices_executed as i64)
+            .checked_mul(self.interval_seconds)
+            .and_then(|offset| self.start_ts.checked_add(offset))
+            .ok_or(error!(PumpError::MathOverflow))
+    }
+
+    /// Size of the next slice; the last one also carries the division remainder
+    pub fn next_slice(&self, now: i64) -> Result<u64> {
+        require!(!self.is_complete(), PumpError::TwapSellComplete);
+        require!(now >= self.next_slice_ts()?, PumpError::TwapSliceTooEarly);
+
+        if self.slices_executed + 1 == self.intervals {
+            return Ok(self.total_amount - self.amount_sold);
+        }
+        Ok(self.total_amount / self.intervals as u64)
+    }
+
+    pub fn record_slice(&mut self, amount: u64) -> Result<()> {
+        self.amount_sold = self
+            .amount_sold
+            .checked_add(amount)
+            .ok_or(PumpError::MathOverflow)?;
+        self.slices_executed += 1;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn twap(total_amount: u64, intervals: u16, interval_seconds: i64) -> TwapSell {
+        let mut twap = TwapSell {
+            seller: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            total_amount: 0,
+            intervals: 0,
+            interval_seconds: 0,
+            start_ts: 0,
+            slices_executed: 0,
+            amount_sold: 0,
+            bump: 255,
+        };
+        twap.schedule(total_amount, intervals, interval_seconds, 1_000).unwrap();
+        twap
+    }
+
+    #[test]
+    fn test_slices_execute_across_intervals() {
+        let mut twap = twap(1_000, 3, 60);
+
+        for now in [1_000, 1_060, 1_125] {
+            let slice = twap.next_slice(now).unwrap();
+            twap.record_slice(slice).unwrap();
+        }
+
+        assert!(twap.is_complete());
+        assert_eq!(twap.amount_sold, 1_000);
+        assert!(twap.next_slice(10_000).is_err());
+    }
+
+    #[test]
+    fn test_last_slice_carries_remainder() {
+        let mut twap = twap(1_000, 3, 60);
+
+        assert_eq!(twap.next_slice(1_000).unwrap(), 333);
+        twap.record_slice(333).unwrap();
+        assert_eq!(twap.next_slice(1_060).unwrap(), 333);
+        twap.record_slice(333).unwrap();
+        assert_eq!(twap.next_slice(1_120).unwrap(), 334);
+    }
+
+    #[test]
+    fn test_early_slice_rejected() {
+        let mut twap = twap(1_000, 4, 60);
+        let slice = twap.next_slice(1_000).unwrap();
+        twap.record_slice(slice).unwrap();
+
+        let too_early: Error = PumpError::TwapSliceTooEarly.into();
+        assert_eq!(twap.next_slice(1_059).unwrap_err(), too_early);
+        assert!(twap.next_slice(1_060).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_schedule_rejected() {
+        let mut twap = twap(1_000, 1, 1);
+
+        assert!(twap.schedule(1_000, 0, 60, 0).is_err());
+        assert!(twap.schedule(1_000, 4, 0, 0).is_err());
+        assert!(twap.schedule(3, 4, 60, 0).is_err());
+        assert!(twap.schedule(1_000_000, MAX_TWAP_INTERVALS + 1, 60, 0).is_err());
+    }
+}
+```
\ No newline at end of file
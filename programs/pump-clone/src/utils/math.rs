@@ -1,6 +1,22 @@
 ```rust
 use anchor_lang::prelude::*;
 
+/// Fixed-point scale for curve ratios and prices: `PRECISION` represents 1.0
+pub const PRECISION: u64 = 1_000_000;
+
+/// Basis points in one whole: 10_000 bps == 100%
+pub const BPS_DIVISOR: u64 = 10_000;
+
+/// Build a math error, logging the failed operation's operands when built with the
+/// `debug-math` feature. Off by default to save compute units.
+macro_rules! math_err {
+    ($err:expr, $lhs:expr, $op:literal, $rhs:expr) => {{
+        #[cfg(feature = "debug-math")]
+        msg!("{:?}: {} {} {}", $err, $lhs, $op, $rhs);
+        error!($err)
+    }};
+}
+
 /// Mathematical utilities for bonding curve calculations
 pub struct MathUtils;
 
@@ -24,7 +40,9 @@ impl MathUtils {
         let start_integral = Self::calculate_integral(current_supply, base_price, max_supply)?;
         let end_integral = Self::calculate_integral(new_supply, base_price, max_supply)?;
         
-        end_integral.checked_sub(start_integral).ok_or(ErrorCode::Underflow.into())
+        end_integral
+            .checked_sub(start_integral)
+            .ok_or_else(|| math_err!(ErrorCode::Underflow, end_integral, "-", start_integral))
     }
 
     /// Calculate the price for selling tokens using a bonding curve
@@ -43,7 +61,9 @@ impl MathUtils {
         let start_integral = Self::calculate_integral(new_supply, base_price, max_supply)?;
         let end_integral = Self::calculate_integral(current_supply, base_price, max_supply)?;
         
-        end_integral.checked_sub(start_integral).ok_or(ErrorCode::Underflow.into())
+        end_integral
+            .checked_sub(start_integral)
+            .ok_or_else(|| math_err!(ErrorCode::Underflow, end_integral, "-", start_integral))
     }
 
     /// Calculate the integral of the bonding curve up to a given supply
@@ -54,14 +74,17 @@ impl MathUtils {
         }
 
         // Use fixed-point arithmetic to avoid floating point operations
-        const PRECISION: u64 = 1_000_000;
-        
-        let supply_scaled = supply.checked_mul(PRECISION).ok_or(ErrorCode::Overflow)?;
-        let max_supply_scaled = max_supply.checked_mul(PRECISION).ok_or(ErrorCode::Overflow)?;
+        let supply_scaled = supply
+            .checked_mul(PRECISION)
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, supply, "*", PRECISION))?;
+        let max_supply_scaled = max_supply
+            .checked_mul(PRECISION)
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, max_supply, "*", PRECISION))?;
         
         // Calculate (1 + supply/max_supply)^3 / 3 - 1/3
-        let ratio = supply_scaled.checked_div(max_supply_scaled).ok_or(ErrorCode::DivisionByZero)?;
-        let one_plus_ratio = PRECISION.checked_add(ratio).ok_or(ErrorCode::Overflow)?;
+        let ratio = supply_scaled
+            .checked_div(max_supply_scaled)
+            .ok_or_else(|| math_err!(ErrorCode::DivisionByZero, supply_scaled, "/", max_supply_scaled))?;
         
         // Approximate (1 + ratio)^3 using binomial expansion for better precision
         let ratio_squared = ratio.checked_mul(ratio).ok_or(ErrorCode::Overflow)?
@@ -83,18 +106,18 @@ impl MathUtils {
             .checked_div(3)
             .ok_or(ErrorCode::DivisionByZero)?;
         
-        let result = base_price
+        let base_value = base_price
             .checked_mul(supply)
-            .ok_or(ErrorCode::Overflow)?
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, base_price, "*", supply))?;
+        let result = base_value
             .checked_mul(integral_scaled)
-            .ok_or(ErrorCode::Overflow)?
-            .checked_div(PRECISION)
-            .ok_or(ErrorCode::DivisionByZero)?;
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, base_value, "*", integral_scaled))?
+            / PRECISION;
         
         Ok(result)
     }
 
-    /// Calculate the current price per token at a given supply level
+    /// Calculate the current price per token at a given supply level, in the units of `base_price`
     pub fn calculate_current_price(
         current_supply: u64,
         base_price: u64,
@@ -106,31 +129,26 @@ impl MathUtils {
             return Err(ErrorCode::DivisionByZero.into());
         }
 
-        const PRECISION: u64 = 1_000_000;
-        
         let supply_ratio = current_supply
             .checked_mul(PRECISION)
-            .ok_or(ErrorCode::Overflow)?
-            .checked_div(max_supply)
-            .ok_or(ErrorCode::DivisionByZero)?;
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, current_supply, "*", PRECISION))?
+            / max_supply;
         
         let one_plus_ratio = PRECISION.checked_add(supply_ratio).ok_or(ErrorCode::Overflow)?;
         let ratio_squared = one_plus_ratio
             .checked_mul(one_plus_ratio)
-            .ok_or(ErrorCode::Overflow)?
-            .checked_div(PRECISION)
-            .ok_or(ErrorCode::DivisionByZero)?;
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, one_plus_ratio, "*", one_plus_ratio))?
+            / PRECISION;
         
         let current_price = base_price
             .checked_mul(ratio_squared)
-            .ok_or(ErrorCode::Overflow)?
-            .checked_div(PRECISION)
-            .ok_or(ErrorCode::DivisionByZero)?;
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, base_price, "*", ratio_squared))?
+            / PRECISION;
         
         Ok(current_price)
     }
 
-    /// Calculate slippage for a given trade
+    /// Calculate slippage for a given trade, in basis points of `expected_price`
     pub fn calculate_slippage(
         expected_price: u64,
         actual_price: u64,
@@ -139,12 +157,10 @@ impl MathUtils {
             return Err(ErrorCode::DivisionByZero.into());
         }
 
-        const PRECISION: u64 = 10_000; // 0.01% precision
-        
         if actual_price > expected_price {
             let difference = actual_price.checked_sub(expected_price).unwrap();
             let slippage = difference
-                .checked_mul(PRECISION)
+                .checked_mul(BPS_DIVISOR)
                 .ok_or(ErrorCode::Overflow)?
                 .checked_div(expected_price)
                 .ok_or(ErrorCode::DivisionByZero)?;
@@ -152,7 +168,7 @@ impl MathUtils {
         } else {
             let difference = expected_price.checked_sub(actual_price).unwrap();
             let slippage = difference
-                .checked_mul(PRECISION)
+                .checked_mul(BPS_DIVISOR)
                 .ok_or(ErrorCode::Overflow)?
                 .checked_div(expected_price)
                 .ok_or(ErrorCode::DivisionByZero)?;
@@ -160,19 +176,30 @@ impl MathUtils {
         }
     }
 
-    /// Calculate trading fees
+    /// Calculate trading fees, in the units of `amount`
     pub fn calculate_fee(amount: u64, fee_basis_points: u64) -> Result<u64> {
-        const BASIS_POINTS_DIVISOR: u64 = 10_000;
-        
         let fee = amount
             .checked_mul(fee_basis_points)
-            .ok_or(ErrorCode::Overflow)?
-            .checked_div(BASIS_POINTS_DIVISOR)
-            .ok_or(ErrorCode::DivisionByZero)?;
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, amount, "*", fee_basis_points))?
+            / BPS_DIVISOR;
         
         Ok(fee)
     }
 
+    /// Calculate trading fees, rounding any fractional unit up in the protocol's favor
+    pub fn calculate_fee_rounded_up(amount: u64, fee_basis_points: u64) -> Result<u64> {
+        let numerator = amount
+            .checked_mul(fee_basis_points)
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, amount, "*", fee_basis_points))?;
+
+        let fee = numerator
+            .checked_add(BPS_DIVISOR - 1)
+            .ok_or_else(|| math_err!(ErrorCode::Overflow, numerator, "+", BPS_DIVISOR - 1))?
+            / BPS_DIVISOR;
+
+        Ok(fee)
+    }
+
     /// Calculate the maximum tokens that can be bought with a given amount of SOL
     pub fn calculate_max_tokens_for_sol(
         sol_amount: u64,
@@ -217,6 +244,25 @@ impl MathUtils {
         Ok(result)
     }
 
+    /// Integer square root, rounded down, using Newton's method
+    pub fn isqrt(n: u128) -> u128 {
+        if n < 2 {
+            return n;
+        }
+
+        // Start from a power of two at or above the root so the iteration only descends
+        let bits = 128 - n.leading_zeros();
+        let mut x = 1u128 << ((bits + 1) / 2);
+
+        loop {
+            let y = (x + n / x) / 2;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
     /// Validate that a price calculation is within acceptable bounds
     pub fn validate_price_bounds(
         price: u64,
@@ -284,5 +330,89 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 100);
     }
+
+    #[test]
+    fn test_calculate_fee_rounded_up() {
+        // 1% of 10_050 is 100.5: truncation gives 100, rounding up gives 101
+        assert_eq!(MathUtils::calculate_fee(10_050, 100).unwrap(), 100);
+        assert_eq!(MathUtils::calculate_fee_rounded_up(10_050, 100).unwrap(), 101);
+
+        // 1% of 99 is 0.99: truncation collects nothing
+        assert_eq!(MathUtils::calculate_fee(99, 100).unwrap(), 0);
+        assert_eq!(MathUtils::calculate_fee_rounded_up(99, 100).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_calculate_fee_rounded_up_exact() {
+        // Exact multiples round the same way in both modes
+        assert_eq!(MathUtils::calculate_fee_rounded_up(10_000, 100).unwrap(), 100);
+        assert_eq!(MathUtils::calculate_fee_rounded_up(0, 100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_isqrt_small_values() {
+        assert_eq!(MathUtils::isqrt(0), 0);
+        assert_eq!(MathUtils::isqrt(1), 1);
+        assert_eq!(MathUtils::isqrt(2), 1);
+        assert_eq!(MathUtils::isqrt(3), 1);
+        assert_eq!(MathUtils::isqrt(4), 2);
+    }
+
+    #[test]
+    fn test_isqrt_perfect_squares() {
+        for root in [5u128, 10, 1_000, 1_000_000, u32::MAX as u128, u64::MAX as u128] {
+            assert_eq!(MathUtils::isqrt(root * root), root);
+            assert_eq!(MathUtils::isqrt(root * root - 1), root - 1);
+        }
+    }
+
+    #[test]
+    fn test_isqrt_floor_property() {
+        for n in 0u128..10_000 {
+            let root = MathUtils::isqrt(n);
+            assert!(root * root <= n);
+            assert!((root + 1) * (root + 1) > n);
+        }
+    }
+
+    #[test]
+    fn test_isqrt_max() {
+        assert_eq!(MathUtils::isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(MathUtils::isqrt(1u128 << 127), 13_043_817_825_332_782_212);
+    }
+
+    #[test]
+    fn test_slippage_and_price_agree_on_scale() {
+        let base_price = 1_000;
+        let max_supply = 10_000;
+
+        // Price runs from base_price at zero supply to 4x base_price at max supply
+        let start = MathUtils::calculate_current_price(0, base_price, max_supply).unwrap();
+        let end = MathUtils::calculate_current_price(max_supply, base_price, max_supply).unwrap();
+        assert_eq!(start, base_price);
+        assert_eq!(end, 4 * base_price);
+
+        // A 4x move is +300%, i.e. three whole BPS_DIVISORs
+        assert_eq!(MathUtils::calculate_slippage(start, end).unwrap(), 3 * BPS_DIVISOR);
+        assert_eq!(MathUtils::calculate_fee(end, BPS_DIVISOR).unwrap(), end);
+    }
+
+    #[test]
+    fn test_overflow_variants_unchanged_by_logging() {
+        let overflow: Error = ErrorCode::Overflow.into();
+
+        assert_eq!(MathUtils::calculate_fee(u64::MAX, 2).unwrap_err(), overflow);
+        assert_eq!(MathUtils::calculate_fee_rounded_up(u64::MAX, 2).unwrap_err(), overflow);
+        assert_eq!(MathUtils::calculate_fee_rounded_up(u64::MAX, 1).unwrap_err(), overflow);
+        assert_eq!(MathUtils::calculate_current_price(u64::MAX, 1, u64::MAX).unwrap_err(), overflow);
+    }
+
+    #[test]
+    fn test_division_by_zero_variant_unchanged_by_logging() {
+        let division_by_zero: Error = ErrorCode::DivisionByZero.into();
+
+        assert_eq!(MathUtils::calculate_current_price(0, 1, 0).unwrap_err(), division_by_zero);
+        assert_eq!(MathUtils::calculate_slippage(0, 1).unwrap_err(), division_by_zero);
+    }
 }
 ```
\ No newline at end of file
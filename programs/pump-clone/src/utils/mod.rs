@@ -0,0 +1,3 @@
+```rust
+pub mod math;
+```
\ No newline at end of file
@@ -0,0 +1,1694 @@
+```rust
+//! End-to-end tests driving the program through its entrypoint with solana-program-test
+
+use anchor_lang::{
+    solana_program::{account_info::AccountInfo, entrypoint::ProgramResult},
+    error::ERROR_CODE_OFFSET,
+    AccountDeserialize, AccountSerialize, AnchorDeserialize, Discriminator, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address, metadata::mpl_token_metadata,
+    token::TokenAccount,
+};
+use mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use pump_clone::errors::PumpError;
+use pump_clone::instructions::{
+    PoolSwapEvent, TokenCreated, TokenGraduatedToPoolEvent, TokenPurchaseEvent, TokenSellEvent,
+    VaultsReconciledEvent,
+};
+use pump_clone::state::{
+    AmmPool, AuthorityStats, BondingCurve, Config, DepthScaling, GraduationMode, OrderFilled, ReservesUpdatedEvent, RewardAccount,
+    UpdateConfigArgs,
+    DEFAULT_MAX_CREATOR_FEE_BPS, DEFAULT_MIN_MARKET_DURATION, DEFAULT_REWARD_VESTING_PERIOD, HOLD_REBATE_TIERS,
+    MAX_REFERRAL_LEVELS, MAX_RESOLUTION_REWARD_BPS, REFERRER_SHARE_BPS,
+};
+use pump_clone::{Market, MarketVoidedEvent};
+use solana_program::program_pack::Pack;
+use solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    ed25519_program,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_stubs::{self, SyscallStubs},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+
+use std::sync::Once;
+
+const TOKEN_NAME: &str = "Test Token";
+
+/// Anchor's entrypoint ties the account slice to the accounts' own lifetime
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    pump_clone::entry(program_id, accounts, data)
+}
+
+/// Stands in for the token metadata program, which program-test doesn't ship. Logs the
+/// requested mutability so tests can check what create_token asked for.
+fn metadata_stub(_: &Pubkey, _: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // One discriminator byte precedes the arguments, which mpl-token-metadata still
+    // encodes with borsh 0.9
+    #[allow(deprecated)]
+    let args: CreateMetadataAccountV3InstructionArgs =
+        solana_program::borsh0_9::try_from_slice_unchecked(&data[1..])?;
+    solana_program::msg!("Metadata is_mutable: {}", args.is_mutable);
+    Ok(())
+}
+
+/// program-test's syscall stubs print `sol_log_data` to stdout, so natively run programs
+/// never get their events into the transaction logs. This forwards everything else and logs
+/// event data the way the runtime does.
+struct EventLogStubs(Box<dyn SyscallStubs>);
+
+impl SyscallStubs for EventLogStubs {
+    fn sol_log(&self, message: &str) {
+        self.0.sol_log(message)
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields.iter().map(|field| STANDARD.encode(field)).collect();
+        self.0.sol_log(&format!("Program data: {}", fields.join(" ")))
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.0.sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_clock_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_epoch_rewards_sysvar(var_addr)
+    }
+
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_fees_sysvar(var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_rent_sysvar(var_addr)
+    }
+
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_last_restart_slot(var_addr)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.0.sol_get_return_data()
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.0.sol_set_return_data(data)
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        self.0.sol_get_stack_height()
+    }
+}
+
+fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new("pump_clone", pump_clone::ID, processor!(process_instruction));
+    program_test.add_program("mpl_token_metadata", mpl_token_metadata::ID, processor!(metadata_stub));
+    program_test
+}
+
+/// Starts the test validator with events logged; program-test installs its stubs on first start
+async fn start() -> ProgramTestContext {
+    static LOG_EVENTS: Once = Once::new();
+
+    let context = program_test().start_with_context().await;
+    LOG_EVENTS.call_once(|| {
+        let stubs = program_stubs::set_syscall_stubs(Box::new(EventLogStubs(Box::new(NoStubs))));
+        program_stubs::set_syscall_stubs(Box::new(EventLogStubs(stubs)));
+    });
+    context
+}
+
+/// Placeholder while swapping program-test's stubs for the wrapper
+struct NoStubs;
+
+impl SyscallStubs for NoStubs {}
+
+fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &pump_clone::ID).0
+}
+
+async fn send(banks_client: &mut BanksClient, payer: &Keypair, instruction: Instruction) {
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction =
+        Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[payer], blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+}
+
+/// Sends `instruction` signed by `signer` and returns the program logs
+async fn send_logged(banks_client: &mut BanksClient, signer: &Keypair, instruction: Instruction) -> Vec<String> {
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction =
+        Transaction::new_signed_with_payer(&[instruction], Some(&signer.pubkey()), &[signer], blockhash);
+    let result = banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+    result.result.unwrap();
+    result.metadata.unwrap().log_messages
+}
+
+/// Decodes every `E` the transaction emitted
+fn events<E: Discriminator + AnchorDeserialize>(logs: &[String]) -> Vec<E> {
+    logs.iter()
+        .filter_map(|log| log.split_once("Program data: ").map(|(_, data)| data))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter(|data| data.starts_with(&E::DISCRIMINATOR))
+        .map(|data| E::deserialize(&mut &data[8..]).unwrap())
+        .collect()
+}
+
+async fn fetch_config(banks_client: &mut BanksClient) -> Config {
+    let account = banks_client.get_account(config_address()).await.unwrap().unwrap();
+    Config::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+async fn fetch_bonding_curve(banks_client: &mut BanksClient, bonding_curve: Pubkey) -> BondingCurve {
+    let account = banks_client.get_account(bonding_curve).await.unwrap().unwrap();
+    BondingCurve::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+async fn token_balance(banks_client: &mut BanksClient, token_account: Pubkey) -> u64 {
+    let account = banks_client.get_account(token_account).await.unwrap().unwrap();
+    TokenAccount::try_deserialize(&mut account.data.as_slice()).unwrap().amount
+}
+
+async fn lamports(banks_client: &mut BanksClient, address: Pubkey) -> u64 {
+    banks_client.get_balance(address).await.unwrap()
+}
+
+/// Addresses of one launched token
+struct Launch {
+    mint: Pubkey,
+    bonding_curve: Pubkey,
+    bonding_curve_token_account: Pubkey,
+    sol_vault: Pubkey,
+}
+
+impl Launch {
+    fn new(creator: &Pubkey) -> Self {
+        let mint = Pubkey::find_program_address(
+            &[b"mint", creator.as_ref(), TOKEN_NAME.as_bytes()],
+            &pump_clone::ID,
+        )
+        .0;
+        let bonding_curve = Pubkey::find_program_address(&[b"bonding_curve", mint.as_ref()], &pump_clone::ID).0;
+        Self {
+            mint,
+            bonding_curve,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, &mint),
+            sol_vault: Pubkey::find_program_address(&[b"sol_vault", bonding_curve.as_ref()], &pump_clone::ID).0,
+        }
+    }
+
+    fn holder_stats(&self, wallet: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"holder_stats", self.mint.as_ref(), wallet.as_ref()],
+            &pump_clone::ID,
+        )
+        .0
+    }
+}
+
+fn initialize_config_ix(admin: &Pubkey, max_markets_per_authority: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::InitializeConfig {
+            config: config_address(),
+            admin: *admin,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::InitializeConfig { max_markets_per_authority }.data(),
+    }
+}
+
+#[tokio::test]
+async fn test_initialize_config_sets_defaults() {
+    let (mut banks_client, payer, _) = program_test().start().await;
+
+    send(&mut banks_client, &payer, initialize_config_ix(&payer.pubkey(), 5)).await;
+
+    let config = fetch_config(&mut banks_client).await;
+    assert_eq!(config.admin, payer.pubkey());
+    assert_eq!(config.max_markets_per_authority, 5);
+    assert_eq!(config.max_creator_fee_bps, DEFAULT_MAX_CREATOR_FEE_BPS);
+    assert_eq!(config.referral_levels, 1);
+    assert!(!config.unique_token_names);
+}
+
+#[tokio::test]
+async fn test_update_config_applies_only_given_fields() {
+    let (mut banks_client, payer, _) = program_test().start().await;
+    send(&mut banks_client, &payer, initialize_config_ix(&payer.pubkey(), 5)).await;
+
+    let update = Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::UpdateConfig {
+            config: config_address(),
+            admin: payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::UpdateConfig {
+            args: UpdateConfigArgs {
+                referral_levels: Some(u8::MAX),
+                unique_token_names: Some(true),
+                ..Default::default()
+            },
+        }
+        .data(),
+    };
+    send(&mut banks_client, &payer, update).await;
+
+    let config = fetch_config(&mut banks_client).await;
+    assert_eq!(config.max_markets_per_authority, 5);
+    assert_eq!(config.referral_levels, MAX_REFERRAL_LEVELS);
+    assert!(config.unique_token_names);
+}
+
+#[tokio::test]
+async fn test_update_config_rejects_non_admin() {
+    let (mut banks_client, payer, _) = program_test().start().await;
+    send(&mut banks_client, &payer, initialize_config_ix(&payer.pubkey(), 5)).await;
+
+    let impostor = Keypair::new();
+    let update = Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::UpdateConfig {
+            config: config_address(),
+            admin: impostor.pubkey(),
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::UpdateConfig { args: UpdateConfigArgs::default() }.data(),
+    };
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[update],
+        Some(&payer.pubkey()),
+        &[&payer, &impostor],
+        blockhash,
+    );
+    assert!(banks_client.process_transaction(transaction).await.is_err());
+}
+#[tokio::test]
+async fn test_set_treasury() {
+    let (mut banks_client, payer, _) = program_test().start().await;
+    send(&mut banks_client, &payer, initialize_config_ix(&payer.pubkey(), 5)).await;
+    assert_eq!(fetch_config(&mut banks_client).await.treasury, payer.pubkey());
+
+    let treasury = Pubkey::new_unique();
+    let set_treasury = Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::UpdateConfig {
+            config: config_address(),
+            admin: payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::SetTreasury { treasury }.data(),
+    };
+    send(&mut banks_client, &payer, set_treasury).await;
+
+    assert_eq!(fetch_config(&mut banks_client).await.treasury, treasury);
+}
+
+fn create_token_ix(creator: &Pubkey, launch: &Launch) -> Instruction {
+    create_token_with_depth_scaling_ix(creator, launch, None)
+}
+
+fn create_token_with_depth_scaling_ix(
+    creator: &Pubkey,
+    launch: &Launch,
+    depth_scaling: Option<DepthScaling>,
+) -> Instruction {
+    create_token_with_options_ix(creator, launch, depth_scaling, false)
+}
+
+fn create_token_with_options_ix(
+    creator: &Pubkey,
+    launch: &Launch,
+    depth_scaling: Option<DepthScaling>,
+    mutable_metadata: bool,
+) -> Instruction {
+    let metadata = Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), launch.mint.as_ref()],
+        &mpl_token_metadata::ID,
+    )
+    .0;
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CreateToken {
+            mint: launch.mint,
+            bonding_curve: launch.bonding_curve,
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            creator_token_account: get_associated_token_address(creator, &launch.mint),
+            metadata,
+            creator: *creator,
+            config: config_address(),
+            name_registry: None,
+            authority_stats: Pubkey::find_program_address(
+                &[b"authority_stats", creator.as_ref()],
+                &pump_clone::ID,
+            )
+            .0,
+            treasury: None,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::CreateToken {
+            name: TOKEN_NAME.to_string(),
+            symbol: "TEST".to_string(),
+            uri: "https://example.com/token.json".to_string(),
+            self_trade_guard: false,
+            creator_fee_bps: 0,
+            creator_fee_exempt: false,
+            fee_curve: None,
+            mutable_metadata,
+            depth_scaling,
+            trading_start: 0,
+            trading_end: 0,
+            burn_remainder: false,
+            creator_sell_locked_until_graduation: false,
+            graduation_mode: GraduationMode::SolThreshold,
+            royalty_bps: 0,
+            skip_metadata: false,
+        }
+        .data(),
+    }
+}
+
+fn initialize_sol_vault_ix(payer: &Pubkey, launch: &Launch) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::InitializeSolVault {
+            payer: *payer,
+            bonding_curve: launch.bonding_curve,
+            sol_vault: launch.sol_vault,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::InitializeSolVault {}.data(),
+    }
+}
+
+/// Accounts for a plain buy, with every optional account left out
+fn buy_accounts(buyer: &Pubkey, creator: &Pubkey, launch: &Launch) -> pump_clone::accounts::BuyTokens {
+    pump_clone::accounts::BuyTokens {
+        buyer: *buyer,
+        bonding_curve: launch.bonding_curve,
+        token_mint: launch.mint,
+        bonding_curve_token_account: launch.bonding_curve_token_account,
+        buyer_token_account: get_associated_token_address(buyer, &launch.mint),
+        trade_guard: None,
+        holder_stats: launch.holder_stats(buyer),
+        order_receipt: None,
+        sol_vault: launch.sol_vault,
+        creator: *creator,
+        config: config_address(),
+        buyer_referral: None,
+        referrer_referral: None,
+        referrer: None,
+        grandparent_referrer: None,
+        fee_token_account: None,
+        treasury_fee_token_account: None,
+        token_program: anchor_spl::token::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    }
+}
+
+fn buy_tokens_ix(buyer: &Pubkey, creator: &Pubkey, launch: &Launch, sol_amount: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: buy_accounts(buyer, creator, launch).to_account_metas(None),
+        data: pump_clone::instruction::BuyTokens {
+            sol_amount,
+            client_order_id: None,
+            clamp_to_graduation: false,
+        }
+        .data(),
+    }
+}
+
+fn sell_tokens_ix(seller: &Pubkey, creator: &Pubkey, launch: &Launch, token_amount: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::SellTokens {
+            seller: *seller,
+            bonding_curve: launch.bonding_curve,
+            token_mint: launch.mint,
+            seller_token_account: get_associated_token_address(seller, &launch.mint),
+            trade_guard: None,
+            holder_stats: launch.holder_stats(seller),
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            sol_vault: launch.sol_vault,
+            creator: *creator,
+            config: config_address(),
+            floor_vault: None,
+            creator_token_account: None,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::SellTokens { token_amount, close_ata: false }.data(),
+    }
+}
+
+/// Initializes the config, launches a token with `payer` as creator and opens its SOL vault
+async fn launch(banks_client: &mut BanksClient, payer: &Keypair) -> Launch {
+    send(banks_client, payer, initialize_config_ix(&payer.pubkey(), 5)).await;
+    let launch = Launch::new(&payer.pubkey());
+    send(banks_client, payer, create_token_ix(&payer.pubkey(), &launch)).await;
+    send(banks_client, payer, initialize_sol_vault_ix(&payer.pubkey(), &launch)).await;
+    launch
+}
+
+/// A new wallet holding `lamports`
+async fn funded_wallet(banks_client: &mut BanksClient, payer: &Keypair, lamports: u64) -> Keypair {
+    let wallet = Keypair::new();
+    send(
+        banks_client,
+        payer,
+        system_instruction::transfer(&payer.pubkey(), &wallet.pubkey(), lamports),
+    )
+    .await;
+    wallet
+}
+
+#[tokio::test]
+async fn test_create_token_splits_supply_between_creator_and_curve() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    send(&mut banks_client, &payer, initialize_config_ix(&payer.pubkey(), 5)).await;
+
+    let launch = Launch::new(&payer.pubkey());
+    let logs = send_logged(
+        &mut banks_client,
+        &payer,
+        create_token_ix(&payer.pubkey(), &launch),
+    )
+    .await;
+
+    let curve = fetch_bonding_curve(&mut banks_client, launch.bonding_curve).await;
+    assert_eq!(curve.mint, launch.mint);
+    assert_eq!(curve.creator, payer.pubkey());
+    assert_eq!(curve.real_sol_reserves, 0);
+    assert_eq!(curve.real_token_reserves, curve.token_total_supply / 5 * 4);
+    assert!(!curve.sol_vault_initialized);
+
+    let creator_token_account = get_associated_token_address(&payer.pubkey(), &launch.mint);
+    assert_eq!(token_balance(&mut banks_client, creator_token_account).await, curve.token_total_supply / 5);
+    assert_eq!(
+        token_balance(&mut banks_client, launch.bonding_curve_token_account).await,
+        curve.real_token_reserves
+    );
+
+    let created = events::<TokenCreated>(&logs);
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].mint, launch.mint);
+    assert_eq!(created[0].real_token_reserves, curve.real_token_reserves);
+}
+
+#[tokio::test]
+async fn test_metadata_immutable_unless_creator_opts_in() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    send(&mut banks_client, &payer, initialize_config_ix(&payer.pubkey(), 5)).await;
+
+    let locked = Launch::new(&payer.pubkey());
+    let logs = send_logged(&mut banks_client, &payer, create_token_ix(&payer.pubkey(), &locked)).await;
+    assert!(logs.iter().any(|log| log.ends_with("Metadata is_mutable: false")));
+
+    let creator = funded_wallet(&mut banks_client, &payer, 10_000_000_000).await;
+    let editable = Launch::new(&creator.pubkey());
+    let logs = send_logged(
+        &mut banks_client,
+        &creator,
+        create_token_with_options_ix(&creator.pubkey(), &editable, None, true),
+    )
+    .await;
+    assert!(logs.iter().any(|log| log.ends_with("Metadata is_mutable: true")));
+}
+
+#[tokio::test]
+async fn test_create_token_retry_fails_once_launched() {
+    let mut context = start().await;
+    let launch = launch(&mut context.banks_client, &context.payer).await;
+
+    // A fresh blockhash so the retry isn't deduplicated as the same transaction
+    context.warp_to_slot(100).unwrap();
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let retry = Transaction::new_signed_with_payer(
+        &[create_token_ix(&context.payer.pubkey(), &launch)],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+    let err = context.banks_client.process_transaction(retry).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + PumpError::TokenAlreadyExists as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_buy_moves_sol_into_vault_and_tokens_to_buyer() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    let launch = launch(&mut banks_client, &payer).await;
+    let buyer = funded_wallet(&mut banks_client, &payer, 10_000_000_000).await;
+    let vault_before = lamports(&mut banks_client, launch.sol_vault).await;
+
+    let logs = send_logged(
+        &mut banks_client,
+        &buyer,
+        buy_tokens_ix(&buyer.pubkey(), &payer.pubkey(), &launch, 1_000_000_000),
+    )
+    .await;
+
+    let purchases = events::<TokenPurchaseEvent>(&logs);
+    assert_eq!(purchases.len(), 1);
+    let purchase = &purchases[0];
+    assert_eq!(purchase.buyer, buyer.pubkey());
+    assert!(purchase.token_amount > 0);
+
+    let curve = fetch_bonding_curve(&mut banks_client, launch.bonding_curve).await;
+    assert_eq!(curve.real_sol_reserves, purchase.curve_sol_amount);
+    assert_eq!(curve.virtual_sol_reserves, purchase.new_sol_reserves);
+    assert_eq!(curve.virtual_token_reserves, purchase.new_token_reserves);
+    assert_eq!(curve.holder_count, 1);
+    assert_eq!(
+        lamports(&mut banks_client, launch.sol_vault).await - vault_before,
+        purchase.curve_sol_amount
+    );
+
+    // The curve's first trade; all of its events share one sequence number
+    assert_eq!(purchase.sequence, 1);
+    assert_eq!(events::<ReservesUpdatedEvent>(&logs)[0].sequence, purchase.sequence);
+    assert_eq!(curve.sequence, purchase.sequence);
+
+    let buyer_token_account = get_associated_token_address(&buyer.pubkey(), &launch.mint);
+    assert_eq!(token_balance(&mut banks_client, buyer_token_account).await, purchase.token_amount);
+}
+
+#[tokio::test]
+async fn test_sell_pays_seller_out_of_vault() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    let launch = launch(&mut banks_client, &payer).await;
+    let trader = funded_wallet(&mut banks_client, &payer, 10_000_000_000).await;
+    let buy_logs = send_logged(
+        &mut banks_client,
+        &trader,
+        buy_tokens_ix(&trader.pubkey(), &payer.pubkey(), &launch, 1_000_000_000),
+    )
+    .await;
+    let bought = events::<TokenPurchaseEvent>(&buy_logs)[0].token_amount;
+
+    let vault_before = lamports(&mut banks_client, launch.sol_vault).await;
+    let curve_before = fetch_bonding_curve(&mut banks_client, launch.bonding_curve).await;
+    let logs = send_logged(
+        &mut banks_client,
+        &trader,
+        sell_tokens_ix(&trader.pubkey(), &payer.pubkey(), &launch, bought / 2),
+    )
+    .await;
+
+    let sells = events::<TokenSellEvent>(&logs);
+    assert_eq!(sells.len(), 1);
+    let sell = &sells[0];
+    assert_eq!(sell.seller, trader.pubkey());
+    assert_eq!(sell.token_amount, bought / 2);
+    assert!(sell.sol_amount > 0);
+
+    let curve = fetch_bonding_curve(&mut banks_client, launch.bonding_curve).await;
+    assert_eq!(curve.real_sol_reserves, curve_before.real_sol_reserves - sell.sol_amount);
+    assert_eq!(curve.real_token_reserves, curve_before.real_token_reserves + bought / 2);
+    assert_eq!(vault_before - lamports(&mut banks_client, launch.sol_vault).await, sell.sol_amount);
+
+    let trader_token_account = get_associated_token_address(&trader.pubkey(), &launch.mint);
+    assert_eq!(token_balance(&mut banks_client, trader_token_account).await, bought - bought / 2);
+    assert_eq!(
+        token_balance(&mut banks_client, launch.bonding_curve_token_account).await,
+        curve.real_token_reserves
+    );
+}
+
+#[tokio::test]
+async fn test_sell_back_after_depth_rescale() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    send(&mut banks_client, &payer, initialize_config_ix(&payer.pubkey(), 5)).await;
+    let launch = Launch::new(&payer.pubkey());
+    let depth_scaling = DepthScaling { interval: 1, multiplier: 2 };
+    send(
+        &mut banks_client,
+        &payer,
+        create_token_with_depth_scaling_ix(&payer.pubkey(), &launch, Some(depth_scaling)),
+    )
+    .await;
+    send(&mut banks_client, &payer, initialize_sol_vault_ix(&payer.pubkey(), &launch)).await;
+
+    let trader = funded_wallet(&mut banks_client, &payer, 20_000_000_000).await;
+    let buy_logs = send_logged(
+        &mut banks_client,
+        &trader,
+        buy_tokens_ix(&trader.pubkey(), &payer.pubkey(), &launch, 10_000_000_000),
+    )
+    .await;
+    let bought = events::<TokenPurchaseEvent>(&buy_logs)[0].token_amount;
+
+    // The buy deepened the token side only
+    let curve = fetch_bonding_curve(&mut banks_client, launch.bonding_curve).await;
+    assert_eq!(
+        curve.virtual_sol_reserves,
+        curve.initial_virtual_sol_reserves + curve.real_sol_reserves
+    );
+    assert!(curve.max_redemption().unwrap() <= curve.real_sol_reserves);
+
+    // Selling it all back, in two steps so the second sell follows another rescale
+    let vault_before = lamports(&mut banks_client, launch.sol_vault).await;
+    let first = send_logged(
+        &mut banks_client,
+        &trader,
+        sell_tokens_ix(&trader.pubkey(), &payer.pubkey(), &launch, bought / 2),
+    )
+    .await;
+    let second = send_logged(
+        &mut banks_client,
+        &trader,
+        sell_tokens_ix(&trader.pubkey(), &payer.pubkey(), &launch, bought - bought / 2),
+    )
+    .await;
+    let paid =
+        events::<TokenSellEvent>(&first)[0].sol_amount + events::<TokenSellEvent>(&second)[0].sol_amount;
+
+    let curve = fetch_bonding_curve(&mut banks_client, launch.bonding_curve).await;
+    assert_eq!(curve.real_token_reserves, curve.initial_real_token_reserves);
+    assert!(paid <= 10_000_000_000);
+    assert_eq!(vault_before - lamports(&mut banks_client, launch.sol_vault).await, paid);
+    let trader_token_account = get_associated_token_address(&trader.pubkey(), &launch.mint);
+    assert_eq!(token_balance(&mut banks_client, trader_token_account).await, 0);
+}
+
+fn reconcile_vaults_ix(admin: &Pubkey, launch: &Launch) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ReconcileVaults {
+            config: config_address(),
+            admin: *admin,
+            bonding_curve: launch.bonding_curve,
+            sol_vault: launch.sol_vault,
+            legacy_sol_vault: Pubkey::find_program_address(&[b"sol_vault"], &pump_clone::ID).0,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ReconcileVaults {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn test_reconcile_vaults_sweeps_legacy_buy_vault() {
+    let mut context = start().await;
+    let launch = launch(&mut context.banks_client, &context.payer).await;
+    let admin = context.payer.pubkey();
+    let trader = funded_wallet(&mut context.banks_client, &context.payer, 10_000_000_000).await;
+    let buy_logs = send_logged(
+        &mut context.banks_client,
+        &trader,
+        buy_tokens_ix(&trader.pubkey(), &admin, &launch, 3_000_000_000),
+    )
+    .await;
+    let bought = events::<TokenPurchaseEvent>(&buy_logs)[0].token_amount;
+    let real_sol_reserves = fetch_bonding_curve(&mut context.banks_client, launch.bonding_curve)
+        .await
+        .real_sol_reserves;
+
+    // Before the seeds were unified the buy paid into the program-wide vault, next to
+    // another curve's 2 SOL, and the curve's own vault only held its rent
+    let rent_minimum = context.banks_client.get_rent().await.unwrap().minimum_balance(0);
+    let legacy_sol_vault = Pubkey::find_program_address(&[b"sol_vault"], &pump_clone::ID).0;
+    let other_curve_sol = 2_000_000_000;
+    let system_account = |lamports| Account { lamports, owner: system_program::ID, ..Account::default() };
+    context.set_account(&launch.sol_vault, &system_account(rent_minimum).into());
+    context.set_account(
+        &legacy_sol_vault,
+        &system_account(rent_minimum + real_sol_reserves + other_curve_sol).into(),
+    );
+
+    // Sells can't be paid out of the stranded vault
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let sell = Transaction::new_signed_with_payer(
+        &[sell_tokens_ix(&trader.pubkey(), &admin, &launch, bought / 2)],
+        Some(&trader.pubkey()),
+        &[&trader],
+        blockhash,
+    );
+    assert!(context.banks_client.process_transaction(sell).await.is_err());
+
+    let logs = send_logged(&mut context.banks_client, &context.payer, reconcile_vaults_ix(&admin, &launch)).await;
+    let reconciled = &events::<VaultsReconciledEvent>(&logs)[0];
+    assert_eq!(reconciled.amount, real_sol_reserves);
+    assert_eq!(reconciled.sol_vault_balance, rent_minimum + real_sol_reserves);
+    assert_eq!(reconciled.legacy_sol_vault_balance, rent_minimum + other_curve_sol);
+    assert_eq!(lamports(&mut context.banks_client, launch.sol_vault).await, rent_minimum + real_sol_reserves);
+    assert_eq!(lamports(&mut context.banks_client, legacy_sol_vault).await, rent_minimum + other_curve_sol);
+
+    // Nothing is left to move for this curve, so a second sweep is rejected. The trader
+    // pays its fee so it isn't a duplicate of the first transaction.
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let again = Transaction::new_signed_with_payer(
+        &[reconcile_vaults_ix(&admin, &launch)],
+        Some(&trader.pubkey()),
+        &[&trader, &context.payer],
+        blockhash,
+    );
+    let err = context.banks_client.process_transaction(again).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + PumpError::VaultsAlreadyReconciled as u32)
+        )
+    );
+
+    // And the reconciled vault pays sells again
+    let logs = send_logged(
+        &mut context.banks_client,
+        &trader,
+        sell_tokens_ix(&trader.pubkey(), &admin, &launch, bought / 2),
+    )
+    .await;
+    assert_eq!(events::<TokenSellEvent>(&logs).len(), 1);
+}
+
+/// `quote_then_buy` over the same accounts as a plain buy
+fn quote_then_buy_ix(
+    buyer: &Pubkey,
+    creator: &Pubkey,
+    launch: &Launch,
+    sol_amount: u64,
+    quoted_tokens_out: u64,
+) -> Instruction {
+    Instruction {
+        data: pump_clone::instruction::QuoteThenBuy { sol_amount, client_order_id: None, quoted_tokens_out }.data(),
+        ..buy_tokens_ix(buyer, creator, launch, sol_amount)
+    }
+}
+
+/// Tokens a buy of `sol_amount` would fill right now, from simulating it
+async fn quote_buy(
+    banks_client: &mut BanksClient,
+    buyer: &Keypair,
+    creator: &Pubkey,
+    launch: &Launch,
+    sol_amount: u64,
+) -> u64 {
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[buy_tokens_ix(&buyer.pubkey(), creator, launch, sol_amount)],
+        Some(&buyer.pubkey()),
+        &[buyer],
+        blockhash,
+    );
+    let simulation = banks_client.simulate_transaction(transaction).await.unwrap();
+    let logs = simulation.simulation_details.unwrap().logs;
+    events::<TokenPurchaseEvent>(&logs)[0].token_amount
+}
+
+#[tokio::test]
+async fn test_quote_then_buy_rejects_stale_quote() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    let launch = launch(&mut banks_client, &payer).await;
+    let creator = payer.pubkey();
+    let buyer = funded_wallet(&mut banks_client, &payer, 10_000_000_000).await;
+    let other = funded_wallet(&mut banks_client, &payer, 10_000_000_000).await;
+    let quoted = quote_buy(&mut banks_client, &buyer, &creator, &launch, 1_000_000_000).await;
+
+    // Another buy moves the reserves before the quoted buy lands
+    send(&mut banks_client, &other, buy_tokens_ix(&other.pubkey(), &creator, &launch, 2_000_000_000)).await;
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let stale = Transaction::new_signed_with_payer(
+        &[quote_then_buy_ix(&buyer.pubkey(), &creator, &launch, 1_000_000_000, quoted)],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        blockhash,
+    );
+    let err = banks_client.process_transaction(stale).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + PumpError::QuoteNotHonored as u32)
+        )
+    );
+
+    // A fresh quote fills to the token
+    let requoted = quote_buy(&mut banks_client, &buyer, &creator, &launch, 1_000_000_000).await;
+    assert!(requoted < quoted);
+    let logs = send_logged(
+        &mut banks_client,
+        &buyer,
+        quote_then_buy_ix(&buyer.pubkey(), &creator, &launch, 1_000_000_000, requoted),
+    )
+    .await;
+    assert_eq!(events::<TokenPurchaseEvent>(&logs)[0].token_amount, requoted);
+    let buyer_token_account = get_associated_token_address(&buyer.pubkey(), &launch.mint);
+    assert_eq!(token_balance(&mut banks_client, buyer_token_account).await, requoted);
+}
+
+/// A buy carrying `client_order_id`, with its order receipt
+fn buy_order_ix(buyer: &Pubkey, creator: &Pubkey, launch: &Launch, sol_amount: u64, client_order_id: u64) -> Instruction {
+    let order_receipt = Pubkey::find_program_address(
+        &[b"order_receipt", buyer.as_ref(), launch.mint.as_ref(), client_order_id.to_le_bytes().as_ref()],
+        &pump_clone::ID,
+    )
+    .0;
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::BuyTokens {
+            order_receipt: Some(order_receipt),
+            ..buy_accounts(buyer, creator, launch)
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::BuyTokens {
+            sol_amount,
+            client_order_id: Some(client_order_id),
+            clamp_to_graduation: false,
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn test_retried_order_returns_original_fill() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    let launch = launch(&mut banks_client, &payer).await;
+    let creator = payer.pubkey();
+    let buyer = funded_wallet(&mut banks_client, &payer, 10_000_000_000).await;
+    let buyer_token_account = get_associated_token_address(&buyer.pubkey(), &launch.mint);
+
+    let mut fills = Vec::new();
+    // The retry is paid for by `payer` so it isn't the same transaction
+    for fee_payer in [&buyer, &payer] {
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[buy_order_ix(&buyer.pubkey(), &creator, &launch, 1_000_000_000, 7)],
+            Some(&fee_payer.pubkey()),
+            &[fee_payer, &buyer],
+            blockhash,
+        );
+        let result = banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+        result.result.unwrap();
+        let return_data = result.metadata.unwrap().return_data.unwrap();
+        assert_eq!(return_data.program_id, pump_clone::ID);
+        fills.push(OrderFilled::try_from_slice(&return_data.data).unwrap());
+        assert_eq!(token_balance(&mut banks_client, buyer_token_account).await, fills[0].token_amount);
+    }
+
+    assert!(fills[0].token_amount > 0);
+    assert_eq!(fills[1], fills[0]);
+}
+
+/// A trade as an indexer sees it
+enum Trade {
+    Buy(TokenPurchaseEvent),
+    Sell(TokenSellEvent),
+}
+
+#[tokio::test]
+async fn test_trade_events_replay_to_curve_state() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    send(&mut banks_client, &payer, initialize_config_ix(&payer.pubkey(), 5)).await;
+    let launch = Launch::new(&payer.pubkey());
+    let depth_scaling = DepthScaling { interval: 2, multiplier: 2 };
+    send(
+        &mut banks_client,
+        &payer,
+        create_token_with_depth_scaling_ix(&payer.pubkey(), &launch, Some(depth_scaling)),
+    )
+    .await;
+    send(&mut banks_client, &payer, initialize_sol_vault_ix(&payer.pubkey(), &launch)).await;
+    let start = fetch_bonding_curve(&mut banks_client, launch.bonding_curve).await;
+
+    let alice = funded_wallet(&mut banks_client, &payer, 10_000_000_000).await;
+    let bob = funded_wallet(&mut banks_client, &payer, 10_000_000_000).await;
+    let creator = payer.pubkey();
+    let mut trades = Vec::new();
+
+    let logs = send_logged(
+        &mut banks_client,
+        &alice,
+        buy_tokens_ix(&alice.pubkey(), &creator, &launch, 1_000_000_000),
+    )
+    .await;
+    let alice_tokens = events::<TokenPurchaseEvent>(&logs)[0].token_amount;
+    trades.extend(events::<TokenPurchaseEvent>(&logs).into_iter().map(Trade::Buy));
+
+    let logs = send_logged(
+        &mut banks_client,
+        &bob,
+        buy_tokens_ix(&bob.pubkey(), &creator, &launch, 2_000_000_000),
+    )
+    .await;
+    let mut bob_tokens = events::<TokenPurchaseEvent>(&logs)[0].token_amount;
+    trades.extend(events::<TokenPurchaseEvent>(&logs).into_iter().map(Trade::Buy));
+
+    let logs = send_logged(
+        &mut banks_client,
+        &alice,
+        sell_tokens_ix(&alice.pubkey(), &creator, &launch, alice_tokens / 2),
+    )
+    .await;
+    trades.extend(events::<TokenSellEvent>(&logs).into_iter().map(Trade::Sell));
+
+    let logs = send_logged(
+        &mut banks_client,
+        &bob,
+        buy_tokens_ix(&bob.pubkey(), &creator, &launch, 500_000_000),
+    )
+    .await;
+    bob_tokens += events::<TokenPurchaseEvent>(&logs)[0].token_amount;
+    trades.extend(events::<TokenPurchaseEvent>(&logs).into_iter().map(Trade::Buy));
+
+    let logs = send_logged(
+        &mut banks_client,
+        &bob,
+        sell_tokens_ix(&bob.pubkey(), &creator, &launch, bob_tokens),
+    )
+    .await;
+    trades.extend(events::<TokenSellEvent>(&logs).into_iter().map(Trade::Sell));
+
+    assert_eq!(trades.len(), 5);
+    let rescales: Vec<bool> = trades
+        .iter()
+        .map(|trade| match trade {
+            Trade::Buy(e) => e.depth_rescaled,
+            Trade::Sell(e) => e.depth_rescaled,
+        })
+        .collect();
+    assert_eq!(rescales, [false, true, false, true, false]);
+
+    // Rebuild the reserves from the launch state and each event's deltas alone,
+    // checking every event's post-trade snapshot against the replay so far
+    let multiplier = depth_scaling.multiplier as u64;
+    let (mut virtual_sol, mut virtual_token) = (start.virtual_sol_reserves, start.virtual_token_reserves);
+    let (mut real_sol, mut real_token) = (start.real_sol_reserves, start.real_token_reserves);
+    for trade in &trades {
+        let (snapshot, depth_rescaled) = match trade {
+            Trade::Buy(e) => {
+                virtual_sol += e.curve_sol_amount;
+                virtual_token -= e.token_amount;
+                real_sol += e.curve_sol_amount;
+                real_token -= e.token_amount;
+                let snapshot = (e.new_sol_reserves, e.new_token_reserves, e.real_sol_reserves, e.real_token_reserves);
+                (snapshot, e.depth_rescaled)
+            }
+            Trade::Sell(e) => {
+                virtual_sol -= e.sol_amount;
+                virtual_token += e.token_amount;
+                real_sol -= e.sol_amount;
+                real_token += e.token_amount;
+                let snapshot =
+                    (e.virtual_sol_reserves, e.virtual_token_reserves, e.real_sol_reserves, e.real_token_reserves);
+                (snapshot, e.depth_rescaled)
+            }
+        };
+        if depth_rescaled {
+            virtual_token *= multiplier;
+        }
+        assert_eq!(snapshot, (virtual_sol, virtual_token, real_sol, real_token));
+    }
+
+    let curve = fetch_bonding_curve(&mut banks_client, launch.bonding_curve).await;
+    assert_eq!(
+        (curve.virtual_sol_reserves, curve.virtual_token_reserves, curve.real_sol_reserves, curve.real_token_reserves),
+        (virtual_sol, virtual_token, real_sol, real_token)
+    );
+}
+
+fn update_config_ix(admin: &Pubkey, args: UpdateConfigArgs) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::UpdateConfig {
+            config: config_address(),
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::UpdateConfig { args }.data(),
+    }
+}
+
+#[tokio::test]
+async fn test_sell_after_long_hold_pays_rebated_fee() {
+    let mut context = start().await;
+    let launch = launch(&mut context.banks_client, &context.payer).await;
+    let admin = context.payer.pubkey();
+    send(
+        &mut context.banks_client,
+        &context.payer,
+        update_config_ix(&admin, UpdateConfigArgs { hold_rebate_enabled: Some(true), ..Default::default() }),
+    )
+    .await;
+
+    let trader = funded_wallet(&mut context.banks_client, &context.payer, 10_000_000_000).await;
+    let buy_logs = send_logged(
+        &mut context.banks_client,
+        &trader,
+        buy_tokens_ix(&trader.pubkey(), &admin, &launch, 1_000_000_000),
+    )
+    .await;
+    let bought = events::<TokenPurchaseEvent>(&buy_logs)[0].token_amount;
+
+    // Hold into the top rebate tier
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += HOLD_REBATE_TIERS[0].0;
+    context.set_sysvar(&clock);
+
+    let curve = fetch_bonding_curve(&mut context.banks_client, launch.bonding_curve).await;
+    let logs = send_logged(
+        &mut context.banks_client,
+        &trader,
+        sell_tokens_ix(&trader.pubkey(), &admin, &launch, bought),
+    )
+    .await;
+    let sell = &events::<TokenSellEvent>(&logs)[0];
+
+    let (vtr, vsr) = (curve.virtual_token_reserves as u128, curve.virtual_sol_reserves as u128);
+    let gross = (vsr - vtr * vsr / (vtr + bought as u128)) as u64;
+    let rebated_fee_bps = curve.trade_fee_bps().unwrap() * (10_000 - HOLD_REBATE_TIERS[0].1 as u64) / 10_000;
+    assert_eq!(gross - sell.sol_amount, gross * rebated_fee_bps / 10_000);
+    assert!(rebated_fee_bps < curve.trade_fee_bps().unwrap());
+}
+
+/// A 6-decimal mint owned by `payer`, with `amount` minted to `holder` and a token account for `payer`
+async fn funded_mint(banks_client: &mut BanksClient, payer: &Keypair, holder: &Pubkey, amount: u64) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &payer.pubkey(), None, 6).unwrap(),
+        create_associated_token_account_idempotent(&payer.pubkey(), holder, &mint.pubkey(), &spl_token::ID),
+        create_associated_token_account_idempotent(&payer.pubkey(), &payer.pubkey(), &mint.pubkey(), &spl_token::ID),
+        spl_token::instruction::mint_to(
+            &spl_token::ID,
+            &mint.pubkey(),
+            &get_associated_token_address(holder, &mint.pubkey()),
+            &payer.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap(),
+    ];
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[payer, &mint], blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+    mint.pubkey()
+}
+
+#[tokio::test]
+async fn test_partner_fee_buy_pays_referrer_and_discounted_fee() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    let launch = launch(&mut banks_client, &payer).await;
+    let buyer = funded_wallet(&mut banks_client, &payer, 10_000_000_000).await;
+    let referrer = funded_wallet(&mut banks_client, &payer, 1_000_000_000).await;
+    let mint = funded_mint(&mut banks_client, &payer, &buyer.pubkey(), 1_000_000_000).await;
+
+    // The config admin is also the treasury
+    send(
+        &mut banks_client,
+        &payer,
+        update_config_ix(
+            &payer.pubkey(),
+            UpdateConfigArgs {
+                partner_fee_mint: Some(mint),
+                partner_fee_rate: Some(50_000_000),
+                partner_fee_discount_bps: Some(2_000),
+                ..Default::default()
+            },
+        ),
+    )
+    .await;
+    let referral_account = Pubkey::find_program_address(&[b"referral", buyer.pubkey().as_ref()], &pump_clone::ID).0;
+    send(
+        &mut banks_client,
+        &buyer,
+        Instruction {
+            program_id: pump_clone::ID,
+            accounts: pump_clone::accounts::RegisterReferral {
+                owner: buyer.pubkey(),
+                referral_account,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: pump_clone::instruction::RegisterReferral { referrer: referrer.pubkey() }.data(),
+        },
+    )
+    .await;
+
+    let sol_amount = 1_000_000_000;
+    let config = fetch_config(&mut banks_client).await;
+    let curve = fetch_bonding_curve(&mut banks_client, launch.bonding_curve).await;
+    let fee_bps = curve
+        .impact_adjusted_fee_bps(curve.trade_fee_bps().unwrap(), sol_amount, config.impact_fee_coefficient)
+        .unwrap();
+    let referrer_before = lamports(&mut banks_client, referrer.pubkey()).await;
+
+    let mut buy = buy_tokens_ix(&buyer.pubkey(), &payer.pubkey(), &launch, sol_amount);
+    buy.accounts = pump_clone::accounts::BuyTokens {
+        buyer_referral: Some(referral_account),
+        referrer: Some(referrer.pubkey()),
+        fee_token_account: Some(get_associated_token_address(&buyer.pubkey(), &mint)),
+        treasury_fee_token_account: Some(get_associated_token_address(&payer.pubkey(), &mint)),
+        ..buy_accounts(&buyer.pubkey(), &payer.pubkey(), &launch)
+    }
+    .to_account_metas(None);
+    let logs = send_logged(&mut banks_client, &buyer, buy).await;
+    let purchase = &events::<TokenPurchaseEvent>(&logs)[0];
+
+    // The referrer keeps its SOL share of the trade fee; the rest is charged in the partner token
+    let trade_fee = (sol_amount - purchase.creator_fee) * fee_bps / 10_000;
+    let referrer_fee = trade_fee * REFERRER_SHARE_BPS / 10_000;
+    assert!(referrer_fee > 0);
+    assert_eq!(lamports(&mut banks_client, referrer.pubkey()).await - referrer_before, referrer_fee);
+    assert_eq!(purchase.curve_sol_amount, sol_amount - purchase.creator_fee - referrer_fee);
+
+    let partner_fee = config.partner_fee_amount(trade_fee - referrer_fee).unwrap();
+    assert_eq!(partner_fee, (trade_fee - referrer_fee) * 50_000_000 / 1_000_000_000 * 8_000 / 10_000);
+    let treasury_fee_token_account = get_associated_token_address(&payer.pubkey(), &mint);
+    assert_eq!(token_balance(&mut banks_client, treasury_fee_token_account).await, partner_fee);
+}
+fn amm_pool_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"amm_pool", mint.as_ref()], &pump_clone::ID).0
+}
+
+fn pool_vault_address(amm_pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"pool_vault", amm_pool.as_ref()], &pump_clone::ID).0
+}
+
+fn swap_accounts(user: &Pubkey, launch: &Launch) -> Vec<AccountMeta> {
+    let amm_pool = amm_pool_address(&launch.mint);
+    pump_clone::accounts::Swap {
+        user: *user,
+        amm_pool,
+        token_mint: launch.mint,
+        pool_token_vault: pool_vault_address(&amm_pool),
+        user_token_account: get_associated_token_address(user, &launch.mint),
+        token_program: anchor_spl::token::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None)
+}
+
+/// Launches a token on a 2 SOL threshold that graduates into the program-owned pool
+async fn pool_launch(context: &mut ProgramTestContext) -> Launch {
+    let launch = launch(&mut context.banks_client, &context.payer).await;
+    let creator = context.payer.pubkey();
+    send(
+        &mut context.banks_client,
+        &context.payer,
+        update_config_ix(&creator, UpdateConfigArgs { internal_pool_graduation: Some(true), ..Default::default() }),
+    )
+    .await;
+    let set_threshold = Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::SetGraduationThreshold {
+            creator,
+            bonding_curve: launch.bonding_curve,
+            config: config_address(),
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::SetGraduationThreshold { graduation_threshold: 2_000_000_000 }.data(),
+    };
+    send(&mut context.banks_client, &context.payer, set_threshold).await;
+    launch
+}
+
+/// Launches a token on a 2 SOL threshold and buys it past graduation
+async fn graduated_launch(context: &mut ProgramTestContext) -> (Launch, Keypair) {
+    let launch = pool_launch(context).await;
+    let trader = funded_wallet(&mut context.banks_client, &context.payer, 10_000_000_000).await;
+    send(
+        &mut context.banks_client,
+        &trader,
+        buy_tokens_ix(&trader.pubkey(), &context.payer.pubkey(), &launch, 3_000_000_000),
+    )
+    .await;
+    (launch, trader)
+}
+
+fn graduate_to_pool_ix(caller: &Pubkey, launch: &Launch) -> Instruction {
+    let amm_pool = amm_pool_address(&launch.mint);
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::GraduateToPool {
+            caller: *caller,
+            bonding_curve: launch.bonding_curve,
+            token_mint: launch.mint,
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            sol_vault: launch.sol_vault,
+            amm_pool,
+            pool_token_vault: pool_vault_address(&amm_pool),
+            config: config_address(),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::GraduateToPool {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn test_graduate_to_pool_moves_curve_reserves_into_pool() {
+    let mut context = start().await;
+    let (launch, trader) = graduated_launch(&mut context).await;
+    let curve_before = fetch_bonding_curve(&mut context.banks_client, launch.bonding_curve).await;
+    assert!(curve_before.complete);
+    let vault_before = lamports(&mut context.banks_client, launch.sol_vault).await;
+
+    let amm_pool = amm_pool_address(&launch.mint);
+    let logs = send_logged(&mut context.banks_client, &trader, graduate_to_pool_ix(&trader.pubkey(), &launch)).await;
+
+    let graduated = events::<TokenGraduatedToPoolEvent>(&logs);
+    assert_eq!(graduated.len(), 1);
+    assert_eq!(graduated[0].sol_liquidity, curve_before.real_sol_reserves);
+    assert_eq!(graduated[0].token_liquidity, curve_before.real_token_reserves);
+
+    let account = context.banks_client.get_account(amm_pool).await.unwrap().unwrap();
+    let pool = AmmPool::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(pool.sol_reserves, curve_before.real_sol_reserves);
+    assert_eq!(pool.token_reserves, curve_before.real_token_reserves);
+    assert_eq!(
+        token_balance(&mut context.banks_client, pool_vault_address(&amm_pool)).await,
+        curve_before.real_token_reserves
+    );
+    assert_eq!(
+        vault_before - lamports(&mut context.banks_client, launch.sol_vault).await,
+        curve_before.real_sol_reserves
+    );
+
+    let curve = fetch_bonding_curve(&mut context.banks_client, launch.bonding_curve).await;
+    assert_eq!(curve.real_sol_reserves, 0);
+    assert_eq!(curve.real_token_reserves, 0);
+    assert!(!curve.buys_enabled && !curve.sells_enabled);
+
+    // Trading continues against the pool
+    let buy = Instruction {
+        program_id: pump_clone::ID,
+        accounts: swap_accounts(&trader.pubkey(), &launch),
+        data: pump_clone::instruction::SwapSolForTokens { sol_in: 100_000_000, min_tokens_out: 1 }.data(),
+    };
+    let logs = send_logged(&mut context.banks_client, &trader, buy).await;
+    let swap = &events::<PoolSwapEvent>(&logs)[0];
+    assert!(swap.is_buy);
+    assert_eq!(swap.sol_reserves, pool.sol_reserves + 100_000_000);
+
+    let pool_lamports = lamports(&mut context.banks_client, amm_pool).await;
+    let sell = Instruction {
+        program_id: pump_clone::ID,
+        accounts: swap_accounts(&trader.pubkey(), &launch),
+        data: pump_clone::instruction::SwapTokensForSol { tokens_in: swap.token_amount, min_sol_out: 1 }.data(),
+    };
+    let logs = send_logged(&mut context.banks_client, &trader, sell).await;
+    let swap_back = &events::<PoolSwapEvent>(&logs)[0];
+    assert!(!swap_back.is_buy);
+    assert!(swap_back.sol_amount < 100_000_000);
+    assert_eq!(pool_lamports - lamports(&mut context.banks_client, amm_pool).await, swap_back.sol_amount);
+}
+
+#[tokio::test]
+async fn test_tokens_sold_back_reach_the_pool() {
+    let mut context = start().await;
+    let launch = pool_launch(&mut context).await;
+    let creator = context.payer.pubkey();
+    let trader = funded_wallet(&mut context.banks_client, &context.payer, 10_000_000_000).await;
+    let trader_token_account = get_associated_token_address(&trader.pubkey(), &launch.mint);
+
+    send(&mut context.banks_client, &trader, buy_tokens_ix(&trader.pubkey(), &creator, &launch, 1_000_000_000)).await;
+    let bought = token_balance(&mut context.banks_client, trader_token_account).await;
+    send(&mut context.banks_client, &trader, sell_tokens_ix(&trader.pubkey(), &creator, &launch, bought / 2)).await;
+    send(&mut context.banks_client, &trader, buy_tokens_ix(&trader.pubkey(), &creator, &launch, 3_000_000_000)).await;
+
+    let curve = fetch_bonding_curve(&mut context.banks_client, launch.bonding_curve).await;
+    assert!(curve.complete);
+    assert_eq!(
+        token_balance(&mut context.banks_client, launch.bonding_curve_token_account).await,
+        curve.real_token_reserves
+    );
+
+    let logs = send_logged(&mut context.banks_client, &trader, graduate_to_pool_ix(&trader.pubkey(), &launch)).await;
+    let graduated = &events::<TokenGraduatedToPoolEvent>(&logs)[0];
+    assert_eq!(graduated.token_liquidity, curve.real_token_reserves);
+    let amm_pool = amm_pool_address(&launch.mint);
+    assert_eq!(
+        token_balance(&mut context.banks_client, pool_vault_address(&amm_pool)).await,
+        curve.real_token_reserves
+    );
+    assert_eq!(token_balance(&mut context.banks_client, launch.bonding_curve_token_account).await, 0);
+}
+
+fn claim_rewards_ix(owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ClaimRewards {
+            owner: *owner,
+            reward_account: Pubkey::find_program_address(&[b"reward", owner.as_ref()], &pump_clone::ID).0,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ClaimRewards {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn test_graduation_reward_claimable_only_once_vested() {
+    let mut context = start().await;
+    let payer = context.payer.insecure_clone();
+    let owner = funded_wallet(&mut context.banks_client, &payer, 1_000_000_000).await;
+
+    // A graduation reward of 0.5 SOL accrued just now
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let vests_at = clock.unix_timestamp + DEFAULT_REWARD_VESTING_PERIOD;
+    let reward = 500_000_000;
+    let (reward_account, bump) = Pubkey::find_program_address(&[b"reward", owner.pubkey().as_ref()], &pump_clone::ID);
+    let mut data = Vec::new();
+    RewardAccount { owner: owner.pubkey(), accrued: reward, vests_at, bump }
+        .try_serialize(&mut data)
+        .unwrap();
+    let rent_minimum = context.banks_client.get_rent().await.unwrap().minimum_balance(RewardAccount::LEN);
+    context.set_account(
+        &reward_account,
+        &Account { lamports: rent_minimum + reward, data, owner: pump_clone::ID, ..Account::default() }.into(),
+    );
+
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let early = Transaction::new_signed_with_payer(
+        &[claim_rewards_ix(&owner.pubkey())],
+        Some(&owner.pubkey()),
+        &[&owner],
+        blockhash,
+    );
+    let err = context.banks_client.process_transaction(early).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ERROR_CODE_OFFSET + PumpError::RewardNotVested as u32)
+        )
+    );
+
+    let mut clock = clock;
+    clock.unix_timestamp = vests_at;
+    context.set_sysvar(&clock);
+    let owner_before = lamports(&mut context.banks_client, owner.pubkey()).await;
+    // `payer` covers the fee, so the owner's balance moves by the reward alone
+    let vested = Transaction::new_signed_with_payer(
+        &[claim_rewards_ix(&owner.pubkey())],
+        Some(&payer.pubkey()),
+        &[&payer, &owner],
+        blockhash,
+    );
+    context.banks_client.process_transaction(vested).await.unwrap();
+
+    assert_eq!(lamports(&mut context.banks_client, owner.pubkey()).await - owner_before, reward);
+    assert_eq!(lamports(&mut context.banks_client, reward_account).await, rent_minimum);
+    let account = context.banks_client.get_account(reward_account).await.unwrap().unwrap();
+    assert_eq!(RewardAccount::try_deserialize(&mut account.data.as_slice()).unwrap().accrued, 0);
+}
+
+const MARKET_LIQUIDITY: u64 = 10_000_000;
+
+fn market_address(market_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"market", market_id.to_le_bytes().as_ref()], &pump_clone::ID).0
+}
+
+fn market_vault_address(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", market.as_ref()], &pump_clone::ID).0
+}
+
+async fn fetch_market(banks_client: &mut BanksClient, market: Pubkey) -> Market {
+    let account = banks_client.get_account(market).await.unwrap().unwrap();
+    Market::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// Opens market `market_id` in `mint`, authored by `payer` and resolvable by `oracle`, and
+/// returns its end time
+fn authority_stats_address(authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"authority_stats", authority.as_ref()], &pump_clone::ID).0
+}
+
+async fn open_market(banks_client: &mut BanksClient, payer: &Keypair, mint: &Pubkey, market_id: u64, oracle: &Pubkey) -> i64 {
+    let clock: Clock = banks_client.get_sysvar().await.unwrap();
+    let end_time = clock.unix_timestamp + DEFAULT_MIN_MARKET_DURATION;
+    let market = market_address(market_id);
+    let initialize_market = Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::InitializeMarket {
+            market,
+            market_vault: market_vault_address(&market),
+            config: config_address(),
+            authority_stats: authority_stats_address(&payer.pubkey()),
+            authority: payer.pubkey(),
+            authority_token_account: get_associated_token_address(&payer.pubkey(), mint),
+            mint: *mint,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::InitializeMarket {
+            market_id,
+            question: "Will it rain?".to_string(),
+            description: String::new(),
+            end_time,
+            initial_liquidity: MARKET_LIQUIDITY,
+            min_price: 100_000,
+            max_price: 900_000,
+            oracle: *oracle,
+            max_shares_per_user: 0,
+        }
+        .data(),
+    };
+    send(banks_client, payer, initialize_market).await;
+    end_time
+}
+
+fn buy_shares_ix(user: &Pubkey, mint: &Pubkey, market_id: u64, amount: u64) -> Instruction {
+    let market = market_address(market_id);
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::BuyShares {
+            market,
+            user_position: Pubkey::find_program_address(&[b"position", market.as_ref(), user.as_ref()], &pump_clone::ID).0,
+            user: *user,
+            user_token_account: get_associated_token_address(user, mint),
+            market_vault: market_vault_address(&market),
+            mint: *mint,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::BuyShares { amount, is_yes: true, max_cost: u64::MAX, adjust: false }.data(),
+    }
+}
+
+/// The oracle's ed25519 signature over `outcome`, followed by `resolve_with_signature` from `resolver`
+fn oracle_resolution_ixs(
+    oracle: &Keypair,
+    resolver: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    market_id: u64,
+    outcome: bool,
+    timestamp: i64,
+) -> [Instruction; 2] {
+    let mut message = market_id.to_le_bytes().to_vec();
+    message.push(outcome as u8);
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    let signature = oracle.sign_message(&message);
+
+    // One signature, with the public key, signature and message all inline
+    let mut data = vec![1, 0];
+    for offset in [48, u16::MAX, 16, u16::MAX, 112, message.len() as u16, u16::MAX] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(oracle.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(&message);
+    let verify = Instruction { program_id: ed25519_program::ID, accounts: vec![], data };
+
+    let market = market_address(market_id);
+    let resolve = Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ResolveWithSignature {
+            market,
+            config: config_address(),
+            resolver: *resolver,
+            resolver_token_account: get_associated_token_address(resolver, mint),
+            authority_token_account: get_associated_token_address(authority, mint),
+            market_vault: market_vault_address(&market),
+            instructions: sysvar::instructions::ID,
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ResolveWithSignature { outcome, timestamp }.data(),
+    };
+    [verify, resolve]
+}
+
+#[tokio::test]
+async fn test_oracle_resolution_pays_resolver_reward() {
+    let mut context = start().await;
+    let payer = context.payer.insecure_clone();
+    let admin = payer.pubkey();
+    send(&mut context.banks_client, &payer, initialize_config_ix(&admin, 5)).await;
+    send(
+        &mut context.banks_client,
+        &payer,
+        update_config_ix(&admin, UpdateConfigArgs { resolution_reward_bps: Some(MAX_RESOLUTION_REWARD_BPS), ..Default::default() }),
+    )
+    .await;
+    let mint = funded_mint(&mut context.banks_client, &payer, &admin, 1_000_000_000).await;
+    let oracle = Keypair::new();
+    let end_time = open_market(&mut context.banks_client, &payer, &mint, 1, &oracle.pubkey()).await;
+    send(&mut context.banks_client, &payer, buy_shares_ix(&admin, &mint, 1, 1_000_000)).await;
+
+    // Anyone holding the oracle's signature can resolve, and is paid for it
+    let resolver = Keypair::new();
+    send(
+        &mut context.banks_client,
+        &payer,
+        create_associated_token_account_idempotent(&admin, &resolver.pubkey(), &mint, &spl_token::ID),
+    )
+    .await;
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = end_time + 1;
+    context.set_sysvar(&clock);
+
+    let liquidity = fetch_market(&mut context.banks_client, market_address(1)).await.total_liquidity;
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &oracle_resolution_ixs(&oracle, &resolver.pubkey(), &admin, &mint, 1, true, clock.unix_timestamp),
+        Some(&admin),
+        &[&payer, &resolver],
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await.unwrap();
+
+    let reward = liquidity * MAX_RESOLUTION_REWARD_BPS as u64 / 10_000;
+    assert!(reward > 0);
+    let resolver_token_account = get_associated_token_address(&resolver.pubkey(), &mint);
+    assert_eq!(token_balance(&mut context.banks_client, resolver_token_account).await, reward);
+    let market = fetch_market(&mut context.banks_client, market_address(1)).await;
+    assert_eq!(market.outcome, Some(true));
+    assert_eq!(market.total_liquidity, liquidity - reward);
+}
+
+#[tokio::test]
+async fn test_untraded_market_resolved_by_oracle_is_voided() {
+    let mut context = start().await;
+    let payer = context.payer.insecure_clone();
+    let admin = payer.pubkey();
+    send(&mut context.banks_client, &payer, initialize_config_ix(&admin, 5)).await;
+    let mint = funded_mint(&mut context.banks_client, &payer, &admin, 1_000_000_000).await;
+    let oracle = Keypair::new();
+    let end_time = open_market(&mut context.banks_client, &payer, &mint, 1, &oracle.pubkey()).await;
+    let authority_token_account = get_associated_token_address(&admin, &mint);
+    let balance_before = token_balance(&mut context.banks_client, authority_token_account).await;
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = end_time + 1;
+    context.set_sysvar(&clock);
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &oracle_resolution_ixs(&oracle, &admin, &admin, &mint, 1, true, clock.unix_timestamp),
+        Some(&admin),
+        &[&payer],
+        blockhash,
+    );
+    let result = context.banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+    result.result.unwrap();
+    let logs = result.metadata.unwrap().log_messages;
+
+    // Voided with no outcome, and the seed liquidity goes back to the authority
+    let voided = events::<MarketVoidedEvent>(&logs);
+    assert_eq!(voided.len(), 1);
+    assert_eq!(voided[0].refund, MARKET_LIQUIDITY);
+    let market = fetch_market(&mut context.banks_client, market_address(1)).await;
+    assert!(market.resolved);
+    assert_eq!(market.outcome, None);
+    assert_eq!(
+        token_balance(&mut context.banks_client, authority_token_account).await,
+        balance_before + MARKET_LIQUIDITY
+    );
+}
+
+fn cancel_market_ix(authority: &Pubkey, mint: &Pubkey, market_id: u64) -> Instruction {
+    let market = market_address(market_id);
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CancelMarket {
+            market,
+            config: config_address(),
+            authority: *authority,
+            authority_stats: authority_stats_address(authority),
+            authority_token_account: get_associated_token_address(authority, mint),
+            market_vault: market_vault_address(&market),
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::CancelMarket {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn test_cancelled_market_frees_the_authority_quota() {
+    let ProgramTestContext { mut banks_client, payer, .. } = start().await;
+    let authority = payer.pubkey();
+    send(&mut banks_client, &payer, initialize_config_ix(&authority, 1)).await;
+    let mint = funded_mint(&mut banks_client, &payer, &authority, 1_000_000_000).await;
+    let oracle = Pubkey::new_unique();
+    open_market(&mut banks_client, &payer, &mint, 1, &oracle).await;
+
+    send(&mut banks_client, &payer, cancel_market_ix(&authority, &mint, 1)).await;
+    let account = banks_client.get_account(authority_stats_address(&authority)).await.unwrap().unwrap();
+    let stats = AuthorityStats::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(stats.markets_created, 0);
+
+    // The one-market quota is free again
+    open_market(&mut banks_client, &payer, &mint, 2, &oracle).await;
+    assert_eq!(fetch_market(&mut banks_client, market_address(2)).await.authority, authority);
+}
+```
\ No newline at end of file